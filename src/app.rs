@@ -0,0 +1,1795 @@
+//! eframe::App implementation: the floating widget's state and render loop.
+
+use eframe::egui;
+use tokio::sync::mpsc;
+
+use crate::audio::MicTestStatus;
+use crate::config::{
+    parse_hex_color, AppPaths, AppSettings, CorrectionStyle, DomainOverride, InjectMethod,
+    OperatingMode, Theme, VadBackend,
+};
+use crate::history::export::{self, ExportFormat};
+use crate::history::stats::DictationStats;
+use crate::history::{HistoryEntry, HistoryStore};
+use crate::hotkey::HotkeyStatus;
+use crate::inject::copy_to_clipboard;
+use crate::pipeline::{PipelineCommand, PipelineError, PipelineResult, SharedSettings};
+use crate::stt::Segment;
+
+/// How many past transcriptions the history panel shows at once.
+const HISTORY_PANEL_SIZE: usize = 20;
+/// Segments below this average token probability are highlighted as
+/// worth double-checking.
+const LOW_CONFIDENCE_THRESHOLD: f32 = 0.5;
+
+/// UI-only mirror of `config::DomainOverride`, plus the "no override" case,
+/// for the settings panel's `ComboBox`. Collapsed back into
+/// `Option<DomainOverride>` in `save_settings_draft`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DomainOverrideChoice {
+    Auto,
+    Locked,
+    Disabled,
+}
+
+/// Editable, string-backed copy of the fields exposed in the settings panel.
+/// Kept separate from `AppSettings` so invalid in-progress edits (e.g. a
+/// half-typed number) don't corrupt the live settings until Save is pressed.
+struct SettingsDraft {
+    operating_mode: OperatingMode,
+    stt_model: String,
+    llm_model: String,
+    llm_base_url: String,
+    push_to_talk_key: String,
+    vad_backend: VadBackend,
+    vad_threshold: String,
+    noise_suppression: bool,
+    /// Pre-roll duration in seconds, as text (0 disables it).
+    preroll_secs: String,
+    max_recording_secs: String,
+    /// Pipeline watchdog timeout for the STT stage, as text (seconds).
+    stt_timeout_secs: String,
+    inject_method: InjectMethod,
+    /// Pipeline watchdog timeout for the inject stage, as text (seconds).
+    inject_timeout_secs: String,
+    use_gpu: bool,
+    /// Record per-word timestamps for the karaoke review mode in the
+    /// history panel. See `stt::engine::WordTiming`.
+    stt_word_timestamps: bool,
+    /// Offload transcription to a remote whisper.cpp/OpenAI-compatible
+    /// server instead of the local model. The API key isn't editable here —
+    /// like `llm_api_key`, it's set directly in `settings.toml` (or the OS
+    /// keychain, once migrated) rather than round-tripped through the UI.
+    stt_remote_enabled: bool,
+    stt_remote_url: String,
+    /// Offload transcription to a self-hosted Vosk server instead.
+    /// Takes priority over `stt_remote_enabled` if both are checked.
+    stt_vosk_enabled: bool,
+    stt_vosk_url: String,
+    /// Label segments as one of two speakers for two-party dictation. See
+    /// `stt::diarize`.
+    stt_diarization_enabled: bool,
+    /// Daily token cap for cloud LLM providers, as text (0 = unlimited).
+    llm_daily_token_budget: String,
+    /// Alternate prompt for restructuring the corrected text. See
+    /// `CorrectionStyle`.
+    llm_correction_style: CorrectionStyle,
+    /// See `DomainOverrideChoice`. Collapsed into
+    /// `AppSettings::domain_override` on save.
+    domain_override_choice: DomainOverrideChoice,
+    /// Domain name for `domain_override_choice == Locked`. Ignored
+    /// otherwise.
+    domain_lock_name: String,
+    /// Result auto-clear timeout in seconds, as text (0 = sticky).
+    result_display_secs: String,
+    theme: Theme,
+    /// Accent color as a 6-digit hex string, live-previewed on every edit.
+    accent_color: String,
+    /// Widget background opacity, as text (0.0-1.0).
+    widget_opacity: String,
+    /// Check GitHub releases for a newer version on startup. See `updater`.
+    check_for_updates: bool,
+    error: Option<String>,
+}
+
+impl From<&AppSettings> for SettingsDraft {
+    fn from(s: &AppSettings) -> Self {
+        Self {
+            operating_mode: s.operating_mode,
+            stt_model: s.stt_model.clone(),
+            llm_model: s.llm_model.clone(),
+            llm_base_url: s.llm_base_url.clone(),
+            push_to_talk_key: s.push_to_talk_key.clone(),
+            vad_backend: s.vad_backend,
+            vad_threshold: s.vad_threshold.to_string(),
+            noise_suppression: s.noise_suppression,
+            preroll_secs: s.preroll_secs.to_string(),
+            max_recording_secs: s.max_recording_secs.to_string(),
+            stt_timeout_secs: s.stt_timeout_secs.to_string(),
+            inject_method: s.inject_method,
+            inject_timeout_secs: s.inject_timeout_secs.to_string(),
+            use_gpu: s.use_gpu,
+            stt_word_timestamps: s.stt_word_timestamps,
+            stt_remote_enabled: s.stt_remote_enabled,
+            stt_remote_url: s.stt_remote_url.clone(),
+            stt_vosk_enabled: s.stt_vosk_enabled,
+            stt_vosk_url: s.stt_vosk_url.clone(),
+            stt_diarization_enabled: s.stt_diarization_enabled,
+            llm_daily_token_budget: s.llm_daily_token_budget.to_string(),
+            llm_correction_style: s.llm_correction_style,
+            domain_override_choice: match &s.domain_override {
+                None => DomainOverrideChoice::Auto,
+                Some(DomainOverride::Locked(_)) => DomainOverrideChoice::Locked,
+                Some(DomainOverride::Disabled) => DomainOverrideChoice::Disabled,
+            },
+            domain_lock_name: match &s.domain_override {
+                Some(DomainOverride::Locked(name)) => name.clone(),
+                _ => String::new(),
+            },
+            result_display_secs: s.result_display_secs.to_string(),
+            theme: s.theme,
+            accent_color: s.accent_color.clone(),
+            widget_opacity: s.widget_opacity.to_string(),
+            check_for_updates: s.check_for_updates,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineState {
+    Idle,
+    Recording,
+    Paused,
+    Transcribing { progress: f32 },
+    Correcting,
+    Injecting,
+    Error { message: String },
+}
+
+pub struct ThaiSttApp {
+    pipeline_state: PipelineState,
+    raw_text: Option<String>,
+    corrected_text: Option<String>,
+    processing_time: Option<f32>,
+
+    show_settings: bool,
+    settings_draft: Option<SettingsDraft>,
+    show_history: bool,
+    history_entries: Vec<HistoryEntry>,
+    history_selected: std::collections::HashSet<usize>,
+    export_status: Option<String>,
+    show_stats: bool,
+    stats: DictationStats,
+    /// Last-known LLM correction cache hit/miss counters, refreshed whenever
+    /// the stats panel is opened.
+    cache_stats: crate::llm::CorrectionCacheStats,
+    show_snippets: bool,
+    /// Raw `trigger = "expansion"` TOML lines, edited in place and written
+    /// straight to `snippets.toml` on Save — same shape the file is stored
+    /// in, so there's nothing to translate back and forth.
+    snippets_draft: Option<String>,
+    snippets_error: Option<String>,
+    /// Names of every saved `config::profiles` profile, refreshed whenever
+    /// the settings panel is opened.
+    profile_names: Vec<String>,
+    /// Profile picked in the switcher dropdown, defaulting to the first
+    /// available profile.
+    selected_profile: String,
+    /// Name typed into "Save current as", cleared after a successful save.
+    new_profile_name: String,
+    profile_status: Option<String>,
+    waveform: Vec<f32>,
+    stt_backend: Option<String>,
+    segments: Vec<Segment>,
+    /// Growing prefix of the in-flight LLM correction, shown in the
+    /// Correcting view as it streams in instead of a bare spinner.
+    correction_partial: Option<String>,
+    /// Last-known reachability of each configured LLM provider, refreshed
+    /// on demand from the settings panel.
+    llm_provider_status: Vec<(String, bool)>,
+    /// Result of the last `PipelineCommand::CheckForUpdate`, driven by
+    /// `AppSettings::check_for_updates`. Read by the title bar and settings
+    /// panel. See `updater`.
+    update_status: crate::updater::UpdateStatus,
+    /// Set when `PipelineResult::LlmBudgetExceeded` fires, and shown until
+    /// the user dismisses it or the operating mode is changed manually.
+    budget_warning: Option<String>,
+    /// Editable copy of `corrected_text`, always shown in the Result state.
+    /// Injecting (via Enter or the Inject button) diffs it against
+    /// `corrected_text` and learns the changed words if it was edited.
+    result_edit_buffer: Option<String>,
+    /// Set alongside `result_edit_buffer` whenever a new correction arrives,
+    /// so `draw_result` requests keyboard focus for it exactly once instead
+    /// of stealing focus back every frame the result stays on screen.
+    result_edit_focus_pending: bool,
+    /// When `corrected_text` was shown, for `settings.result_display_secs`
+    /// auto-clear. `None` while nothing is displayed.
+    result_shown_at: Option<std::time::Instant>,
+    /// Post-processed text awaiting a manual Inject click, set when
+    /// `settings.auto_inject` is false. `None` once injected or cleared.
+    pending_inject: Option<String>,
+    /// Whether the Result/Correcting views show the raw-vs-corrected diff
+    /// instead of (or alongside) the plain text. Persists across results
+    /// until the user toggles it off.
+    show_diff: bool,
+    /// Set after the first `update()` frame, once the restored
+    /// `widget_position` has been validated against actual monitor geometry.
+    placement_checked: bool,
+    /// Current window visibility, toggled by `HotkeyEvent::ToggleVisibility`.
+    /// Recording and transcription keep running while hidden — only the
+    /// widget's own window is affected.
+    visible: bool,
+    /// Fed directly by the hotkey listener bridge, bypassing the pipeline
+    /// entirely since visibility is a window concern, not a pipeline one.
+    visibility_rx: mpsc::Receiver<()>,
+    /// Live (peak, RMS) mic level, updated by the capture callback whether
+    /// or not a recording is in progress. Read each frame while the
+    /// settings panel's input meter is visible.
+    input_level: crate::audio::SharedInputLevel,
+    /// Set while a "record 3s and play back" mic test is running.
+    mic_test_status: crate::audio::SharedMicTestStatus,
+    /// History entry index and playback handle for an in-progress karaoke
+    /// review (`draw_history`'s Play button). `None` when nothing is
+    /// playing. Only one entry can play at a time — starting another stops
+    /// this one first.
+    karaoke_playback: Option<(usize, crate::audio::PlaybackHandle)>,
+    /// Owns the cpal input stream, so it lives exactly as long as the app
+    /// (the UI thread) and can be torn down explicitly in `on_exit` instead
+    /// of relying on it getting dropped on abrupt process exit.
+    audio_capture: crate::audio::AudioCapture,
+    /// Set by the hotkey listener thread and its self-test timer. Read by
+    /// the settings panel to warn about a silently-dead listener (missing
+    /// Accessibility permission on macOS, or an unsupported Wayland
+    /// session) instead of leaving the user to wonder why the hotkey does
+    /// nothing.
+    hotkey_status: crate::hotkey::SharedHotkeyStatus,
+    /// Asks `main`'s hotkey bridge to spawn a fresh listener thread, e.g.
+    /// after the user grants Accessibility permission and clicks "Restart".
+    restart_hotkey_tx: mpsc::Sender<()>,
+    /// Loaded once at startup from `pipeline::recovery::load()`. `Some` means
+    /// the previous run crashed (or injection failed) after STT completed,
+    /// and the title bar offers to copy/inject/dismiss the lost text.
+    recovered_dictation: Option<crate::pipeline::recovery::RecoveryState>,
+
+    /// Shared with `PipelineOrchestrator` so a settings change from either
+    /// side (a saved settings panel edit, or a pipeline-driven change like
+    /// `LlmBudgetExceeded`'s forced Fast mode) is immediately visible to
+    /// the other without a `ConfigReloaded` round-trip.
+    settings: SharedSettings,
+
+    command_tx: mpsc::Sender<PipelineCommand>,
+    result_rx: mpsc::Receiver<PipelineResult>,
+}
+
+impl ThaiSttApp {
+    pub fn new(
+        settings: SharedSettings,
+        command_tx: mpsc::Sender<PipelineCommand>,
+        result_rx: mpsc::Receiver<PipelineResult>,
+        visibility_rx: mpsc::Receiver<()>,
+        input_level: crate::audio::SharedInputLevel,
+        audio_capture: crate::audio::AudioCapture,
+        hotkey_status: crate::hotkey::SharedHotkeyStatus,
+        restart_hotkey_tx: mpsc::Sender<()>,
+    ) -> Self {
+        Self {
+            pipeline_state: PipelineState::Idle,
+            raw_text: None,
+            corrected_text: None,
+            processing_time: None,
+            show_settings: false,
+            settings_draft: None,
+            show_history: false,
+            history_entries: Vec::new(),
+            history_selected: std::collections::HashSet::new(),
+            export_status: None,
+            show_stats: false,
+            stats: DictationStats::default(),
+            cache_stats: crate::llm::CorrectionCacheStats::default(),
+            show_snippets: false,
+            snippets_draft: None,
+            snippets_error: None,
+            profile_names: Vec::new(),
+            selected_profile: String::new(),
+            new_profile_name: String::new(),
+            profile_status: None,
+            waveform: Vec::new(),
+            stt_backend: None,
+            segments: Vec::new(),
+            correction_partial: None,
+            llm_provider_status: Vec::new(),
+            update_status: crate::updater::UpdateStatus::Idle,
+            budget_warning: None,
+            result_edit_buffer: None,
+            result_edit_focus_pending: false,
+            result_shown_at: None,
+            pending_inject: None,
+            show_diff: false,
+            placement_checked: false,
+            visible: true,
+            visibility_rx,
+            input_level,
+            mic_test_status: crate::audio::mic_test::new_status(),
+            karaoke_playback: None,
+            audio_capture,
+            hotkey_status,
+            restart_hotkey_tx,
+            recovered_dictation: crate::pipeline::recovery::load(),
+            settings,
+            command_tx,
+            result_rx,
+        }
+    }
+
+    /// Drains pending toggle-visibility hotkey presses and flips the window's
+    /// actual OS-level visibility to match.
+    fn poll_visibility_toggle(&mut self, ctx: &egui::Context) {
+        let mut toggled = false;
+        while self.visibility_rx.try_recv().is_ok() {
+            toggled = !toggled;
+        }
+        if toggled {
+            self.visible = !self.visible;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Visible(self.visible));
+        }
+    }
+
+    fn poll_results(&mut self) {
+        while let Ok(result) = self.result_rx.try_recv() {
+            match result {
+                PipelineResult::RecordingStarted => {
+                    self.pipeline_state = PipelineState::Recording;
+                    self.raw_text = None;
+                    self.corrected_text = None;
+                    self.segments.clear();
+                    self.result_edit_buffer = None;
+                    self.result_edit_focus_pending = false;
+                    self.correction_partial = None;
+                    self.result_shown_at = None;
+                    self.pending_inject = None;
+                }
+                PipelineResult::RecordingStopped { .. } => {
+                    self.pipeline_state = PipelineState::Transcribing { progress: 0.0 };
+                    self.waveform.clear();
+                }
+                PipelineResult::RecordingPaused => {
+                    self.pipeline_state = PipelineState::Paused;
+                }
+                PipelineResult::RecordingResumed => {
+                    self.pipeline_state = PipelineState::Recording;
+                }
+                PipelineResult::PartialTranscription(text) => {
+                    self.raw_text = Some(text);
+                }
+                PipelineResult::WaveformUpdate(bars) => {
+                    self.waveform = bars;
+                }
+                PipelineResult::EngineReady { stt_backend } => {
+                    self.stt_backend = Some(stt_backend);
+                }
+                PipelineResult::LlmProviderStatus(statuses) => {
+                    self.llm_provider_status = statuses;
+                }
+                PipelineResult::CacheStats(stats) => {
+                    self.cache_stats = stats;
+                }
+                PipelineResult::UpdateStatus(status) => {
+                    self.update_status = status;
+                }
+                PipelineResult::LlmBudgetExceeded => {
+                    // The pipeline already flipped the shared `operating_mode`
+                    // to `Fast` before sending this — just surface the banner.
+                    self.budget_warning =
+                        Some("Daily LLM token budget reached — switched to Fast mode.".to_string());
+                }
+                PipelineResult::TranscriptionComplete(t) => {
+                    self.raw_text = Some(t.raw_text);
+                    self.segments = t.segments;
+                    self.correction_partial = None;
+                    self.pipeline_state = PipelineState::Correcting;
+                }
+                PipelineResult::CorrectionPartial { text } => {
+                    self.correction_partial = Some(text);
+                }
+                PipelineResult::CorrectionComplete(c) => {
+                    self.result_edit_buffer = Some(c.corrected_text.clone());
+                    self.result_edit_focus_pending = true;
+                    self.corrected_text = Some(c.corrected_text);
+                    self.correction_partial = None;
+                    self.result_shown_at = Some(std::time::Instant::now());
+                    self.pipeline_state = PipelineState::Injecting;
+                }
+                PipelineResult::InjectionPending(text) => {
+                    self.pending_inject = Some(text);
+                    self.pipeline_state = PipelineState::Idle;
+                }
+                PipelineResult::InjectionComplete => {
+                    self.pending_inject = None;
+                    self.pipeline_state = PipelineState::Idle;
+                }
+                PipelineResult::ConfigReloaded => {
+                    // The pipeline already reloaded into the shared settings
+                    // before sending this — nothing left to do here.
+                }
+                PipelineResult::Cancelled => {
+                    self.pipeline_state = PipelineState::Idle;
+                    self.raw_text = None;
+                    self.corrected_text = None;
+                    self.waveform.clear();
+                    self.segments.clear();
+                    self.result_edit_buffer = None;
+                    self.result_edit_focus_pending = false;
+                    self.correction_partial = None;
+                    self.result_shown_at = None;
+                    self.pending_inject = None;
+                }
+                PipelineResult::Error(e) => {
+                    self.pipeline_state = PipelineState::Error {
+                        message: describe_error(&e),
+                    };
+                }
+            }
+        }
+    }
+
+    fn draw_title_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let icon = match &self.pipeline_state {
+                PipelineState::Idle => "🎤",
+                PipelineState::Recording => "🔴",
+                PipelineState::Paused => "⏸",
+                PipelineState::Transcribing { .. } => "⏳",
+                PipelineState::Correcting => "✨",
+                PipelineState::Injecting => "✅",
+                PipelineState::Error { .. } => "⚠️",
+            };
+            // The window has no OS decorations, so dragging this label is
+            // the only way to move it — clicking starts an OS-level window
+            // drag, and releasing snaps/persists the resulting position.
+            let title = ui.add(
+                egui::Label::new(format!("{} Thai STT", icon)).sense(egui::Sense::click_and_drag()),
+            );
+            if title.drag_started() {
+                ui.ctx().send_viewport_cmd(egui::ViewportCommand::StartDrag);
+            }
+            if title.drag_stopped() {
+                self.snap_and_persist_position(ui.ctx());
+            }
+            if ui.button("🕒").clicked() {
+                self.show_history = !self.show_history;
+                if self.show_history {
+                    self.history_entries = HistoryStore::default().recent(HISTORY_PANEL_SIZE);
+                    self.history_selected.clear();
+                    self.export_status = None;
+                }
+            }
+            if ui.button("📊").clicked() {
+                self.show_stats = !self.show_stats;
+                if self.show_stats {
+                    let now_secs = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or(0);
+                    let entries = HistoryStore::default().load_all();
+                    self.stats = crate::history::stats::compute(&entries, now_secs);
+                    let _ = self.command_tx.try_send(PipelineCommand::CheckCacheStats);
+                }
+            }
+            if ui.button("🔤").clicked() {
+                self.show_snippets = !self.show_snippets;
+                self.snippets_error = None;
+                self.snippets_draft = if self.show_snippets {
+                    Some(std::fs::read_to_string(AppPaths::snippets_path()).unwrap_or_default())
+                } else {
+                    None
+                };
+            }
+            if ui.button("⚙").clicked() {
+                self.show_settings = !self.show_settings;
+                self.settings_draft = if self.show_settings {
+                    self.profile_names = crate::config::profiles::list();
+                    if self.selected_profile.is_empty() {
+                        self.selected_profile =
+                            self.profile_names.first().cloned().unwrap_or_default();
+                    }
+                    self.profile_status = None;
+                    Some(SettingsDraft::from(&*self.settings.read()))
+                } else {
+                    None
+                };
+            }
+        });
+    }
+
+    /// Called once the user releases a title-bar drag. Snaps the window
+    /// flush against a monitor edge when `snap_to_edge` is enabled and the
+    /// drop point is within `snap_margin` pixels of one, then persists the
+    /// resulting position so it's restored on the next launch.
+    fn snap_and_persist_position(&self, ctx: &egui::Context) {
+        let (outer_rect, monitor_size) =
+            ctx.input(|i| (i.viewport().outer_rect, i.viewport().monitor_size));
+        let Some(rect) = outer_rect else {
+            return;
+        };
+
+        let mut candidate = self.settings.read().clone();
+        let mut pos = rect.min;
+
+        if candidate.snap_to_edge {
+            if let Some(monitor) = monitor_size {
+                let margin = candidate.snap_margin;
+                if pos.x < margin {
+                    pos.x = 0.0;
+                }
+                if pos.y < margin {
+                    pos.y = 0.0;
+                }
+                if pos.x + rect.width() > monitor.x - margin {
+                    pos.x = monitor.x - rect.width();
+                }
+                if pos.y + rect.height() > monitor.y - margin {
+                    pos.y = monitor.y - rect.height();
+                }
+                if pos != rect.min {
+                    ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(pos));
+                }
+            }
+        }
+
+        candidate.widget_position = Some((pos.x, pos.y));
+        if candidate.save().is_ok() {
+            *self.settings.write() = candidate;
+        }
+    }
+
+    /// Runs once on the first frame: the saved `widget_position` may point
+    /// off-screen (e.g. a monitor was disconnected since it was saved), so
+    /// clamp the restored position back onto whatever monitor the window
+    /// actually landed on.
+    fn clamp_to_monitor(&self, ctx: &egui::Context) {
+        let (outer_rect, monitor_size) =
+            ctx.input(|i| (i.viewport().outer_rect, i.viewport().monitor_size));
+        let (Some(rect), Some(monitor)) = (outer_rect, monitor_size) else {
+            return;
+        };
+
+        let max_x = (monitor.x - rect.width()).max(0.0);
+        let max_y = (monitor.y - rect.height()).max(0.0);
+        let clamped = egui::pos2(rect.min.x.clamp(0.0, max_x), rect.min.y.clamp(0.0, max_y));
+
+        if clamped != rect.min {
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(clamped));
+        }
+    }
+
+    fn draw_idle(&self, ui: &mut egui::Ui) {
+        ui.label(format!(
+            "{}: Push-to-talk",
+            self.settings.read().push_to_talk_key
+        ));
+    }
+
+    fn draw_recording(&self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            for bar in &self.waveform {
+                ui.add(egui::ProgressBar::new(*bar).desired_width(4.0));
+            }
+        });
+        if let Some(preview) = &self.raw_text {
+            ui.label(egui::RichText::new(preview).italics().weak());
+        }
+    }
+
+    fn draw_transcribing(&self, ui: &mut egui::Ui, progress: f32) {
+        ui.add(egui::ProgressBar::new(progress).text("Transcribing..."));
+    }
+
+    fn draw_correcting(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.show_diff, "Show diff");
+        if self.show_diff {
+            if let (Some(raw), Some(partial)) = (&self.raw_text, &self.correction_partial) {
+                draw_diff(ui, raw, partial);
+            }
+        } else if let Some(partial) = &self.correction_partial {
+            ui.label(partial);
+        } else if let Some(raw) = &self.raw_text {
+            ui.label(egui::RichText::new(raw).italics().weak());
+        }
+        ui.label("⏳ Polishing...");
+    }
+
+    /// Shows the corrected result as an always-editable field: it grabs
+    /// keyboard focus as soon as a correction arrives (`result_edit_focus_pending`)
+    /// and Enter (without Shift, which still inserts a newline) injects the
+    /// current buffer contents immediately, same as clicking "📋 Inject".
+    /// Any edit that differs from the original correction is sent back to
+    /// the pipeline as a learned vocabulary correction first.
+    fn draw_result(&mut self, ui: &mut egui::Ui) {
+        let mut inject_now = false;
+        ui.checkbox(&mut self.show_diff, "Show diff");
+        if self.show_diff {
+            if let (Some(raw), Some(corrected)) = (&self.raw_text, &self.corrected_text) {
+                draw_diff(ui, raw, corrected);
+            }
+        }
+        if let Some(corrected) = self.corrected_text.clone() {
+            if let Some(buffer) = &mut self.result_edit_buffer {
+                let response = ui.text_edit_multiline(buffer);
+                if self.result_edit_focus_pending {
+                    response.request_focus();
+                    self.result_edit_focus_pending = false;
+                }
+                if response.has_focus()
+                    && ui.input(|i| i.key_pressed(egui::Key::Enter) && !i.modifiers.shift)
+                {
+                    inject_now = true;
+                }
+                if ui.small_button("✕").clicked() {
+                    self.corrected_text = None;
+                    self.result_edit_buffer = None;
+                    self.result_shown_at = None;
+                }
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label(&corrected);
+                    if ui.small_button("✕").clicked() {
+                        self.corrected_text = None;
+                        self.result_shown_at = None;
+                    }
+                });
+            }
+        }
+        if self.pending_inject.is_some() && ui.button("📋 Inject").clicked() {
+            inject_now = true;
+        }
+        if inject_now {
+            if let (Some(corrected), Some(buffer)) =
+                (self.corrected_text.clone(), self.result_edit_buffer.clone())
+            {
+                if buffer != corrected {
+                    let _ = self.command_tx.try_send(PipelineCommand::LearnCorrection {
+                        original: corrected,
+                        corrected: buffer.clone(),
+                    });
+                }
+                self.corrected_text = Some(buffer.clone());
+                let _ = self
+                    .command_tx
+                    .try_send(PipelineCommand::InjectText(buffer));
+                self.pending_inject = None;
+            } else if let Some(text) = self.pending_inject.clone() {
+                let _ = self.command_tx.try_send(PipelineCommand::InjectText(text));
+                self.pending_inject = None;
+            }
+        }
+        if self
+            .segments
+            .iter()
+            .any(|s| s.avg_confidence < LOW_CONFIDENCE_THRESHOLD)
+        {
+            ui.horizontal_wrapped(|ui| {
+                for segment in &self.segments {
+                    let text = egui::RichText::new(&segment.text);
+                    let text = if segment.avg_confidence < LOW_CONFIDENCE_THRESHOLD {
+                        text.color(egui::Color32::YELLOW)
+                    } else {
+                        text.weak()
+                    };
+                    ui.label(text);
+                }
+            });
+        }
+    }
+
+    fn draw_error(&self, ui: &mut egui::Ui, message: &str) {
+        ui.colored_label(egui::Color32::RED, message);
+    }
+
+    /// Scrollable list of past transcriptions, newest first, with buttons to
+    /// re-copy or re-inject any of them without re-recording, checkboxes to
+    /// select entries for export, and export buttons for the selection.
+    fn draw_history(&mut self, ui: &mut egui::Ui) {
+        if self.history_entries.is_empty() {
+            ui.label("No transcriptions yet.");
+            return;
+        }
+
+        // Drop the handle once playback finishes so the Play button resets
+        // on its own without waiting for user interaction.
+        if matches!(&self.karaoke_playback, Some((_, handle)) if handle.is_done()) {
+            self.karaoke_playback = None;
+        }
+
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for (i, entry) in self.history_entries.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let mut selected = self.history_selected.contains(&i);
+                        if ui.checkbox(&mut selected, "").changed() {
+                            if selected {
+                                self.history_selected.insert(i);
+                            } else {
+                                self.history_selected.remove(&i);
+                            }
+                        }
+                        ui.label(egui::RichText::new(entry.best_text()).weak());
+                        if ui.small_button("Copy").clicked() {
+                            let _ = copy_to_clipboard(entry.best_text());
+                        }
+                        if ui.small_button("Inject").clicked() {
+                            let _ = self
+                                .command_tx
+                                .try_send(PipelineCommand::InjectText(entry.best_text().into()));
+                        }
+                        if let Some(path) = &entry.recording_path {
+                            let playing = matches!(&self.karaoke_playback, Some((j, _)) if *j == i);
+                            if playing {
+                                if ui.small_button("Stop").clicked() {
+                                    if let Some((_, handle)) = self.karaoke_playback.take() {
+                                        handle.stop();
+                                    }
+                                }
+                            } else if ui.small_button("Play").clicked() {
+                                if let Some((_, handle)) = self.karaoke_playback.take() {
+                                    handle.stop();
+                                }
+                                match crate::audio::play(path) {
+                                    Ok(handle) => self.karaoke_playback = Some((i, handle)),
+                                    Err(e) => {
+                                        self.export_status = Some(format!("Playback failed: {e}"))
+                                    }
+                                }
+                            }
+                        }
+                    });
+
+                    if let Some((j, handle)) = &self.karaoke_playback {
+                        if *j == i {
+                            draw_karaoke_words(ui, entry, handle.position_ms());
+                        }
+                    }
+                }
+            });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Export selected:");
+            if ui.small_button("TXT").clicked() {
+                self.export_selected(ExportFormat::Txt);
+            }
+            if ui.small_button("Markdown").clicked() {
+                self.export_selected(ExportFormat::Markdown);
+            }
+            if ui.small_button("SRT").clicked() {
+                self.export_selected(ExportFormat::Srt);
+            }
+        });
+        if let Some(status) = &self.export_status {
+            ui.label(egui::RichText::new(status).weak());
+        }
+    }
+
+    /// Writes the checked history entries, oldest first, to
+    /// `AppPaths::exports_dir()` in `format`, and reports the resulting
+    /// path (or the error) in `export_status`.
+    fn export_selected(&mut self, format: ExportFormat) {
+        if self.history_selected.is_empty() {
+            self.export_status = Some("Select at least one transcript to export.".into());
+            return;
+        }
+
+        let mut selected: Vec<&HistoryEntry> = self
+            .history_selected
+            .iter()
+            .filter_map(|i| self.history_entries.get(*i))
+            .collect();
+        // `history_entries` is newest-first; export chronologically.
+        selected.reverse();
+        let entries: Vec<HistoryEntry> = selected.into_iter().cloned().collect();
+
+        self.export_status = Some(match export::write_export(&entries, format) {
+            Ok(path) => format!("Exported to {}", path.display()),
+            Err(e) => format!("Export failed: {e}"),
+        });
+    }
+
+    /// Dictation stats panel — words dictated today/this week, average STT
+    /// latency, how often the LLM actually changed the text, and which
+    /// detected domains show up most, all derived from the history log.
+    fn draw_stats(&mut self, ui: &mut egui::Ui) {
+        let stats = &self.stats;
+        ui.label(format!("Words today: {}", stats.words_today));
+        ui.label(format!("Words this week: {}", stats.words_this_week));
+        ui.label(format!(
+            "Average latency: {}",
+            stats
+                .avg_latency_ms
+                .map(|ms| format!("{:.0} ms", ms))
+                .unwrap_or_else(|| "n/a".into())
+        ));
+        ui.label(format!(
+            "LLM correction rate: {:.0}%",
+            stats.correction_rate * 100.0
+        ));
+
+        if !stats.top_domains.is_empty() {
+            ui.separator();
+            ui.label("Most-used domains:");
+            for (domain, count) in stats.top_domains.iter().take(5) {
+                ui.label(format!("  {domain}: {count}"));
+            }
+        }
+
+        ui.separator();
+        let cache = &self.cache_stats;
+        ui.label(format!(
+            "LLM cache: {} hits / {} misses ({:.0}% hit rate)",
+            cache.hits,
+            cache.misses,
+            cache.hit_rate() * 100.0
+        ));
+    }
+
+    /// Snippet editor: one `trigger = "expansion"` TOML line per macro,
+    /// written straight to `snippets.toml` on Save. Takes effect on the
+    /// next restart, same as the LLM base URL and injection method in the
+    /// settings panel.
+    fn draw_snippets(&mut self, ui: &mut egui::Ui) {
+        let Some(draft) = &mut self.snippets_draft else {
+            return;
+        };
+
+        ui.label(
+            egui::RichText::new("One per line: trigger = \"expansion text\"")
+                .weak()
+                .small(),
+        );
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(draft)
+                        .desired_rows(8)
+                        .desired_width(f32::INFINITY),
+                );
+            });
+
+        if let Some(err) = &self.snippets_error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                self.save_snippets_draft();
+            }
+            if ui.button("Cancel").clicked() {
+                self.show_snippets = false;
+                self.snippets_draft = None;
+                self.snippets_error = None;
+            }
+        });
+    }
+
+    /// Validates the draft as `trigger -> expansion` TOML and writes it to
+    /// `snippets.toml` on success, leaving the panel open with an inline
+    /// error otherwise.
+    fn save_snippets_draft(&mut self) {
+        let Some(draft) = &self.snippets_draft else {
+            return;
+        };
+
+        if toml::from_str::<std::collections::HashMap<String, String>>(draft).is_err() {
+            self.snippets_error =
+                Some("Invalid TOML — expected lines like trigger = \"expansion text\"".into());
+            return;
+        }
+
+        if let Err(e) = std::fs::create_dir_all(AppPaths::config_dir())
+            .and_then(|_| std::fs::write(AppPaths::snippets_path(), draft))
+        {
+            self.snippets_error = Some(format!("Failed to save: {e}"));
+            return;
+        }
+
+        self.show_snippets = false;
+        self.snippets_draft = None;
+        self.snippets_error = None;
+    }
+
+    /// Warns when the global hotkey listener's self-test found no evidence
+    /// it's actually receiving events — the silent failure mode on macOS
+    /// without Accessibility permission, and on some Wayland compositors —
+    /// and offers a way to retry after the user fixes it.
+    fn draw_hotkey_diagnostics(&mut self, ui: &mut egui::Ui) {
+        let status = self.hotkey_status.lock().unwrap().clone();
+        match status {
+            HotkeyStatus::Starting | HotkeyStatus::Listening => {}
+            HotkeyStatus::NoEventsDetected => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        "⚠ No global key events detected. On macOS, grant this app \
+                         Accessibility permission in System Settings > Privacy & \
+                         Security > Accessibility. On Linux, the hotkey may not work \
+                         under Wayland — try an X11 session.",
+                    );
+                    if ui.button("🔄 Restart listener").clicked() {
+                        let _ = self.restart_hotkey_tx.try_send(());
+                    }
+                });
+            }
+            HotkeyStatus::Error(e) => {
+                ui.horizontal_wrapped(|ui| {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("⚠ Hotkey listener failed: {}", e),
+                    );
+                    if ui.button("🔄 Restart listener").clicked() {
+                        let _ = self.restart_hotkey_tx.try_send(());
+                    }
+                });
+            }
+        }
+    }
+
+    /// Live input-level meter (peak + RMS) plus a "record 3s and play back"
+    /// mic test, so users can verify their device before dictating.
+    fn draw_mic_meter(&mut self, ui: &mut egui::Ui) {
+        let (peak, rms) = self.input_level.snapshot();
+        ui.horizontal(|ui| {
+            ui.label("Input level:");
+            ui.add(egui::ProgressBar::new(rms.min(1.0)).text("RMS"));
+            ui.add(egui::ProgressBar::new(peak.min(1.0)).text("Peak"));
+        });
+        // The meter only updates while `update()` keeps repainting; while
+        // the settings panel is open that's frequent enough already, but
+        // request one more frame explicitly so it doesn't stall if nothing
+        // else is animating.
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_millis(100));
+
+        let status = self.mic_test_status.lock().unwrap().clone();
+        ui.horizontal(|ui| {
+            let label = match &status {
+                MicTestStatus::Idle | MicTestStatus::Done => "🎙 Test mic (record 3s, play back)",
+                MicTestStatus::Recording => "⏺ Recording...",
+                MicTestStatus::Playing => "▶ Playing back...",
+                MicTestStatus::Error(_) => "🎙 Test mic (record 3s, play back)",
+            };
+            let busy = matches!(status, MicTestStatus::Recording | MicTestStatus::Playing);
+            if ui.add_enabled(!busy, egui::Button::new(label)).clicked() {
+                *self.mic_test_status.lock().unwrap() = MicTestStatus::Idle;
+                crate::audio::spawn_mic_test(self.mic_test_status.clone());
+            }
+            if let MicTestStatus::Error(e) = &status {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+        });
+    }
+
+    /// Named whole-settings profile switcher, shown above the regular
+    /// settings form. Switching applies immediately (no restart, unlike
+    /// the LLM base URL/injection method fields further down) since it's
+    /// just another `AppSettings::save()` + `ReloadConfig` round-trip.
+    fn draw_profile_switcher(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Profile")
+                .selected_text(if self.selected_profile.is_empty() {
+                    "(none saved)"
+                } else {
+                    &self.selected_profile
+                })
+                .show_ui(ui, |ui| {
+                    for name in &self.profile_names {
+                        ui.selectable_value(&mut self.selected_profile, name.clone(), name);
+                    }
+                });
+            if ui
+                .add_enabled(
+                    !self.selected_profile.is_empty(),
+                    egui::Button::new("Switch"),
+                )
+                .clicked()
+            {
+                self.switch_to_profile();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Save current as:");
+            ui.text_edit_singleline(&mut self.new_profile_name);
+            if ui
+                .add_enabled(
+                    !self.new_profile_name.trim().is_empty(),
+                    egui::Button::new("Save"),
+                )
+                .clicked()
+            {
+                self.save_current_as_profile();
+            }
+        });
+        if let Some(status) = &self.profile_status {
+            ui.label(egui::RichText::new(status).weak());
+        }
+    }
+
+    /// Loads `self.selected_profile`, validates it, and — on success —
+    /// makes it the active settings both on disk and for the running
+    /// pipeline, the same way a settings-panel Save does.
+    fn switch_to_profile(&mut self) {
+        let Some(candidate) = crate::config::profiles::load(&self.selected_profile) else {
+            self.profile_status = Some(format!(
+                "Could not load profile \"{}\"",
+                self.selected_profile
+            ));
+            return;
+        };
+
+        let issues = candidate.validate();
+        if !issues.is_empty() {
+            self.profile_status = Some(
+                issues
+                    .iter()
+                    .map(|issue| issue.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            );
+            return;
+        }
+
+        if let Err(e) = candidate.save() {
+            self.profile_status = Some(format!("Failed to activate profile: {e}"));
+            return;
+        }
+
+        if candidate.stt_model != self.settings.read().stt_model {
+            let _ = self
+                .command_tx
+                .try_send(PipelineCommand::SwitchModel(candidate.stt_model.clone()));
+        }
+
+        self.settings_draft = Some(SettingsDraft::from(&candidate));
+        *self.settings.write() = candidate;
+        let _ = self.command_tx.try_send(PipelineCommand::ReloadConfig);
+        self.profile_status = Some(format!("Switched to \"{}\"", self.selected_profile));
+    }
+
+    /// Saves the currently active settings as a new named profile file.
+    fn save_current_as_profile(&mut self) {
+        let name = self.new_profile_name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        match crate::config::profiles::save(&name, &self.settings.read()) {
+            Ok(()) => {
+                self.profile_names = crate::config::profiles::list();
+                self.selected_profile = name.clone();
+                self.new_profile_name.clear();
+                self.profile_status = Some(format!("Saved profile \"{}\"", name));
+            }
+            Err(e) => {
+                self.profile_status = Some(format!("Failed to save profile: {e}"));
+            }
+        }
+    }
+
+    /// Editable settings form. Edits live in `settings_draft` until Save is
+    /// pressed, so a half-typed field never touches the running pipeline.
+    fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        self.draw_profile_switcher(ui);
+        ui.separator();
+
+        let Some(draft) = &mut self.settings_draft else {
+            return;
+        };
+
+        egui::ComboBox::from_label("Mode")
+            .selected_text(format!("{:?}", draft.operating_mode))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut draft.operating_mode, OperatingMode::Fast, "Fast");
+                ui.selectable_value(
+                    &mut draft.operating_mode,
+                    OperatingMode::Standard,
+                    "Standard",
+                );
+                ui.selectable_value(&mut draft.operating_mode, OperatingMode::Context, "Context");
+            });
+
+        ui.horizontal(|ui| {
+            ui.label("STT model:");
+            let selected_text = crate::stt::find_model(&draft.stt_model)
+                .map(|m| m.display_name.to_string())
+                .unwrap_or_else(|| draft.stt_model.clone());
+            egui::ComboBox::from_id_salt("stt_model")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    for group in crate::stt::ALL_MODELS {
+                        for model in *group {
+                            ui.selectable_value(
+                                &mut draft.stt_model,
+                                model.id.to_string(),
+                                model.display_name,
+                            );
+                        }
+                    }
+                });
+        });
+        ui.checkbox(
+            &mut draft.use_gpu,
+            "Use GPU acceleration (restart required)",
+        );
+        if let Some(backend) = &self.stt_backend {
+            ui.label(format!("Active STT backend: {}", backend));
+        }
+        ui.checkbox(
+            &mut draft.stt_word_timestamps,
+            "Word-level timestamps (enables karaoke playback review in history)",
+        );
+        ui.checkbox(
+            &mut draft.stt_remote_enabled,
+            "Use remote STT server (whisper.cpp / OpenAI-compatible)",
+        );
+        ui.horizontal(|ui| {
+            ui.label("Remote STT URL:");
+            ui.text_edit_singleline(&mut draft.stt_remote_url);
+        });
+        ui.checkbox(
+            &mut draft.stt_vosk_enabled,
+            "Use Vosk STT server (takes priority over remote STT above)",
+        );
+        ui.horizontal(|ui| {
+            ui.label("Vosk STT URL:");
+            ui.text_edit_singleline(&mut draft.stt_vosk_url);
+        });
+        ui.checkbox(
+            &mut draft.stt_diarization_enabled,
+            "Speaker diarization (label segments Speaker A/B for two-party dictation)",
+        );
+        ui.horizontal(|ui| {
+            ui.label("LLM model:");
+            ui.text_edit_singleline(&mut draft.llm_model);
+        });
+        ui.horizontal(|ui| {
+            ui.label("LLM base URL:");
+            ui.text_edit_singleline(&mut draft.llm_base_url);
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Check LLM providers").clicked() {
+                let _ = self.command_tx.try_send(PipelineCommand::CheckLlmProviders);
+            }
+            for (label, reachable) in &self.llm_provider_status {
+                let icon = if *reachable { "🟢" } else { "🔴" };
+                ui.label(format!("{} {}", icon, label));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Daily token budget (0 = unlimited):");
+            ui.text_edit_singleline(&mut draft.llm_daily_token_budget);
+        });
+        egui::ComboBox::from_label("Correction style")
+            .selected_text(format!("{:?}", draft.llm_correction_style))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut draft.llm_correction_style,
+                    CorrectionStyle::Standard,
+                    "Standard",
+                );
+                ui.selectable_value(
+                    &mut draft.llm_correction_style,
+                    CorrectionStyle::StructuredList,
+                    "Structured list",
+                );
+                ui.selectable_value(
+                    &mut draft.llm_correction_style,
+                    CorrectionStyle::SoapNote,
+                    "SOAP note",
+                );
+            });
+        egui::ComboBox::from_label("Domain detection")
+            .selected_text(match draft.domain_override_choice {
+                DomainOverrideChoice::Auto => "Auto-detect",
+                DomainOverrideChoice::Locked => "Locked",
+                DomainOverrideChoice::Disabled => "Disabled",
+            })
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut draft.domain_override_choice,
+                    DomainOverrideChoice::Auto,
+                    "Auto-detect",
+                );
+                ui.selectable_value(
+                    &mut draft.domain_override_choice,
+                    DomainOverrideChoice::Locked,
+                    "Locked",
+                );
+                ui.selectable_value(
+                    &mut draft.domain_override_choice,
+                    DomainOverrideChoice::Disabled,
+                    "Disabled",
+                );
+            });
+        if draft.domain_override_choice == DomainOverrideChoice::Locked {
+            ui.horizontal(|ui| {
+                ui.label("Locked domain:");
+                ui.text_edit_singleline(&mut draft.domain_lock_name);
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.label("Hotkey:");
+            ui.text_edit_singleline(&mut draft.push_to_talk_key);
+        });
+        self.draw_hotkey_diagnostics(ui);
+        self.draw_mic_meter(ui);
+        egui::ComboBox::from_label("VAD backend")
+            .selected_text(format!("{:?}", draft.vad_backend))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut draft.vad_backend, VadBackend::Energy, "Energy");
+                ui.selectable_value(&mut draft.vad_backend, VadBackend::Silero, "Silero");
+            });
+        ui.horizontal(|ui| {
+            ui.label("VAD threshold:");
+            ui.text_edit_singleline(&mut draft.vad_threshold);
+        });
+        ui.checkbox(
+            &mut draft.noise_suppression,
+            "Noise suppression (reduce background hum/hiss before transcription)",
+        );
+        ui.horizontal(|ui| {
+            ui.label("Pre-roll (s, catches speech before the hotkey press):");
+            ui.text_edit_singleline(&mut draft.preroll_secs);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Max recording (s):");
+            ui.text_edit_singleline(&mut draft.max_recording_secs);
+        });
+        ui.horizontal(|ui| {
+            ui.label("STT timeout (s, aborts a stuck transcription):");
+            ui.text_edit_singleline(&mut draft.stt_timeout_secs);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Result auto-clear (s, 0 = sticky):");
+            ui.text_edit_singleline(&mut draft.result_display_secs);
+        });
+        egui::ComboBox::from_label("Injection method")
+            .selected_text(format!("{:?}", draft.inject_method))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut draft.inject_method,
+                    InjectMethod::Clipboard,
+                    "Clipboard",
+                );
+                ui.selectable_value(
+                    &mut draft.inject_method,
+                    InjectMethod::Keystroke,
+                    "Keystroke",
+                );
+                ui.selectable_value(
+                    &mut draft.inject_method,
+                    InjectMethod::UnicodeSendInput,
+                    "Unicode SendInput (Windows)",
+                );
+            });
+        ui.horizontal(|ui| {
+            ui.label("Inject timeout (s, aborts a stuck injection):");
+            ui.text_edit_singleline(&mut draft.inject_timeout_secs);
+        });
+        egui::ComboBox::from_label("Theme")
+            .selected_text(format!("{:?}", draft.theme))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut draft.theme, Theme::Dark, "Dark");
+                ui.selectable_value(&mut draft.theme, Theme::Light, "Light");
+                ui.selectable_value(&mut draft.theme, Theme::System, "System");
+            });
+        ui.horizontal(|ui| {
+            ui.label("Accent color (hex):");
+            ui.text_edit_singleline(&mut draft.accent_color);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Widget opacity (0.0-1.0):");
+            ui.text_edit_singleline(&mut draft.widget_opacity);
+        });
+        ui.label(
+            egui::RichText::new("Preview: this panel is already using your in-progress theme.")
+                .weak()
+                .small(),
+        );
+        ui.checkbox(
+            &mut draft.check_for_updates,
+            "Check GitHub releases for updates on startup",
+        );
+        if draft.check_for_updates {
+            ui.horizontal(|ui| {
+                if ui.button("Check now").clicked() {
+                    let _ = self.command_tx.try_send(PipelineCommand::CheckForUpdate);
+                }
+                match &self.update_status {
+                    crate::updater::UpdateStatus::Idle => {}
+                    crate::updater::UpdateStatus::Checking => {
+                        ui.label("Checking...");
+                    }
+                    crate::updater::UpdateStatus::UpToDate => {
+                        ui.label("Up to date.");
+                    }
+                    crate::updater::UpdateStatus::Available(info) => {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            format!("⚠ Update available: {}", info.version),
+                        );
+                    }
+                    crate::updater::UpdateStatus::Downloading => {
+                        ui.label("Downloading...");
+                    }
+                    crate::updater::UpdateStatus::Downloaded(path) => {
+                        ui.label(format!("Saved to {}", path.display()));
+                    }
+                    crate::updater::UpdateStatus::Error(e) => {
+                        ui.colored_label(egui::Color32::RED, format!("Update check failed: {}", e));
+                    }
+                }
+            });
+        }
+
+        if let Some(err) = &draft.error {
+            ui.colored_label(egui::Color32::RED, err);
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                self.save_settings_draft();
+            }
+            if ui.button("Cancel").clicked() {
+                self.show_settings = false;
+                self.settings_draft = None;
+            }
+        });
+    }
+
+    /// Validates the draft, writes it to disk on success, and asks the
+    /// pipeline to pick up the change. Leaves the panel open with an inline
+    /// error if validation fails so the user can fix it without losing edits.
+    fn save_settings_draft(&mut self) {
+        let Some(draft) = &self.settings_draft else {
+            return;
+        };
+
+        let vad_threshold = match draft.vad_threshold.parse::<f32>() {
+            Ok(v) => v,
+            Err(_) => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("VAD threshold must be a number".into());
+                return;
+            }
+        };
+        let preroll_secs = match draft.preroll_secs.parse::<f32>() {
+            Ok(v) if v >= 0.0 => v,
+            _ => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("Pre-roll must be a non-negative number of seconds".into());
+                return;
+            }
+        };
+        let max_recording_secs = match draft.max_recording_secs.parse::<u64>() {
+            Ok(v) => v,
+            Err(_) => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("Max recording length must be a whole number of seconds".into());
+                return;
+            }
+        };
+        let stt_timeout_secs = match draft.stt_timeout_secs.parse::<u64>() {
+            Ok(v) if v > 0 => v,
+            _ => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("STT timeout must be a whole number of seconds greater than 0".into());
+                return;
+            }
+        };
+        let inject_timeout_secs = match draft.inject_timeout_secs.parse::<u64>() {
+            Ok(v) if v > 0 => v,
+            _ => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("Inject timeout must be a whole number of seconds greater than 0".into());
+                return;
+            }
+        };
+        let llm_daily_token_budget = match draft.llm_daily_token_budget.parse::<u64>() {
+            Ok(v) => v,
+            Err(_) => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("Daily token budget must be a whole number".into());
+                return;
+            }
+        };
+        let result_display_secs = match draft.result_display_secs.parse::<u64>() {
+            Ok(v) => v,
+            Err(_) => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("Result auto-clear must be a whole number of seconds".into());
+                return;
+            }
+        };
+        let widget_opacity = match draft.widget_opacity.parse::<f32>() {
+            Ok(v) => v,
+            Err(_) => {
+                self.settings_draft.as_mut().unwrap().error =
+                    Some("Widget opacity must be a number".into());
+                return;
+            }
+        };
+        if parse_hex_color(&draft.accent_color).is_none() {
+            self.settings_draft.as_mut().unwrap().error =
+                Some("Accent color must be a 6-digit hex string, e.g. \"4A9EFF\"".into());
+            return;
+        }
+        let domain_override = match draft.domain_override_choice {
+            DomainOverrideChoice::Auto => None,
+            DomainOverrideChoice::Disabled => Some(DomainOverride::Disabled),
+            DomainOverrideChoice::Locked => {
+                if draft.domain_lock_name.trim().is_empty() {
+                    self.settings_draft.as_mut().unwrap().error =
+                        Some("Locked domain name can't be empty".into());
+                    return;
+                }
+                Some(DomainOverride::Locked(
+                    draft.domain_lock_name.trim().to_string(),
+                ))
+            }
+        };
+
+        let mut candidate = self.settings.read().clone();
+        candidate.operating_mode = draft.operating_mode;
+        candidate.stt_model = draft.stt_model.clone();
+        candidate.llm_model = draft.llm_model.clone();
+        candidate.llm_base_url = draft.llm_base_url.clone();
+        candidate.push_to_talk_key = draft.push_to_talk_key.clone();
+        candidate.vad_backend = draft.vad_backend;
+        candidate.vad_threshold = vad_threshold;
+        candidate.noise_suppression = draft.noise_suppression;
+        candidate.preroll_secs = preroll_secs;
+        candidate.max_recording_secs = max_recording_secs;
+        candidate.stt_timeout_secs = stt_timeout_secs;
+        candidate.inject_method = draft.inject_method;
+        candidate.inject_timeout_secs = inject_timeout_secs;
+        candidate.use_gpu = draft.use_gpu;
+        candidate.stt_word_timestamps = draft.stt_word_timestamps;
+        candidate.stt_remote_enabled = draft.stt_remote_enabled;
+        candidate.stt_remote_url = draft.stt_remote_url.clone();
+        candidate.stt_vosk_enabled = draft.stt_vosk_enabled;
+        candidate.stt_vosk_url = draft.stt_vosk_url.clone();
+        candidate.stt_diarization_enabled = draft.stt_diarization_enabled;
+        candidate.llm_daily_token_budget = llm_daily_token_budget;
+        candidate.llm_correction_style = draft.llm_correction_style;
+        candidate.domain_override = domain_override;
+        candidate.result_display_secs = result_display_secs;
+        candidate.theme = draft.theme;
+        candidate.accent_color = draft.accent_color.clone();
+        candidate.widget_opacity = widget_opacity;
+        candidate.check_for_updates = draft.check_for_updates;
+
+        let issues = candidate.validate();
+        if !issues.is_empty() {
+            let msg = issues
+                .iter()
+                .map(|issue| issue.to_string())
+                .collect::<Vec<_>>()
+                .join("\n");
+            self.settings_draft.as_mut().unwrap().error = Some(msg);
+            return;
+        }
+
+        if let Err(e) = candidate.save() {
+            self.settings_draft.as_mut().unwrap().error = Some(e.to_string());
+            return;
+        }
+
+        if candidate.stt_model != self.settings.read().stt_model {
+            let _ = self
+                .command_tx
+                .try_send(PipelineCommand::SwitchModel(candidate.stt_model.clone()));
+        }
+        if candidate.operating_mode != self.settings.read().operating_mode {
+            self.budget_warning = None;
+        }
+
+        *self.settings.write() = candidate;
+        let _ = self.command_tx.try_send(PipelineCommand::ReloadConfig);
+        self.show_settings = false;
+        self.settings_draft = None;
+    }
+}
+
+/// Renders the word-level diff between `raw` and `corrected`, so users can
+/// see exactly what the LLM changed: deletions struck through in red,
+/// insertions underlined in green, unchanged words plain.
+fn draw_diff(ui: &mut egui::Ui, raw: &str, corrected: &str) {
+    let ops = crate::text::diff::diff_words(raw, corrected);
+    ui.horizontal_wrapped(|ui| {
+        for op in &ops {
+            let (text, style) = match op {
+                crate::text::diff::DiffOp::Equal(t) => (t, None),
+                crate::text::diff::DiffOp::Delete(t) => (t, Some(false)),
+                crate::text::diff::DiffOp::Insert(t) => (t, Some(true)),
+            };
+            let rich = match style {
+                None => egui::RichText::new(text),
+                Some(false) => egui::RichText::new(text)
+                    .strikethrough()
+                    .color(egui::Color32::RED),
+                Some(true) => egui::RichText::new(text)
+                    .underline()
+                    .color(egui::Color32::GREEN),
+            };
+            ui.label(rich);
+        }
+    });
+}
+
+/// Renders `entry`'s transcript as individual word spans, highlighting
+/// whichever `WordTiming` (across all its segments) contains
+/// `position_ms`. Falls back to the plain best-text label if the entry
+/// was recorded without word timestamps (`AppSettings::stt_word_timestamps`
+/// was off).
+fn draw_karaoke_words(ui: &mut egui::Ui, entry: &HistoryEntry, position_ms: u64) {
+    if entry.segments.iter().all(|s| s.words.is_empty()) {
+        ui.label(egui::RichText::new(entry.best_text()).weak());
+        return;
+    }
+
+    ui.horizontal_wrapped(|ui| {
+        for segment in &entry.segments {
+            for word in &segment.words {
+                let current = position_ms >= word.start_ms && position_ms < word.end_ms;
+                let text = if current {
+                    egui::RichText::new(&word.word)
+                        .strong()
+                        .color(egui::Color32::YELLOW)
+                } else {
+                    egui::RichText::new(&word.word).weak()
+                };
+                ui.label(text);
+            }
+        }
+    });
+}
+
+fn describe_error(e: &PipelineError) -> String {
+    match e {
+        PipelineError::Audio(m) => format!("Audio error: {}", m),
+        PipelineError::Stt(m) => format!("STT error: {}", m),
+        PipelineError::Llm(m) => format!("LLM error: {}", m),
+        PipelineError::Inject(m) => format!("Injection error: {}", m),
+        PipelineError::Internal(m) => format!("Internal error: {}", m),
+    }
+}
+
+impl eframe::App for ThaiSttApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        if !self.placement_checked {
+            self.placement_checked = true;
+            self.clamp_to_monitor(ctx);
+        }
+
+        self.poll_visibility_toggle(ctx);
+        self.poll_results();
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            let _ = self.command_tx.try_send(PipelineCommand::Cancel);
+        }
+
+        if self.pipeline_state == PipelineState::Recording {
+            ctx.request_repaint_after(std::time::Duration::from_millis(33));
+        }
+
+        if let Some(shown_at) = self.result_shown_at {
+            let display_secs = self.settings.read().result_display_secs;
+            if display_secs > 0 {
+                let timeout = std::time::Duration::from_secs(display_secs);
+                if shown_at.elapsed() >= timeout {
+                    self.corrected_text = None;
+                    self.result_shown_at = None;
+                } else {
+                    ctx.request_repaint_after(std::time::Duration::from_millis(250));
+                }
+            }
+        }
+
+        let (theme, accent_color, opacity) = match &self.settings_draft {
+            // While the settings panel is open, preview the in-progress
+            // edits immediately instead of waiting for Save.
+            Some(draft) => (
+                draft.theme,
+                draft.accent_color.clone(),
+                draft.widget_opacity.clone(),
+            ),
+            None => {
+                let s = self.settings.read();
+                (
+                    s.theme,
+                    s.accent_color.clone(),
+                    s.widget_opacity.to_string(),
+                )
+            }
+        };
+        let opacity = opacity.parse::<f32>().unwrap_or(0.9).clamp(0.0, 1.0);
+        let accent = parse_hex_color(&accent_color)
+            .map(|(r, g, b)| egui::Color32::from_rgb(r, g, b))
+            .unwrap_or(egui::Color32::from_rgb(0x4A, 0x9E, 0xFF));
+
+        let mut visuals = match theme {
+            Theme::Light => egui::Visuals::light(),
+            Theme::Dark | Theme::System => egui::Visuals::dark(),
+        };
+        visuals.hyperlink_color = accent;
+        visuals.selection.bg_fill = accent;
+        visuals.widgets.hovered.bg_fill = accent.gamma_multiply(0.5);
+        ctx.set_visuals(visuals);
+
+        let base = match theme {
+            Theme::Light => egui::Color32::from_rgb(240, 240, 240),
+            Theme::Dark | Theme::System => egui::Color32::from_rgb(30, 30, 30),
+        };
+        let alpha = (opacity * 255.0).round() as u8;
+
+        egui::CentralPanel::default()
+            .frame(
+                egui::Frame::none()
+                    .fill(egui::Color32::from_rgba_premultiplied(
+                        base.r(),
+                        base.g(),
+                        base.b(),
+                        alpha,
+                    ))
+                    .rounding(8.0)
+                    .inner_margin(8.0),
+            )
+            .show(ctx, |ui| {
+                self.draw_title_bar(ui);
+                ui.separator();
+
+                if let Some(warning) = self.budget_warning.clone() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(egui::Color32::YELLOW, &warning);
+                        if ui.small_button("✕").clicked() {
+                            self.budget_warning = None;
+                        }
+                    });
+                    ui.separator();
+                }
+
+                if let Some(recovered) = self.recovered_dictation.clone() {
+                    ui.vertical(|ui| {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            "⚠ Recovered an unsent dictation from before the app closed:",
+                        );
+                        ui.label(egui::RichText::new(recovered.best_text()).weak());
+                        ui.horizontal(|ui| {
+                            if ui.small_button("Copy").clicked() {
+                                let _ = copy_to_clipboard(recovered.best_text());
+                                crate::pipeline::recovery::clear();
+                                self.recovered_dictation = None;
+                            }
+                            if ui.small_button("Inject").clicked() {
+                                let _ = self.command_tx.try_send(PipelineCommand::InjectText(
+                                    recovered.best_text().into(),
+                                ));
+                                crate::pipeline::recovery::clear();
+                                self.recovered_dictation = None;
+                            }
+                            if ui.small_button("Dismiss").clicked() {
+                                crate::pipeline::recovery::clear();
+                                self.recovered_dictation = None;
+                            }
+                        });
+                    });
+                    ui.separator();
+                }
+
+                match self.update_status.clone() {
+                    crate::updater::UpdateStatus::Available(info) => {
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.colored_label(
+                                    egui::Color32::YELLOW,
+                                    format!("⚠ Update available: v{}", info.version),
+                                );
+                                if info.download_url.is_some()
+                                    && info.download_sha256.is_some()
+                                    && ui.small_button("Download").clicked()
+                                {
+                                    let _ = self
+                                        .command_tx
+                                        .try_send(PipelineCommand::DownloadUpdate(info.clone()));
+                                }
+                                if ui.small_button("✕").clicked() {
+                                    self.update_status = crate::updater::UpdateStatus::Idle;
+                                }
+                            });
+                            if info.download_url.is_some() && info.download_sha256.is_none() {
+                                ui.label(
+                                    egui::RichText::new(
+                                        "No verified checksum for this release — download it \
+                                         manually from the GitHub releases page instead.",
+                                    )
+                                    .weak()
+                                    .small(),
+                                );
+                            }
+                            if !info.changelog.trim().is_empty() {
+                                ui.label(egui::RichText::new(&info.changelog).weak().small());
+                            }
+                        });
+                        ui.separator();
+                    }
+                    crate::updater::UpdateStatus::Downloading => {
+                        ui.label("Downloading update...");
+                        ui.separator();
+                    }
+                    crate::updater::UpdateStatus::Downloaded(path) => {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(
+                                egui::Color32::GREEN,
+                                format!("Update saved to {}", path.display()),
+                            );
+                            if ui.small_button("✕").clicked() {
+                                self.update_status = crate::updater::UpdateStatus::Idle;
+                            }
+                        });
+                        ui.separator();
+                    }
+                    _ => {}
+                }
+
+                if self.show_settings {
+                    self.draw_settings(ui);
+                    return;
+                }
+
+                if self.show_history {
+                    self.draw_history(ui);
+                    return;
+                }
+
+                if self.show_stats {
+                    self.draw_stats(ui);
+                    return;
+                }
+
+                if self.show_snippets {
+                    self.draw_snippets(ui);
+                    return;
+                }
+
+                match self.pipeline_state.clone() {
+                    PipelineState::Idle => {
+                        self.draw_idle(ui);
+                        if self.corrected_text.is_some() {
+                            ui.separator();
+                            self.draw_result(ui);
+                        }
+                    }
+                    PipelineState::Recording => self.draw_recording(ui),
+                    PipelineState::Paused => self.draw_recording(ui),
+                    PipelineState::Transcribing { progress } => {
+                        self.draw_transcribing(ui, progress)
+                    }
+                    PipelineState::Correcting => self.draw_correcting(ui),
+                    PipelineState::Injecting => self.draw_result(ui),
+                    PipelineState::Error { message } => self.draw_error(ui, &message),
+                }
+            });
+    }
+
+    /// Runs once when the window is actually closing (a normal close, Alt+F4,
+    /// or `shutdown::spawn_signal_listener` requesting a close after Ctrl+C)
+    /// — stops the mic stream and cancels any in-flight pipeline work
+    /// instead of leaving both to die abruptly with the process. History is
+    /// already flushed on every write (see `HistoryStore::append`), so
+    /// there's nothing to do for it here.
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        log::info!("Shutting down");
+        self.audio_capture.stop_recording();
+        crate::shutdown::shutdown(&self.settings, &self.command_tx);
+    }
+}
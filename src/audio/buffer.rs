@@ -0,0 +1,177 @@
+//! Ring buffer for captured microphone samples (f32, 16kHz, mono), with an
+//! optional spill-to-disk threshold so a long `max_recording_secs` doesn't
+//! have to hold everything in RAM.
+
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::PathBuf;
+
+/// An open temp file receiving samples that have aged out of `data`, as raw
+/// little-endian f32 with no header — cheaper to append to than re-encoding
+/// WAV on every push, and reassembled by `drain`.
+struct Spill {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    sample_count: usize,
+}
+
+/// Fixed-capacity sample buffer filled by the cpal callback and drained by the
+/// pipeline. Push-to-talk semantics: once full, further samples are dropped
+/// rather than overwriting older ones.
+///
+/// Past `spill_threshold` samples, `data` becomes a sliding window of the
+/// most recent samples — older ones are written to a temp file instead of
+/// growing `data` further. `drain()` reassembles the full recording in
+/// order; `snapshot()` (used for the live streaming preview) intentionally
+/// only sees the in-memory tail, which bounds preview re-transcription cost
+/// during very long recordings.
+pub struct AudioBuffer {
+    data: Vec<f32>,
+    capacity: usize,
+    pub is_recording: bool,
+    /// Set while recording is paused: `push_samples` drops incoming audio
+    /// without affecting `is_recording`, so `drain`/`stop` still finalize
+    /// the samples captured before the pause plus anything after resume.
+    pub is_paused: bool,
+    /// Samples kept in `data` before older ones spill to disk. `None`
+    /// disables spilling — `data` grows for the whole recording, as before.
+    spill_threshold: Option<usize>,
+    spill: Option<Spill>,
+}
+
+impl AudioBuffer {
+    /// Create a buffer sized for `max_seconds` of audio at 16kHz, with no
+    /// disk spill. Equivalent to `with_spill(max_seconds, 0)`.
+    pub fn new(max_seconds: usize) -> Self {
+        Self::with_spill(max_seconds, 0)
+    }
+
+    /// Create a buffer sized for `max_seconds` of audio at 16kHz. Once the
+    /// in-memory tail exceeds `spill_threshold_secs` of audio, older samples
+    /// spill to a temp file instead of growing memory further. A threshold
+    /// of 0 disables spilling.
+    pub fn with_spill(max_seconds: usize, spill_threshold_secs: u64) -> Self {
+        let capacity = max_seconds * 16_000;
+        let spill_threshold = if spill_threshold_secs == 0 {
+            None
+        } else {
+            Some((spill_threshold_secs as usize * 16_000).min(capacity))
+        };
+        Self {
+            data: Vec::with_capacity(spill_threshold.unwrap_or(capacity)),
+            capacity,
+            is_recording: false,
+            is_paused: false,
+            spill_threshold,
+            spill: None,
+        }
+    }
+
+    /// Append samples from the cpal callback. No-op unless recording, or
+    /// while paused.
+    pub fn push_samples(&mut self, samples: &[f32]) {
+        if !self.is_recording || self.is_paused {
+            return;
+        }
+
+        let already_buffered = self.data.len() + self.spill.as_ref().map_or(0, |s| s.sample_count);
+        let remaining = self.capacity.saturating_sub(already_buffered);
+        let n = remaining.min(samples.len());
+        if n == 0 {
+            return;
+        }
+        self.data.extend_from_slice(&samples[..n]);
+
+        if let Some(threshold) = self.spill_threshold {
+            if self.data.len() > threshold {
+                let overflow = self.data.len() - threshold;
+                let aged_out: Vec<f32> = self.data.drain(..overflow).collect();
+                self.spill_to_disk(&aged_out);
+            }
+        }
+    }
+
+    /// Appends `samples` to the spill file, opening it on first use.
+    fn spill_to_disk(&mut self, samples: &[f32]) {
+        if self.spill.is_none() {
+            let path = std::env::temp_dir().join(format!(
+                "voice-to-text-spill-{}-{}.raw",
+                std::process::id(),
+                self as *const Self as usize
+            ));
+            match File::create(&path) {
+                Ok(file) => {
+                    self.spill = Some(Spill {
+                        path,
+                        writer: BufWriter::new(file),
+                        sample_count: 0,
+                    })
+                }
+                Err(e) => {
+                    log::warn!("Failed to open audio spill file: {}", e);
+                    return;
+                }
+            }
+        }
+
+        let spill = self.spill.as_mut().expect("just initialized above");
+        for sample in samples {
+            if let Err(e) = spill.writer.write_all(&sample.to_le_bytes()) {
+                log::warn!("Failed to write audio spill file: {}", e);
+                return;
+            }
+        }
+        spill.sample_count += samples.len();
+    }
+
+    /// Take all buffered audio (spilled prefix + in-memory tail, in
+    /// recording order), leaving the buffer empty.
+    pub fn drain(&mut self) -> Vec<f32> {
+        let tail = std::mem::take(&mut self.data);
+        let Some(mut spill) = self.spill.take() else {
+            return tail;
+        };
+
+        let _ = spill.writer.flush();
+        let mut audio = Vec::with_capacity(spill.sample_count + tail.len());
+        if let Ok(mut file) = File::open(&spill.path) {
+            let mut bytes = Vec::with_capacity(spill.sample_count * 4);
+            if file.read_to_end(&mut bytes).is_ok() {
+                audio.extend(
+                    bytes
+                        .chunks_exact(4)
+                        .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])),
+                );
+            }
+        }
+        let _ = std::fs::remove_file(&spill.path);
+        audio.extend(tail);
+        audio
+    }
+
+    /// Copy the in-memory tail without draining it, for streaming
+    /// transcription of an in-progress recording and the live waveform. See
+    /// the type doc comment for why this excludes spilled samples.
+    pub fn snapshot(&self) -> Vec<f32> {
+        self.data.clone()
+    }
+
+    /// Discard buffered audio (including any spill file) without returning it.
+    pub fn clear(&mut self) {
+        self.data.clear();
+        self.is_paused = false;
+        if let Some(spill) = self.spill.take() {
+            let _ = std::fs::remove_file(&spill.path);
+        }
+    }
+
+    pub fn duration_seconds(&self) -> f32 {
+        let total = self.data.len() + self.spill.as_ref().map_or(0, |s| s.sample_count);
+        total as f32 / 16_000.0
+    }
+
+    pub fn is_full(&self) -> bool {
+        let total = self.data.len() + self.spill.as_ref().map_or(0, |s| s.sample_count);
+        total >= self.capacity
+    }
+}
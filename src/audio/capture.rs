@@ -0,0 +1,125 @@
+//! Microphone capture via cpal, resampled to Whisper's expected 16kHz mono format.
+
+use std::sync::{Arc, Mutex};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+use super::buffer::AudioBuffer;
+use super::level::{InputLevel, SharedInputLevel};
+use super::preroll::{PrerollBuffer, SharedPreroll};
+
+pub type SharedAudioBuffer = Arc<Mutex<AudioBuffer>>;
+
+pub struct AudioCapture {
+    device: cpal::Device,
+    config: cpal::StreamConfig,
+    sample_rate: u32,
+    stream: Option<cpal::Stream>,
+    level: SharedInputLevel,
+    preroll: SharedPreroll,
+}
+
+impl AudioCapture {
+    /// `preroll_secs` is how much audio to keep continuously buffered before
+    /// a recording starts (see `AppSettings.preroll_secs`), so word onsets
+    /// spoken just before the hotkey press aren't clipped.
+    pub fn new(preroll_secs: f32) -> anyhow::Result<Self> {
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .ok_or_else(|| anyhow::anyhow!("No input device found"))?;
+
+        let supported = device.default_input_config()?;
+        let sample_rate = supported.sample_rate().0;
+        let config = cpal::StreamConfig {
+            channels: 1,
+            sample_rate: supported.sample_rate(),
+            buffer_size: cpal::BufferSize::Default,
+        };
+
+        Ok(Self {
+            device,
+            config,
+            sample_rate,
+            stream: None,
+            level: Arc::new(InputLevel::default()),
+            preroll: Arc::new(Mutex::new(PrerollBuffer::new(preroll_secs))),
+        })
+    }
+
+    /// Live (peak, RMS) input level, updated on every capture callback
+    /// whether or not a recording is in progress — drives the settings
+    /// panel's mic meter.
+    pub fn level(&self) -> SharedInputLevel {
+        self.level.clone()
+    }
+
+    /// Continuously-filled pre-roll ring buffer, consumed by
+    /// `PipelineOrchestrator` on `StartRecording`.
+    pub fn preroll(&self) -> SharedPreroll {
+        self.preroll.clone()
+    }
+
+    /// Start streaming microphone samples into `buffer`, resampling to 16kHz on the fly.
+    pub fn start_recording(&mut self, buffer: SharedAudioBuffer) -> anyhow::Result<()> {
+        let config = self.config.clone();
+        let source_rate = self.sample_rate;
+        let level = self.level.clone();
+        let preroll = self.preroll.clone();
+
+        let stream = self.device.build_input_stream(
+            &config,
+            move |data: &[f32], _info: &cpal::InputCallbackInfo| {
+                level.update(data);
+                let resampled = resample_to_16k(data, source_rate);
+                if let Ok(mut p) = preroll.lock() {
+                    p.push(&resampled);
+                }
+                if let Ok(mut buf) = buffer.lock() {
+                    buf.push_samples(&resampled);
+                }
+            },
+            |err| {
+                log::error!("Audio stream error: {}", err);
+            },
+            None,
+        )?;
+
+        stream.play()?;
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    /// Drop the stream, stopping capture.
+    pub fn stop_recording(&mut self) {
+        self.stream = None;
+    }
+}
+
+/// Linear-interpolation resample. Good enough for speech; swap for `rubato`
+/// if quality issues surface in practice.
+pub fn resample_to_16k(samples: &[f32], source_rate: u32) -> Vec<f32> {
+    if source_rate == 16_000 || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = 16_000.0 / source_rate as f64;
+    let output_len = (samples.len() as f64 * ratio) as usize;
+    let mut output = Vec::with_capacity(output_len);
+
+    for i in 0..output_len {
+        let src_idx = i as f64 / ratio;
+        let idx = src_idx as usize;
+        let frac = src_idx - idx as f64;
+
+        let sample = if idx + 1 < samples.len() {
+            samples[idx] as f64 * (1.0 - frac) + samples[idx + 1] as f64 * frac
+        } else {
+            samples[idx] as f64
+        };
+
+        output.push(sample as f32);
+    }
+
+    output
+}
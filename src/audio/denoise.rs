@@ -0,0 +1,64 @@
+//! Noise suppression, applied after resampling and before VAD when
+//! `AppSettings.noise_suppression` is enabled.
+//!
+//! This is a single-band spectral-subtraction approximation done directly on
+//! the time-domain signal rather than per-frequency-bin: the noise floor is
+//! estimated from the quietest frames, then each frame's RMS is reduced by
+//! that floor and the frame is scaled to match, the same "subtract the noise
+//! magnitude, floor at zero" idea spectral subtraction applies per bin. A
+//! real RNNoise binding or an FFT-based per-bin version would do better on
+//! non-stationary noise, but neither an FFT crate nor RNNoise is a
+//! dependency of this project yet.
+
+const FRAME_SIZE: usize = 480; // 30ms at 16kHz, matching `vad::FRAME_SIZE`.
+
+/// Fraction of the quietest frames used to estimate the noise floor.
+const NOISE_FLOOR_PERCENTILE: f32 = 0.1;
+
+fn frame_rms(chunk: &[f32]) -> f32 {
+    (chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32).sqrt()
+}
+
+/// Estimate the noise floor as the RMS of the quietest `NOISE_FLOOR_PERCENTILE`
+/// fraction of frames, on the assumption that most of a push-to-talk
+/// recording is either speech or steady background noise (fan, hum, hiss).
+fn estimate_noise_floor(frame_rms_values: &[f32]) -> f32 {
+    if frame_rms_values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = frame_rms_values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let quiet_count = ((sorted.len() as f32 * NOISE_FLOOR_PERCENTILE).ceil() as usize)
+        .max(1)
+        .min(sorted.len());
+    sorted[..quiet_count].iter().sum::<f32>() / quiet_count as f32
+}
+
+/// Apply noise suppression to `audio`, returning a new buffer of the same
+/// length. Cheap enough to run on the whole recording at once rather than
+/// streaming per-callback like resampling does.
+pub fn denoise(audio: &[f32]) -> Vec<f32> {
+    if audio.is_empty() {
+        return Vec::new();
+    }
+
+    let frame_rms_values: Vec<f32> = audio.chunks(FRAME_SIZE).map(frame_rms).collect();
+    let noise_floor = estimate_noise_floor(&frame_rms_values);
+    if noise_floor <= 0.0 {
+        return audio.to_vec();
+    }
+
+    let mut output = Vec::with_capacity(audio.len());
+    for (chunk, &rms) in audio.chunks(FRAME_SIZE).zip(frame_rms_values.iter()) {
+        let gain = if rms > noise_floor {
+            (1.0 - noise_floor / rms).max(0.0)
+        } else {
+            0.0
+        };
+        output.extend(chunk.iter().map(|s| s * gain));
+    }
+
+    output
+}
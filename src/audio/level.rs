@@ -0,0 +1,41 @@
+//! Live input-level tracking (peak + RMS), updated on every capture
+//! callback regardless of whether a push-to-talk recording is in progress —
+//! this is what drives the settings panel's mic level meter.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+pub type SharedInputLevel = Arc<InputLevel>;
+
+/// f32 values are stored as their bit pattern in an `AtomicU32` since there's
+/// no `AtomicF32` in `std` — reads/writes are just bit-for-bit round trips,
+/// not arithmetic, so this is safe.
+#[derive(Default)]
+pub struct InputLevel {
+    peak: AtomicU32,
+    rms: AtomicU32,
+}
+
+impl InputLevel {
+    /// Recompute peak/RMS from a chunk of raw samples. Called from the audio
+    /// callback on every buffer, independent of `AudioBuffer.is_recording`.
+    pub fn update(&self, samples: &[f32]) {
+        if samples.is_empty() {
+            return;
+        }
+
+        let peak = samples.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
+        let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+
+        self.peak.store(peak.to_bits(), Ordering::Relaxed);
+        self.rms.store(rms.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Current (peak, RMS) amplitude, both in `0.0..=1.0` for typical signals.
+    pub fn snapshot(&self) -> (f32, f32) {
+        (
+            f32::from_bits(self.peak.load(Ordering::Relaxed)),
+            f32::from_bits(self.rms.load(Ordering::Relaxed)),
+        )
+    }
+}
@@ -0,0 +1,175 @@
+//! "Record 3s and play back" mic test for the settings panel, letting users
+//! verify their device is picking up sound before trusting it to dictation.
+//!
+//! Runs on its own short-lived input/output streams rather than reusing the
+//! main `AudioCapture`, so it works standalone and doesn't disturb the
+//! always-on capture stream started at app launch.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+/// How long the mic test records before playing it back.
+const TEST_DURATION_SECS: f32 = 3.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MicTestStatus {
+    Idle,
+    Recording,
+    Playing,
+    Done,
+    Error(String),
+}
+
+pub type SharedMicTestStatus = Arc<Mutex<MicTestStatus>>;
+
+pub fn new_status() -> SharedMicTestStatus {
+    Arc::new(Mutex::new(MicTestStatus::Idle))
+}
+
+/// Kicks off record-then-playback on a background thread, updating `status`
+/// as it progresses so the settings panel can show it live.
+pub fn spawn_mic_test(status: SharedMicTestStatus) {
+    std::thread::spawn(move || {
+        if let Err(e) = run(&status) {
+            *status.lock().unwrap() = MicTestStatus::Error(e.to_string());
+        }
+    });
+}
+
+fn run(status: &SharedMicTestStatus) -> anyhow::Result<()> {
+    *status.lock().unwrap() = MicTestStatus::Recording;
+    let (samples, source_rate) = record(TEST_DURATION_SECS)?;
+
+    *status.lock().unwrap() = MicTestStatus::Playing;
+    playback(&samples, source_rate)?;
+
+    *status.lock().unwrap() = MicTestStatus::Done;
+    Ok(())
+}
+
+fn record(seconds: f32) -> anyhow::Result<(Vec<f32>, u32)> {
+    let host = cpal::default_host();
+    let device = host
+        .default_input_device()
+        .ok_or_else(|| anyhow::anyhow!("No input device found"))?;
+    let supported = device.default_input_config()?;
+    let sample_rate = supported.sample_rate().0;
+    let channels = supported.channels() as usize;
+    let config = cpal::StreamConfig {
+        channels: supported.channels(),
+        sample_rate: supported.sample_rate(),
+        buffer_size: cpal::BufferSize::Default,
+    };
+
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let recorded_cb = recorded.clone();
+    let stream = device.build_input_stream(
+        &config,
+        move |data: &[f32], _info: &cpal::InputCallbackInfo| {
+            recorded_cb.lock().unwrap().extend_from_slice(data);
+        },
+        |err| log::error!("Mic test input stream error: {}", err),
+        None,
+    )?;
+    stream.play()?;
+    std::thread::sleep(std::time::Duration::from_secs_f32(seconds));
+    drop(stream);
+
+    let interleaved = Arc::try_unwrap(recorded)
+        .map(|m| m.into_inner().unwrap())
+        .unwrap_or_default();
+
+    // Downmix to mono so playback doesn't need to know the original channel
+    // count.
+    let mono = if channels <= 1 {
+        interleaved
+    } else {
+        interleaved
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / channels as f32)
+            .collect()
+    };
+    Ok((mono, sample_rate))
+}
+
+/// Linear-interpolation resample, mirroring `capture::resample_to_16k` but
+/// for an arbitrary target rate (the output device's default, which won't
+/// always match the input device's). Shared with `audio::player`, which
+/// plays back saved recordings at the same device rate.
+pub(crate) fn resample(samples: &[f32], source_rate: u32, target_rate: u32) -> Vec<f32> {
+    if source_rate == target_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+
+    let ratio = target_rate as f64 / source_rate as f64;
+    let output_len = (samples.len() as f64 * ratio) as usize;
+    let mut output = Vec::with_capacity(output_len);
+
+    for i in 0..output_len {
+        let src_idx = i as f64 / ratio;
+        let idx = src_idx as usize;
+        let frac = src_idx - idx as f64;
+
+        let sample = if idx + 1 < samples.len() {
+            samples[idx] as f64 * (1.0 - frac) + samples[idx + 1] as f64 * frac
+        } else {
+            samples[idx] as f64
+        };
+
+        output.push(sample as f32);
+    }
+
+    output
+}
+
+fn playback(samples: &[f32], source_rate: u32) -> anyhow::Result<()> {
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| anyhow::anyhow!("No output device found"))?;
+    let supported = device.default_output_config()?;
+    let channels = supported.channels() as usize;
+    let target_rate = supported.sample_rate().0;
+    let config = cpal::StreamConfig {
+        channels: supported.channels(),
+        sample_rate: supported.sample_rate(),
+        buffer_size: cpal::BufferSize::Default,
+    };
+    let samples = resample(samples, source_rate, target_rate);
+
+    let position = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let done = Arc::new(AtomicBool::new(false));
+    let position_cb = position.clone();
+    let done_cb = done.clone();
+
+    let stream = device.build_output_stream(
+        &config,
+        move |out: &mut [f32], _info: &cpal::OutputCallbackInfo| {
+            let mut idx = position_cb.load(Ordering::Relaxed);
+            for frame in out.chunks_mut(channels) {
+                let sample = samples.get(idx).copied().unwrap_or(0.0);
+                for slot in frame {
+                    *slot = sample;
+                }
+                idx += 1;
+            }
+            position_cb.store(idx, Ordering::Relaxed);
+            if idx >= samples.len() {
+                done_cb.store(true, Ordering::Relaxed);
+            }
+        },
+        |err| log::error!("Mic test output stream error: {}", err),
+        None,
+    )?;
+    stream.play()?;
+
+    while !done.load(Ordering::Relaxed) {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    // Let the last buffer actually reach the speakers before tearing down.
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Audio pipeline: microphone capture, resampling, ring buffer, VAD.
+
+pub mod buffer;
+pub mod capture;
+pub mod denoise;
+pub mod file;
+pub mod level;
+pub mod mic_test;
+pub mod player;
+pub mod preroll;
+pub mod recording_store;
+pub mod vad;
+
+pub use buffer::AudioBuffer;
+pub use capture::{AudioCapture, SharedAudioBuffer};
+pub use denoise::denoise;
+pub use file::load_audio_file;
+pub use level::{InputLevel, SharedInputLevel};
+pub use mic_test::{spawn_mic_test, MicTestStatus, SharedMicTestStatus};
+pub use player::{play, PlaybackHandle};
+pub use preroll::{PrerollBuffer, SharedPreroll};
+pub use recording_store::RecordingStore;
+pub use vad::{build_vad_engine, SileroVad, VadDetector, VadEngine};
+
+/// Pre-transcription audio quality checks.
+pub enum AudioError {
+    TooShort,
+    TooQuiet,
+    Clipping,
+}
+
+impl std::fmt::Display for AudioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooShort => write!(f, "Recording too short (minimum 0.5s)"),
+            Self::TooQuiet => write!(f, "No speech detected (audio too quiet)"),
+            Self::Clipping => write!(f, "Audio is clipping — check microphone gain"),
+        }
+    }
+}
+
+impl AudioError {
+    /// User-facing message with a remediation hint, localized to `language`
+    /// ("th" for Thai, anything else falls back to English).
+    pub fn localized_message(&self, language: &str) -> String {
+        match (self, language) {
+            (Self::TooShort, "th") => {
+                "การบันทึกสั้นเกินไป (อย่างน้อย 0.5 วินาที) — ลองกดค้างให้นานขึ้น".to_string()
+            }
+            (Self::TooQuiet, "th") => {
+                "ไม่พบเสียงพูด (เสียงเบาเกินไป) — ตรวจสอบไมโครโฟนหรือพูดให้ดังขึ้น".to_string()
+            }
+            (Self::Clipping, "th") => {
+                "เสียงแตก (Clipping) — ลองลดความไวไมโครโฟนหรือขยับให้ห่างขึ้น".to_string()
+            }
+            (Self::TooShort, _) => {
+                format!("{} — try holding the push-to-talk key longer", self)
+            }
+            (Self::TooQuiet, _) => {
+                format!("{} — check your microphone or speak louder", self)
+            }
+            (Self::Clipping, _) => {
+                format!("{} — lower your microphone gain or move back from it", self)
+            }
+        }
+    }
+}
+
+pub struct AudioValidator;
+
+impl AudioValidator {
+    pub fn validate(audio: &[f32]) -> Result<(), AudioError> {
+        if audio.len() < 8_000 {
+            return Err(AudioError::TooShort);
+        }
+
+        let max_amplitude = audio.iter().map(|s| s.abs()).fold(0.0f32, f32::max);
+        if max_amplitude < 0.01 {
+            return Err(AudioError::TooQuiet);
+        }
+
+        let clipped = audio.iter().filter(|s| s.abs() > 0.99).count();
+        if clipped as f32 / audio.len() as f32 > 0.1 {
+            return Err(AudioError::Clipping);
+        }
+
+        Ok(())
+    }
+}
+
+/// RMS amplitude per bar, for waveform display while recording.
+pub fn compute_waveform(audio: &[f32], num_bars: usize) -> Vec<f32> {
+    if num_bars == 0 {
+        return Vec::new();
+    }
+    let chunk_size = audio.len() / num_bars;
+    if chunk_size == 0 {
+        return vec![0.0; num_bars];
+    }
+
+    audio
+        .chunks(chunk_size)
+        .take(num_bars)
+        .map(|chunk| {
+            let rms = (chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32).sqrt();
+            rms.min(1.0)
+        })
+        .collect()
+}
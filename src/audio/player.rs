@@ -0,0 +1,113 @@
+//! Non-blocking playback of a saved WAV recording, for the history panel's
+//! word-by-word "karaoke" review mode. Builds an output stream the same way
+//! `mic_test::playback` does, but returns immediately with a shared,
+//! millisecond playback position instead of blocking the calling thread
+//! until done, so the UI can poll it every frame and highlight whichever
+//! `Segment`/`WordTiming` span contains that position.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+
+use super::mic_test::resample;
+
+/// Handle to an in-progress playback. Dropping it doesn't stop playback —
+/// call `stop()` explicitly (e.g. when the user closes the history panel or
+/// starts playing a different entry).
+#[derive(Clone)]
+pub struct PlaybackHandle {
+    position_ms: Arc<AtomicU64>,
+    done: Arc<AtomicBool>,
+    stop_requested: Arc<AtomicBool>,
+}
+
+impl PlaybackHandle {
+    /// Milliseconds into the recording playback has reached. Timeline-only
+    /// (not resampled-domain), so it compares directly against
+    /// `Segment`/`WordTiming` timestamps regardless of the output device's
+    /// sample rate.
+    pub fn position_ms(&self) -> u64 {
+        self.position_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Relaxed)
+    }
+
+    pub fn stop(&self) {
+        self.stop_requested.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Load `path` and start playing it on a dedicated background thread,
+/// returning immediately. The output stream lives on that thread for the
+/// duration of playback, since `cpal::Stream` isn't `Send` on every
+/// platform backend.
+pub fn play(path: &std::path::Path) -> anyhow::Result<PlaybackHandle> {
+    let samples = super::load_audio_file(path)?;
+
+    let handle = PlaybackHandle {
+        position_ms: Arc::new(AtomicU64::new(0)),
+        done: Arc::new(AtomicBool::new(false)),
+        stop_requested: Arc::new(AtomicBool::new(false)),
+    };
+    let handle_for_thread = handle.clone();
+
+    std::thread::spawn(move || {
+        if let Err(e) = run(&samples, &handle_for_thread) {
+            log::warn!("Playback failed: {}", e);
+        }
+        handle_for_thread.done.store(true, Ordering::Relaxed);
+    });
+
+    Ok(handle)
+}
+
+fn run(samples: &[f32], handle: &PlaybackHandle) -> anyhow::Result<()> {
+    let host = cpal::default_host();
+    let device = host
+        .default_output_device()
+        .ok_or_else(|| anyhow::anyhow!("No output device found"))?;
+    let supported = device.default_output_config()?;
+    let channels = supported.channels() as usize;
+    let target_rate = supported.sample_rate().0;
+    let config = cpal::StreamConfig {
+        channels: supported.channels(),
+        sample_rate: supported.sample_rate(),
+        buffer_size: cpal::BufferSize::Default,
+    };
+    let samples = resample(samples, 16_000, target_rate);
+
+    let idx = Arc::new(AtomicUsize::new(0));
+    let idx_cb = idx.clone();
+    let position_ms_cb = handle.position_ms.clone();
+
+    let stream = device.build_output_stream(
+        &config,
+        move |out: &mut [f32], _info: &cpal::OutputCallbackInfo| {
+            let mut i = idx_cb.load(Ordering::Relaxed);
+            for frame in out.chunks_mut(channels) {
+                let sample = samples.get(i).copied().unwrap_or(0.0);
+                for slot in frame {
+                    *slot = sample;
+                }
+                i += 1;
+            }
+            idx_cb.store(i, Ordering::Relaxed);
+            position_ms_cb.store((i as u64 * 1000) / target_rate as u64, Ordering::Relaxed);
+        },
+        |err| log::error!("Playback output stream error: {}", err),
+        None,
+    )?;
+    stream.play()?;
+
+    let total = samples.len();
+    while idx.load(Ordering::Relaxed) < total && !handle.stop_requested.load(Ordering::Relaxed) {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    // Let the last buffer actually reach the speakers before tearing down.
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    Ok(())
+}
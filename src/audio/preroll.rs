@@ -0,0 +1,44 @@
+//! Pre-roll ring buffer: continuously keeps the last `preroll_secs` of audio
+//! around even while idle, so `StartRecording` can prepend it and catch word
+//! onsets spoken slightly before the hotkey is pressed.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+pub type SharedPreroll = Arc<Mutex<PrerollBuffer>>;
+
+pub struct PrerollBuffer {
+    data: VecDeque<f32>,
+    capacity: usize,
+}
+
+impl PrerollBuffer {
+    /// `seconds` of pre-roll at 16kHz. Zero disables it (capacity 0, every
+    /// push is a no-op).
+    pub fn new(seconds: f32) -> Self {
+        let capacity = (seconds.max(0.0) * 16_000.0) as usize;
+        Self {
+            data: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Append samples from the capture callback, dropping the oldest ones
+    /// once `capacity` is exceeded.
+    pub fn push(&mut self, samples: &[f32]) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.data.extend(samples.iter().copied());
+        while self.data.len() > self.capacity {
+            self.data.pop_front();
+        }
+    }
+
+    /// Copy the buffered pre-roll audio in chronological order, without
+    /// clearing it — the next recording's onset overlaps whatever's still
+    /// captured between now and the hotkey press.
+    pub fn snapshot(&self) -> Vec<f32> {
+        self.data.iter().copied().collect()
+    }
+}
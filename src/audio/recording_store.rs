@@ -0,0 +1,100 @@
+//! Optional debug archive of raw captured audio, so misrecognitions can be
+//! inspected or re-run later. Off by default — see `AppSettings.save_recordings`.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::config::AppPaths;
+
+/// Writes captured utterances as timestamped WAV files under
+/// `AppPaths::recordings_dir()`, trimming the oldest ones once the
+/// configured file-count or total-size budget is exceeded.
+pub struct RecordingStore {
+    dir: PathBuf,
+    max_files: usize,
+    max_total_bytes: u64,
+}
+
+impl RecordingStore {
+    pub fn new(max_files: usize, max_total_mb: u64) -> Self {
+        Self {
+            dir: AppPaths::recordings_dir(),
+            max_files,
+            max_total_bytes: max_total_mb * 1024 * 1024,
+        }
+    }
+
+    /// Save `audio` (16kHz mono f32) as a new WAV file, then enforce retention.
+    pub fn save(&self, audio: &[f32]) -> std::io::Result<PathBuf> {
+        AppPaths::ensure_dir(&self.dir)?;
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self.dir.join(format!("{timestamp}.wav"));
+        write_wav_16k_mono(&path, audio)?;
+
+        self.enforce_retention();
+        Ok(path)
+    }
+
+    fn enforce_retention(&self) {
+        let Ok(read_dir) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut entries: Vec<_> = read_dir
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.metadata().ok().map(|m| (e.path(), m)))
+            .collect();
+        entries.sort_by_key(|(_, meta)| meta.modified().ok());
+
+        while entries.len() > self.max_files {
+            let (path, _) = entries.remove(0);
+            let _ = std::fs::remove_file(path);
+        }
+
+        let mut total_bytes: u64 = entries.iter().map(|(_, meta)| meta.len()).sum();
+        while total_bytes > self.max_total_bytes && !entries.is_empty() {
+            let (path, meta) = entries.remove(0);
+            total_bytes = total_bytes.saturating_sub(meta.len());
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn write_wav_16k_mono(path: &Path, audio: &[f32]) -> std::io::Result<()> {
+    std::fs::File::create(path)?.write_all(&encode_wav_16k_mono(audio))
+}
+
+/// Encode 16kHz mono f32 samples as an in-memory 16-bit PCM WAV file (RIFF
+/// header + `data` chunk). Shared by `save` above and `stt::remote`, which
+/// uploads the bytes to a remote transcription server instead of writing
+/// them to disk.
+pub(crate) fn encode_wav_16k_mono(audio: &[f32]) -> Vec<u8> {
+    let data_len = (audio.len() * 2) as u32;
+    let byte_rate: u32 = 16_000 * 2;
+
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVE");
+    bytes.extend_from_slice(b"fmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    bytes.extend_from_slice(&1u16.to_le_bytes()); // mono
+    bytes.extend_from_slice(&16_000u32.to_le_bytes());
+    bytes.extend_from_slice(&byte_rate.to_le_bytes());
+    bytes.extend_from_slice(&2u16.to_le_bytes()); // block align
+    bytes.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+
+    for &sample in audio {
+        let pcm = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        bytes.extend_from_slice(&pcm.to_le_bytes());
+    }
+
+    bytes
+}
@@ -0,0 +1,163 @@
+//! Voice Activity Detection — trims leading/trailing silence before STT.
+//!
+//! Two backends, selected by `VadBackend`: simple energy thresholding
+//! (`VadDetector`, the original MVP) and `SileroVad`, an ONNX Runtime-backed
+//! model that's much more robust in noisy environments. whisper-rs also
+//! exposes a built-in Silero-based VAD (`WhisperVadSegments`) that could
+//! replace this module entirely if we ever drop the standalone `ort` dependency.
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use crate::config::{AppPaths, AppSettings, VadBackend};
+
+const FRAME_SIZE: usize = 480; // 30ms at 16kHz
+
+/// Common interface so the pipeline doesn't care which backend is active.
+pub trait VadEngine: Send + Sync {
+    fn trim_silence<'a>(&self, audio: &'a [f32]) -> &'a [f32];
+}
+
+/// Energy-based VAD: RMS energy per 30ms frame compared against `threshold`.
+pub struct VadDetector {
+    pub threshold: f32,
+}
+
+impl Default for VadDetector {
+    fn default() -> Self {
+        Self { threshold: 0.0005 }
+    }
+}
+
+impl VadDetector {
+    pub fn new(threshold: f32) -> Self {
+        Self { threshold }
+    }
+}
+
+impl VadEngine for VadDetector {
+    fn trim_silence<'a>(&self, audio: &'a [f32]) -> &'a [f32] {
+        trim_silence(audio, self.threshold)
+    }
+}
+
+fn frame_energy(chunk: &[f32]) -> f32 {
+    chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len() as f32
+}
+
+/// Trim leading/trailing silence from `audio` using `threshold` as the
+/// minimum per-frame energy to count as speech.
+pub fn trim_silence(audio: &[f32], threshold: f32) -> &[f32] {
+    let start = audio
+        .chunks(FRAME_SIZE)
+        .position(|chunk| frame_energy(chunk) > threshold)
+        .unwrap_or(0)
+        * FRAME_SIZE;
+
+    let end = audio
+        .chunks(FRAME_SIZE)
+        .rposition(|chunk| frame_energy(chunk) > threshold)
+        .map(|pos| (pos + 1) * FRAME_SIZE)
+        .unwrap_or(audio.len());
+
+    &audio[start..end.min(audio.len())]
+}
+
+/// Samples per inference window Silero VAD expects at 16kHz.
+const SILERO_WINDOW_SIZE: usize = 512;
+/// Recurrent state shape the v5 ONNX graph carries between windows.
+const SILERO_STATE_SHAPE: [usize; 3] = [2, 1, 128];
+
+/// Silero VAD via ONNX Runtime. Runs the model over fixed 32ms windows and
+/// trims leading/trailing windows whose speech probability stays below
+/// `threshold`, mirroring `VadDetector`'s frame-based approach so the two
+/// backends are interchangeable.
+pub struct SileroVad {
+    session: Mutex<ort::session::Session>,
+    threshold: f32,
+}
+
+impl SileroVad {
+    /// Load the model from `AppPaths::models_dir()/silero_vad.onnx`.
+    pub fn load(threshold: f32) -> anyhow::Result<Self> {
+        Self::load_from(Self::model_path(), threshold)
+    }
+
+    pub fn load_from(path: PathBuf, threshold: f32) -> anyhow::Result<Self> {
+        let session = ort::session::Session::builder()?.commit_from_file(path)?;
+        Ok(Self {
+            session: Mutex::new(session),
+            threshold,
+        })
+    }
+
+    pub fn model_path() -> PathBuf {
+        AppPaths::models_dir().join("silero_vad.onnx")
+    }
+
+    /// Speech probability for a single 512-sample window, zero-padded if short.
+    fn window_probability(&self, window: &[f32], state: &mut Vec<f32>) -> anyhow::Result<f32> {
+        let mut padded = [0.0f32; SILERO_WINDOW_SIZE];
+        padded[..window.len()].copy_from_slice(window);
+
+        let input = ndarray::Array2::from_shape_vec((1, SILERO_WINDOW_SIZE), padded.to_vec())?;
+        let state_arr = ndarray::Array3::from_shape_vec(SILERO_STATE_SHAPE, state.clone())?;
+        let sr = ndarray::Array1::from_vec(vec![16_000i64]);
+
+        let mut session = self.session.lock().unwrap();
+        let outputs = session.run(ort::inputs![
+            "input" => input,
+            "sr" => sr,
+            "state" => state_arr,
+        ]?)?;
+
+        let probability = outputs["output"].try_extract_tensor::<f32>()?[[0, 0]];
+        if let Ok(next_state) = outputs["stateN"].try_extract_tensor::<f32>() {
+            *state = next_state.iter().copied().collect();
+        }
+
+        Ok(probability)
+    }
+}
+
+impl VadEngine for SileroVad {
+    fn trim_silence<'a>(&self, audio: &'a [f32]) -> &'a [f32] {
+        let mut state = vec![0.0f32; SILERO_STATE_SHAPE.iter().product()];
+
+        let speech_mask: Vec<bool> = audio
+            .chunks(SILERO_WINDOW_SIZE)
+            .map(|window| {
+                self.window_probability(window, &mut state)
+                    .map(|p| p > self.threshold)
+                    .unwrap_or(false)
+            })
+            .collect();
+
+        let start = speech_mask.iter().position(|&speech| speech).unwrap_or(0) * SILERO_WINDOW_SIZE;
+        let end = speech_mask
+            .iter()
+            .rposition(|&speech| speech)
+            .map(|pos| (pos + 1) * SILERO_WINDOW_SIZE)
+            .unwrap_or(audio.len());
+
+        &audio[start..end.min(audio.len())]
+    }
+}
+
+/// Picks the VAD backend from settings, falling back to the always-available
+/// energy detector if Silero is selected but its model can't be loaded.
+pub fn build_vad_engine(settings: &AppSettings) -> Arc<dyn VadEngine> {
+    match settings.vad_backend {
+        VadBackend::Energy => Arc::new(VadDetector::new(settings.vad_threshold)),
+        VadBackend::Silero => match SileroVad::load(settings.vad_threshold) {
+            Ok(vad) => Arc::new(vad),
+            Err(e) => {
+                log::warn!(
+                    "Failed to load Silero VAD model, falling back to energy VAD: {}",
+                    e
+                );
+                Arc::new(VadDetector::new(settings.vad_threshold))
+            }
+        },
+    }
+}
@@ -0,0 +1,184 @@
+//! `--transcribe <file>`: run the resample → VAD → STT → LLM pipeline
+//! against a pre-recorded WAV/FLAC/MP3 file and print the transcript, for
+//! batch processing and scripting without launching the widget.
+
+use std::path::Path;
+use std::time::Instant;
+
+use crate::audio::{self, AudioValidator, VadEngine};
+use crate::config::{AppSettings, OperatingMode};
+use crate::llm::{self, ContextManager, LlmCorrectorConfig};
+use crate::stt::{self, SttEngine, WhisperEngine};
+
+/// Returns the value passed after `--flag`, if present, e.g.
+/// `flag_arg("--mode")` for `--mode fast`. Shared by every single-value CLI
+/// flag this binary accepts.
+pub fn flag_arg(flag: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    let idx = args.iter().position(|a| a == flag)?;
+    args.get(idx + 1).cloned()
+}
+
+/// Returns the path passed via `--transcribe <path>`, if present.
+pub fn transcribe_arg() -> Option<String> {
+    flag_arg("--transcribe")
+}
+
+/// Returns the path passed via `--benchmark <path>`, if present.
+pub fn benchmark_arg() -> Option<String> {
+    flag_arg("--benchmark")
+}
+
+/// True if launched with `--headless`. Runs the capture/hotkey/pipeline
+/// stack without the `eframe`/`egui` widget window, for users who want
+/// system-wide dictation via the hotkey and never look at the floating
+/// widget — status is available through logs and the HTTP/socket control
+/// API (`control_api_enabled`/`ipc_socket_enabled`), not the UI.
+pub fn headless_mode() -> bool {
+    std::env::args().any(|a| a == "--headless")
+}
+
+/// True if launched with `--portable`, or a `portable.marker` file sits
+/// next to the executable. The marker lets a USB-stick/locked-down-machine
+/// deployment just drop the file in once, instead of every launch needing
+/// the flag (a desktop shortcut, `rdev`-registered autostart entry, etc.
+/// rarely carries extra arguments).
+pub fn portable_mode() -> bool {
+    if std::env::args().any(|a| a == "--portable") {
+        return true;
+    }
+    portable_marker_path().is_file()
+}
+
+fn portable_marker_path() -> std::path::PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join("portable.marker")))
+        .unwrap_or_else(|| std::path::PathBuf::from("portable.marker"))
+}
+
+/// Transcribe `path` with every locally downloaded model and print
+/// time/output for each, to help pick a size/quality tradeoff. Exits the
+/// process on completion or failure — never returns.
+pub fn run_benchmark(path: &str) -> ! {
+    if let Err(e) = try_run_benchmark(path) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    std::process::exit(0);
+}
+
+fn try_run_benchmark(path: &str) -> anyhow::Result<()> {
+    let settings = AppSettings::load();
+
+    let audio = audio::load_audio_file(Path::new(path))?;
+    let vad = audio::build_vad_engine(&settings);
+    let trimmed = vad.trim_silence(&audio).to_vec();
+    AudioValidator::validate(&trimmed)
+        .map_err(|e| anyhow::anyhow!(e.localized_message(&settings.stt_language)))?;
+
+    let models = stt::list_local_models();
+    if models.is_empty() {
+        anyhow::bail!(
+            "No locally downloaded models found — run without --benchmark once to download one"
+        );
+    }
+
+    for model in models {
+        let engine = match WhisperEngine::with_params(
+            &model.local_path().to_string_lossy(),
+            settings.use_gpu,
+            settings.hallucination_blocklist.clone(),
+            stt::TranscribeParams::from_settings(&settings),
+        ) {
+            Ok(e) => e,
+            Err(e) => {
+                println!("{}: failed to load ({})", model.display_name, e);
+                continue;
+            }
+        };
+
+        let start = Instant::now();
+        match engine.transcribe(&trimmed, &settings.stt_language) {
+            Ok(result) => {
+                println!(
+                    "{} — {:.2}s: {}",
+                    model.display_name,
+                    start.elapsed().as_secs_f32(),
+                    result.text
+                );
+            }
+            Err(e) => {
+                println!("{}: transcription failed ({})", model.display_name, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Transcribe `path` and print the result to stdout. Exits the process on
+/// completion or failure — never returns.
+pub fn run(path: &str) -> ! {
+    if let Err(e) = try_run(path) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    std::process::exit(0);
+}
+
+fn try_run(path: &str) -> anyhow::Result<()> {
+    let settings = AppSettings::load();
+
+    let audio = audio::load_audio_file(Path::new(path))?;
+
+    let vad = audio::build_vad_engine(&settings);
+    let trimmed = vad.trim_silence(&audio).to_vec();
+
+    AudioValidator::validate(&trimmed)
+        .map_err(|e| anyhow::anyhow!(e.localized_message(&settings.stt_language)))?;
+
+    let model_path = stt::find_model(&settings.stt_model)
+        .map(|m| m.local_path())
+        .unwrap_or_default();
+    let stt_engine = WhisperEngine::with_params(
+        &model_path.to_string_lossy(),
+        settings.use_gpu,
+        settings.hallucination_blocklist.clone(),
+        stt::TranscribeParams::from_settings(&settings),
+    )?;
+    let raw_text = stt_engine
+        .transcribe(&trimmed, &settings.stt_language)?
+        .text;
+
+    let final_text = if settings.operating_mode == OperatingMode::Fast {
+        raw_text
+    } else {
+        let corrector = llm::build_corrector(
+            LlmCorrectorConfig {
+                provider: settings.llm_provider.clone(),
+                base_url: settings.llm_base_url.clone(),
+                api_key: crate::config::secrets::resolve(
+                    &settings.llm_api_key,
+                    crate::config::secrets::PRIMARY,
+                ),
+                model: settings.llm_model.clone(),
+                temperature: settings.llm_temperature,
+                timeout_secs: settings.llm_timeout_secs,
+                target_language: settings.stt_language.clone(),
+                daily_token_budget: settings.llm_daily_token_budget,
+                correction_style: settings.llm_correction_style,
+                ollama_keep_alive: settings.ollama_keep_alive.clone(),
+            },
+            Vec::new(),
+            0, // single-shot correction — nothing to reuse a cache for
+        );
+        let context = ContextManager::new().build_context(None, settings.domain_override.as_ref());
+        let rt = tokio::runtime::Runtime::new()?;
+        rt.block_on(corrector.correct(&raw_text, &context))
+            .unwrap_or(raw_text)
+    };
+
+    println!("{}", final_text);
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Dictation command grammar: spoken editing phrases ("new line", "ขึ้นบรรทัดใหม่")
+//! that get turned into real edits instead of being injected as literal text.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppPaths;
+
+/// What a matched phrase does to the text being built up.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandAction {
+    /// Insert a line break.
+    Newline,
+    /// Insert a period (sentence-final punctuation).
+    Period,
+    /// Discard the sentence dictated immediately before this phrase.
+    DeleteLastSentence,
+}
+
+/// Built-in Thai/English phrases, used when no `commands.toml` exists yet.
+fn default_phrases() -> HashMap<String, CommandAction> {
+    [
+        ("ขึ้นบรรทัดใหม่", CommandAction::Newline),
+        ("new line", CommandAction::Newline),
+        ("จบประโยค", CommandAction::Period),
+        ("end sentence", CommandAction::Period),
+        ("ลบประโยคล่าสุด", CommandAction::DeleteLastSentence),
+        ("delete last sentence", CommandAction::DeleteLastSentence),
+    ]
+    .into_iter()
+    .map(|(phrase, action)| (phrase.to_string(), action))
+    .collect()
+}
+
+/// Loads the phrase → action map and rewrites transcripts before injection.
+pub struct CommandProcessor {
+    phrases: HashMap<String, CommandAction>,
+}
+
+impl Default for CommandProcessor {
+    fn default() -> Self {
+        Self::load_or_default()
+    }
+}
+
+impl CommandProcessor {
+    /// Load `commands.toml`, falling back to the built-in phrase set if it
+    /// doesn't exist or fails to parse.
+    pub fn load_or_default() -> Self {
+        let path = AppPaths::commands_path();
+        let phrases = if path.exists() {
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| toml::from_str::<HashMap<String, CommandAction>>(&content).ok())
+                .unwrap_or_else(default_phrases)
+        } else {
+            default_phrases()
+        };
+        Self { phrases }
+    }
+
+    /// Rewrite `text`, replacing every recognized phrase with its effect.
+    /// Unrecognized text passes through unchanged.
+    pub fn apply(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut remaining = text;
+
+        while let Some((start, end, action)) = self.find_next_command(remaining) {
+            output.push_str(remaining[..start].trim_end());
+
+            match action {
+                CommandAction::Newline => output.push('\n'),
+                CommandAction::Period => output.push('.'),
+                CommandAction::DeleteLastSentence => delete_last_sentence(&mut output),
+            }
+
+            remaining = remaining[end..].trim_start();
+        }
+
+        if !output.is_empty() && !remaining.is_empty() {
+            output.push(' ');
+        }
+        output.push_str(remaining);
+        output
+    }
+
+    /// Find the earliest-occurring configured phrase in `text`, returning
+    /// its byte range and action. Case-insensitive so "New Line" and
+    /// "new line" both match.
+    fn find_next_command(&self, text: &str) -> Option<(usize, usize, CommandAction)> {
+        let lower = text.to_lowercase();
+        self.phrases
+            .iter()
+            .filter_map(|(phrase, action)| {
+                lower
+                    .find(&phrase.to_lowercase())
+                    .map(|start| (start, start + phrase.len(), *action))
+            })
+            .min_by_key(|(start, _, _)| *start)
+    }
+}
+
+/// Truncate `output` back to just before its last sentence, so a
+/// "delete last sentence" command erases what was dictated right before it.
+fn delete_last_sentence(output: &mut String) {
+    let boundary = output.rfind(['.', '\n']).map(|pos| pos + 1).unwrap_or(0);
+    output.truncate(boundary);
+    let trimmed_len = output.trim_end().len();
+    output.truncate(trimmed_len);
+}
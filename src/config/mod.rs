@@ -0,0 +1,996 @@
+//! Application settings: operating mode, STT/LLM/hotkey/context/UI/audio config, persistence.
+
+pub mod overrides;
+pub mod paths;
+pub mod profiles;
+pub mod secrets;
+pub mod watcher;
+
+use serde::{Deserialize, Serialize};
+
+pub use paths::AppPaths;
+
+use crate::hotkey;
+use crate::stt;
+
+/// Operating mode — controls how much post-processing runs after STT.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OperatingMode {
+    /// STT only, no LLM correction. Fastest.
+    Fast,
+    /// STT + LLM correction (zero-shot, no context).
+    Standard,
+    /// STT + LLM correction with rolling context, domain detection, user vocab.
+    Context,
+}
+
+impl Default for OperatingMode {
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+/// LLM provider selection — determines API format and auth mechanism.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LlmProvider {
+    /// Ollama running locally — native REST API, no auth required.
+    Ollama,
+    /// Any OpenAI-compatible API — OpenAI, Groq, Together.ai, LM Studio, vLLM.
+    OpenAiCompatible,
+    /// In-process inference via llama_cpp crate — no network, no auth (Phase 2).
+    LlamaCpp,
+    /// Rule-based deterministic cleanup, no network or model required. See
+    /// `OfflineCorrector`.
+    Offline,
+    /// LLM disabled — Fast mode only.
+    Disabled,
+}
+
+/// Which alternate prompt `PromptBuilder` uses to restructure the corrected
+/// text, on top of the usual homophone/filler-word cleanup. Selected via
+/// `AppSettings::llm_correction_style`, which makes it swap along with the
+/// rest of a saved config profile (see `config::profiles`) — e.g. a
+/// "Medical" profile can pair `SoapNote` with the medical domain prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CorrectionStyle {
+    /// The normal correction prompts — fix errors, keep the meaning and
+    /// shape of what was said.
+    Standard,
+    /// Reformat spoken enumerations ("ข้อหนึ่ง ... ข้อสอง ...", "first ...
+    /// second ...") into a numbered or bulleted Markdown list.
+    StructuredList,
+    /// Reformat a spoken clinical encounter into a SOAP note
+    /// (Subjective/Objective/Assessment/Plan) template.
+    SoapNote,
+}
+
+impl Default for CorrectionStyle {
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+/// One entry in the LLM correction failover chain — everything
+/// `LlmCorrectorConfig` needs to build a corrector for a single provider,
+/// minus the fields (temperature, timeout, target language) shared with
+/// the primary provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmProviderConfig {
+    pub provider: LlmProvider,
+    pub base_url: String,
+    pub model: String,
+    /// Same `keyring:`-reference-or-plaintext convention as
+    /// `AppSettings::llm_api_key`; resolve with `secrets::resolve` using
+    /// `secrets::fallback(index)` as the key id.
+    pub api_key: Option<String>,
+}
+
+/// Manual override for `llm::context::DomainDetector`'s per-utterance
+/// re-detection, since auto-detection can flip domains mid-session on
+/// ambiguous text. Stored per `AppProfile`, and as a global default for
+/// dictation outside any configured profile — see
+/// `AppSettings::domain_override`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DomainOverride {
+    /// Always report this domain, instead of running detection.
+    Locked(String),
+    /// Never report a domain — corrections skip domain-specific prompts and
+    /// keyword biasing entirely.
+    Disabled,
+}
+
+/// A named context profile bound to a foreground application — e.g. a
+/// dedicated vocabulary and domain list for "Slack" vs. a medical EMR — so
+/// corrections use the right learned terms for whatever the user is
+/// dictating into. See `llm::profiles::ProfileContextManager`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppProfile {
+    pub name: String,
+    /// Matches when the focused window's title or process name contains
+    /// this substring (case-insensitive).
+    pub match_pattern: String,
+    /// Pins or disables domain detection for utterances matched to this
+    /// profile. `None` uses the global `AppSettings::domain_override`, if any.
+    pub domain_override: Option<DomainOverride>,
+}
+
+/// An extra push-to-talk key bound to a bundle of settings overrides —
+/// e.g. F10 held down for "English Fast" instead of the primary
+/// `push_to_talk_key`'s configured mode. Distinct from `config::profiles`
+/// (a whole-settings swap picked by the user in the settings panel) and
+/// from `AppProfile` (per-app context matched by focus): a hotkey preset is
+/// a partial override, applied only for the utterance recorded while its
+/// key is held, and selected by which key the user pressed. See
+/// `hotkey::spawn_hotkey_listener` and
+/// `PipelineCommand::ApplyPreset`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeyPreset {
+    /// Opaque id the hotkey listener tags its events with and the
+    /// orchestrator looks up by — not shown to the user, so any unique
+    /// string works (e.g. "english-fast").
+    pub id: String,
+    /// A key combo like `"F10"`, parsed the same way as `push_to_talk_key`.
+    pub key: String,
+    /// `None` leaves the field at whatever the base settings already have.
+    pub stt_model: Option<String>,
+    pub operating_mode: Option<OperatingMode>,
+    pub stt_language: Option<String>,
+    pub translate_to_english: Option<bool>,
+    pub llm_correction_style: Option<CorrectionStyle>,
+}
+
+/// Which VAD implementation trims silence before transcription.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum VadBackend {
+    /// Simple RMS-energy thresholding. No model file, works everywhere.
+    Energy,
+    /// Silero VAD via ONNX Runtime. More robust in noisy environments.
+    Silero,
+}
+
+impl Default for VadBackend {
+    fn default() -> Self {
+        Self::Energy
+    }
+}
+
+/// Which whisper.cpp decoding strategy `WhisperEngine` uses.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SttSamplingStrategy {
+    /// Fastest. `stt_best_of` controls how many candidates are sampled per
+    /// token when temperature fallback kicks in.
+    Greedy,
+    /// Explores `stt_beam_size` candidate sequences at once. Slower but
+    /// less prone to the repetition loops greedy decoding can fall into —
+    /// also what a pathological-output retry escalates to automatically.
+    BeamSearch,
+}
+
+impl Default for SttSamplingStrategy {
+    fn default() -> Self {
+        Self::Greedy
+    }
+}
+
+/// How `text::numbers` renders numbers found in a transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NumberFormat {
+    /// Spelled-out Thai numbers become Arabic digits ("สิบเอ็ด" -> "11");
+    /// any Thai-script digits become Arabic too.
+    Arabic,
+    /// Same word-to-digit conversion as `Arabic`, but settles on Thai-script
+    /// digits ("สิบเอ็ด" -> "๑๑").
+    Thai,
+    /// Digits become spelled-out Thai words instead ("11" -> "สิบเอ็ด").
+    SpelledOut,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::Arabic
+    }
+}
+
+/// How corrected text reaches the focused application.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum InjectMethod {
+    /// Set the clipboard then simulate Ctrl+V/Cmd+V. Reliable for Thai
+    /// combining characters, but requires the target app to accept paste.
+    Clipboard,
+    /// Type the text character-by-character via synthesized key events.
+    /// Works in apps that reject paste (terminals, VMs, remote desktops),
+    /// at the cost of being slower and less reliable for Thai script.
+    Keystroke,
+    /// Windows only: insert text directly via `SendInput` with
+    /// `KEYEVENTF_UNICODE`, bypassing the clipboard entirely. Avoids races
+    /// with clipboard-manager utilities that grab focus on every clipboard
+    /// write. Falls back to `Clipboard` on other platforms.
+    UnicodeSendInput,
+}
+
+impl Default for InjectMethod {
+    fn default() -> Self {
+        Self::Clipboard
+    }
+}
+
+/// Widget appearance — controls the egui `Visuals` applied to the floating
+/// window.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    Dark,
+    Light,
+    /// Follows the OS theme. No OS theme-detection binding exists in this
+    /// crate yet, so this currently behaves like `Dark`.
+    System,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::Dark
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSettings {
+    pub operating_mode: OperatingMode,
+
+    pub stt_model: String,
+    pub stt_language: String,
+    /// Prefer GPU acceleration (CUDA/Metal/Vulkan) for Whisper inference
+    /// when the binary was built with a GPU backend. Falls back to CPU
+    /// automatically if GPU init fails.
+    pub use_gpu: bool,
+    /// Phrases to drop from a transcription outright, matched
+    /// case-insensitively against a whole segment's text (e.g. the stock
+    /// "ขอบคุณครับ"/"Thanks for watching" Whisper emits on silence). See
+    /// `stt::hallucination`.
+    pub hallucination_blocklist: Vec<String>,
+    pub stt_sampling_strategy: SttSamplingStrategy,
+    /// Candidates sampled per token under `SttSamplingStrategy::Greedy`.
+    pub stt_best_of: i32,
+    /// Candidate sequences explored under `SttSamplingStrategy::BeamSearch`.
+    pub stt_beam_size: i32,
+    /// CPU threads whisper.cpp uses for inference. 0 picks automatically
+    /// (physical core count, capped at 8 — see `WhisperEngine::optimal_threads`)
+    /// until `stt_threads_calibrated` runs once, after which it holds the
+    /// benchmarked value (see `stt::calibration`).
+    pub stt_n_threads: i32,
+    /// Set once `stt::calibration::calibrate` has timed 1..N threads against
+    /// the loaded model and written its pick into `stt_n_threads`, so
+    /// calibration only ever runs on first launch. Reset to `false` (and
+    /// `stt_n_threads` to `0`) to force it to run again, e.g. after a CPU
+    /// upgrade.
+    pub stt_threads_calibrated: bool,
+    /// Fixed vocabulary/style hint passed to Whisper as its initial prompt
+    /// whenever a call doesn't supply its own (the pipeline's dynamic
+    /// context-built prompt takes priority when present). Empty disables it.
+    pub stt_initial_prompt: String,
+    /// Decode per-token timestamps and derive `Segment::words` from them,
+    /// for the history panel's word-by-word karaoke playback review. Off by
+    /// default since it adds decode overhead. See `stt::engine::WordTiming`.
+    pub stt_word_timestamps: bool,
+    /// Offload transcription to a whisper.cpp server or OpenAI-compatible
+    /// `/v1/audio/transcriptions` endpoint instead of the local
+    /// `WhisperEngine`. See `stt::remote::RemoteSttEngine`.
+    pub stt_remote_enabled: bool,
+    /// Base URL of the remote STT server, e.g. `http://192.168.1.20:8080`.
+    pub stt_remote_url: String,
+    /// A `keyring:` reference once migrated into the OS keychain by
+    /// `AppSettings::load`, or a plaintext key before its first migration.
+    /// Resolve with `secrets::resolve(&self.stt_remote_api_key,
+    /// secrets::STT_REMOTE)` rather than reading this field directly. Empty
+    /// for a bare LAN whisper.cpp server that doesn't require one.
+    pub stt_remote_api_key: Option<String>,
+    /// Offload transcription to a self-hosted Vosk (or compatible)
+    /// recognizer server instead of the local `WhisperEngine`. Takes
+    /// priority over `stt_remote_enabled` if both are set — see
+    /// `PipelineOrchestrator::active_stt`. See `stt::vosk::VoskEngine`.
+    pub stt_vosk_enabled: bool,
+    /// Base URL of the Vosk recognizer server, e.g. `http://192.168.1.20:2700`.
+    pub stt_vosk_url: String,
+    /// Label each transcript segment as one of two speakers by clustering
+    /// recording level, for two-party dictation (e.g. doctor/patient) —
+    /// see `stt::diarize`. Off by default since it only makes sense for
+    /// that use case, not solo dictation.
+    pub stt_diarization_enabled: bool,
+    /// Maximum time a single transcription is allowed to run before the
+    /// pipeline watchdog aborts it and reports a timeout error instead of
+    /// leaving the widget stuck showing "Transcribing" forever. Covers both
+    /// the local `WhisperEngine` and the remote/Vosk backends.
+    pub stt_timeout_secs: u64,
+
+    /// How numbers in the final transcript are rendered. See
+    /// `text::numbers`.
+    pub number_format: NumberFormat,
+    /// Standardize whitespace around punctuation and Thai/Latin script
+    /// boundaries. See `text::spacing`.
+    pub normalize_punctuation_spacing: bool,
+
+    pub llm_enabled: bool,
+    pub llm_provider: LlmProvider,
+    pub llm_model: String,
+    pub llm_base_url: String,
+    /// A `keyring:` reference once migrated into the OS keychain by
+    /// `AppSettings::load`, or a plaintext key before its first migration.
+    /// Resolve with `secrets::resolve(&self.llm_api_key, secrets::PRIMARY)`
+    /// rather than reading this field directly.
+    pub llm_api_key: Option<String>,
+    pub llm_temperature: f32,
+    pub llm_timeout_secs: u64,
+    /// Ordered fallback providers, tried in turn when the primary provider
+    /// (`llm_provider`/`llm_base_url`/...) errors or times out.
+    pub llm_fallback_providers: Vec<LlmProviderConfig>,
+    /// Daily token cap shared across all configured cloud providers. 0
+    /// means unlimited. Once hit, `OperatingMode` switches to `Fast` and
+    /// the UI shows a warning until it is dismissed or the mode is changed.
+    /// The mode is not switched back when the day rolls over.
+    pub llm_daily_token_budget: u64,
+    /// Alternate prompt `PromptBuilder` uses to restructure the corrected
+    /// text — a numbered/bulleted list or a SOAP note — instead of the
+    /// default word-for-word cleanup. See `CorrectionStyle`.
+    pub llm_correction_style: CorrectionStyle,
+    /// Ollama's `keep_alive` request parameter, e.g. `"5m"`, `"-1"`
+    /// (forever), `"0"` (unload immediately). Empty omits the parameter and
+    /// uses Ollama's own default. Ignored by every other provider. See
+    /// `llm::keep_alive_pinger`.
+    pub ollama_keep_alive: String,
+    /// How often the keep-alive pinger re-pings Ollama during a running
+    /// session, keeping the correction model resident so it doesn't cold
+    /// start after a lull in dictation. 0 disables the pinger. Ignored by
+    /// every other provider.
+    pub ollama_keep_alive_ping_secs: u64,
+    /// Number of `(raw_text, context)` correction results kept in the LLM
+    /// result cache. A repeated utterance under the same rolling context
+    /// (short acknowledgements like "ครับ"/"โอเค", or a re-dictated line)
+    /// returns the cached correction instead of hitting the network. 0
+    /// disables the cache entirely.
+    pub llm_cache_size: usize,
+
+    /// A key combo like `"F9"` or `"Ctrl+Shift+T"`, or a mouse button
+    /// (`"Mouse4"`/`"Mouse5"`) for a foot pedal or extra mouse button.
+    pub push_to_talk_key: String,
+    pub toggle_visibility_key: String,
+    /// Pauses/resumes an in-progress recording without finalizing it, so the
+    /// user can step away mid-dictation and keep appending to the same
+    /// buffer. Empty string disables the feature entirely.
+    pub pause_resume_key: String,
+    /// Flips `translate_to_english` on press. Empty string disables the
+    /// feature entirely.
+    pub translate_toggle_key: String,
+    /// Extra push-to-talk keys, each bound to a partial settings override —
+    /// e.g. F10 for "English Fast", F11 for "Translate". Empty means
+    /// `push_to_talk_key` is the only push-to-talk trigger. See
+    /// `HotkeyPreset`.
+    pub hotkey_presets: Vec<HotkeyPreset>,
+
+    /// When on, Whisper runs its translate task instead of plain
+    /// transcription — decoding the spoken language straight into English
+    /// text, skipped past LLM correction (its prompts are Thai-specific)
+    /// and injected as-is. Toggled by `translate_toggle_key` (or set by a
+    /// `HotkeyPreset`), for users who want to write in English from Thai speech.
+    pub translate_to_english: bool,
+
+    /// Skip straight to `OperatingMode::Fast` for utterances shorter than
+    /// `auto_mode_short_secs`, regardless of the configured
+    /// `operating_mode` — short commands rarely need LLM correction, and
+    /// skipping it cuts their latency. Longer dictations still use whatever
+    /// `operating_mode` is configured. See
+    /// `AppSettings::effective_operating_mode_for_duration`.
+    pub auto_mode_by_length: bool,
+    /// Utterances shorter than this many seconds are treated as `Fast`
+    /// under `auto_mode_by_length`. Ignored when that's off.
+    pub auto_mode_short_secs: f32,
+    /// Run a throwaway STT decode plus an Ollama keep-alive request right
+    /// after startup, so the memory paging/threadpool spin-up and model
+    /// load that make the very first real request slower happen before the
+    /// user is waiting on them. See `stt::WhisperEngine::warm_up` and
+    /// `llm::LlmCorrector::warm_up`.
+    pub warm_up_enabled: bool,
+
+    /// Off by default — reads the clipboard's trailing lines and feeds them
+    /// to `ContextManager::build_context` as `target_context`, so
+    /// corrections match the terminology and tone of whatever document the
+    /// user is dictating into. Requires explicit opt-in since it means the
+    /// app reads clipboard content it didn't itself put there. Ignored
+    /// under `privacy_mode`, same as the rest of context-building.
+    pub target_context_enabled: bool,
+    /// Trailing clipboard lines captured when `target_context_enabled` is
+    /// on. See `inject::clipboard::read_recent_lines`.
+    pub target_context_lines: usize,
+
+    /// Pins or disables domain detection for dictation that doesn't match
+    /// any configured `AppProfile`. A matched profile's own
+    /// `AppProfile::domain_override` takes priority over this. `None` runs
+    /// `DomainDetector::detect` fresh on every utterance, as before.
+    pub domain_override: Option<DomainOverride>,
+
+    pub context_window_size: usize,
+    pub context_reset_silence_secs: u64,
+    /// Per-application context profiles, matched against the focused
+    /// window at correction time. Empty means everything uses one shared
+    /// context and vocabulary, regardless of which app has focus.
+    pub profiles: Vec<AppProfile>,
+
+    pub widget_position: Option<(f32, f32)>,
+    /// Snap the widget flush against a screen edge/corner once it's dragged
+    /// within `snap_margin` pixels of one.
+    pub snap_to_edge: bool,
+    /// Distance from a monitor edge, in pixels, that triggers snapping.
+    pub snap_margin: f32,
+    pub auto_inject: bool,
+    pub show_raw_text: bool,
+    /// Seconds a completed correction stays shown before auto-clearing. 0
+    /// makes it sticky — it stays until the next recording or a manual
+    /// dismiss, useful for reviewing long corrected paragraphs.
+    pub result_display_secs: u64,
+    pub theme: Theme,
+    /// Accent color for selection/hover highlights, as a 6-digit hex string
+    /// (no leading `#`), e.g. `"4A9EFF"`.
+    pub accent_color: String,
+    /// Opacity of the widget's background frame, from 0.0 (fully
+    /// transparent) to 1.0 (fully opaque).
+    pub widget_opacity: f32,
+
+    pub audio_device: Option<String>,
+    pub max_recording_secs: u64,
+    /// Seconds of audio kept in memory before older samples spill to a temp
+    /// file (see `AudioBuffer::with_spill`). 0 disables spilling, keeping
+    /// the whole recording in RAM up to `max_recording_secs`.
+    pub audio_spill_threshold_secs: u64,
+    /// Energy-based VAD threshold (see `audio::VadDetector`). Lower values
+    /// pick up quieter speech but also more background noise.
+    pub vad_threshold: f32,
+    pub vad_backend: VadBackend,
+    /// Run `audio::denoise` between resampling and VAD, cutting background
+    /// noise floor (fan, hum, hiss) before Whisper sees the audio.
+    pub noise_suppression: bool,
+    /// Seconds of audio continuously buffered before a recording starts and
+    /// prepended to it on `StartRecording`, so word onsets spoken just
+    /// before the hotkey press aren't clipped. 0 disables pre-roll.
+    pub preroll_secs: f32,
+
+    pub inject_method: InjectMethod,
+    /// Delay between synthesized key events when `inject_method` is
+    /// `Keystroke`. Higher values are more reliable on slower apps.
+    pub inject_keystroke_delay_ms: u64,
+    /// Maximum time the pipeline watchdog gives the inject stage before
+    /// aborting it and reporting a timeout error, e.g. when the focused
+    /// window stops responding to synthesized input entirely.
+    pub inject_timeout_secs: u64,
+
+    /// Append every final transcript, with a timestamp header, to
+    /// `note_file_path` instead of (or in addition to, if `auto_inject` is
+    /// also on) injecting it into the focused window. See `inject::note`.
+    pub append_to_note: bool,
+    /// Destination file for `append_to_note`, e.g. a running daily notes
+    /// Markdown file. Ignored while `append_to_note` is off.
+    pub note_file_path: Option<String>,
+
+    /// POST a JSON payload (raw text, corrected text, timestamps, domain)
+    /// to `webhook_url` after each utterance, for piping dictations into
+    /// n8n/Zapier/Obsidian plugins and other automations. See
+    /// `integrations::webhook`.
+    pub webhook_enabled: bool,
+    /// Destination URL for `webhook_enabled`. Ignored while
+    /// `webhook_enabled` is off.
+    pub webhook_url: Option<String>,
+
+    /// Check GitHub releases for a newer version on startup and surface a
+    /// notification badge with the changelog. Off by default since it's an
+    /// outbound network call the offline-first widget wouldn't otherwise
+    /// make. See `updater`.
+    pub check_for_updates: bool,
+
+    /// Expose a localhost-only HTTP control API (`/record/start`,
+    /// `/record/stop`, `/status`, `/history`) so external tools, Stream
+    /// Deck plugins, or scripts can drive the pipeline without the hotkey.
+    /// Off by default. See `control::spawn_control_server`.
+    pub control_api_enabled: bool,
+    /// Port the control API binds to on `127.0.0.1`. Ignored while
+    /// `control_api_enabled` is off.
+    pub control_api_port: u16,
+    /// Shared secret the control API requires as a `X-Control-Token` header
+    /// on every request when set. Localhost-bound doesn't mean trusted: any
+    /// webpage open in the user's browser can also reach `127.0.0.1`, and
+    /// `/history` returns full dictation history. Left unset by default so
+    /// the API keeps working out of the box for local scripts and Stream
+    /// Deck plugins, but a request carrying a browser `Origin` header is
+    /// always rejected regardless of this setting, since none of our
+    /// legitimate clients are web pages.
+    pub control_api_token: Option<String>,
+    /// Expose a Unix-domain-socket command interface at
+    /// `AppPaths::ipc_socket_path()` mirroring the pipeline's
+    /// start/stop/pause/resume/toggle/cancel commands, for window-manager
+    /// keybindings and shell scripts — notably on Wayland, where global
+    /// hotkeys via `rdev` are unreliable. Linux only; ignored elsewhere.
+    /// See `control::socket`.
+    pub ipc_socket_enabled: bool,
+
+    /// On laptops, automatically drop to `power_saver_model` and
+    /// `OperatingMode::Fast` while running on battery, and restore the
+    /// configured model/mode once plugged back in. See `power`. Doubles
+    /// as the override toggle — turn off to keep the configured
+    /// model/mode regardless of power source.
+    pub power_aware_mode: bool,
+    /// Model switched to while on battery under `power_aware_mode`.
+    pub power_saver_model: String,
+
+    /// Archive each captured utterance as a WAV file under
+    /// `AppPaths::recordings_dir()`, for debugging misrecognitions.
+    pub save_recordings: bool,
+    pub recordings_max_files: usize,
+    pub recordings_max_mb: u64,
+
+    /// Include raw/corrected transcript text in the session log
+    /// (`logging::init`). Disable for privacy if session logs might be
+    /// shared in a bug report; stage timings and errors are still logged
+    /// either way. See `logging::redact_transcript`.
+    pub log_transcripts: bool,
+
+    /// Guarantees nothing leaves memory: forces `OperatingMode::Fast`
+    /// (no cloud LLM call), and disables history, recording save,
+    /// vocabulary persistence, and transcript logging, regardless of what
+    /// those individual settings say. Checked centrally through
+    /// `AppSettings::effective_operating_mode`/`persist_enabled` rather
+    /// than scattered across call sites, so this one flag can't be
+    /// half-applied.
+    pub privacy_mode: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            operating_mode: OperatingMode::Standard,
+            stt_model: "thonburian-medium".into(),
+            stt_language: "th".into(),
+            use_gpu: true,
+            hallucination_blocklist: crate::stt::hallucination::DEFAULT_BLOCKLIST
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            stt_sampling_strategy: SttSamplingStrategy::Greedy,
+            stt_best_of: 1,
+            stt_beam_size: 5,
+            stt_n_threads: 0,
+            stt_threads_calibrated: false,
+            stt_initial_prompt: String::new(),
+            stt_word_timestamps: false,
+            stt_remote_enabled: false,
+            stt_remote_url: String::new(),
+            stt_remote_api_key: None,
+            stt_vosk_enabled: false,
+            stt_vosk_url: String::new(),
+            stt_diarization_enabled: false,
+            stt_timeout_secs: 60,
+            number_format: NumberFormat::Arabic,
+            normalize_punctuation_spacing: true,
+            llm_enabled: true,
+            llm_provider: LlmProvider::Ollama,
+            llm_model: "qwen2.5:3b".into(),
+            llm_base_url: "http://localhost:11434".into(),
+            llm_api_key: None,
+            llm_temperature: 0.3,
+            llm_timeout_secs: 10,
+            llm_fallback_providers: Vec::new(),
+            llm_daily_token_budget: 0,
+            llm_correction_style: CorrectionStyle::Standard,
+            ollama_keep_alive: "5m".into(),
+            ollama_keep_alive_ping_secs: 240,
+            llm_cache_size: 50,
+            push_to_talk_key: "F9".into(),
+            toggle_visibility_key: "Ctrl+Shift+T".into(),
+            pause_resume_key: "F10".into(),
+            translate_toggle_key: String::new(),
+            hotkey_presets: Vec::new(),
+            translate_to_english: false,
+            auto_mode_by_length: false,
+            auto_mode_short_secs: 3.0,
+            warm_up_enabled: true,
+            target_context_enabled: false,
+            target_context_lines: 5,
+            domain_override: None,
+            context_window_size: 3,
+            context_reset_silence_secs: 120,
+            profiles: Vec::new(),
+            widget_position: None,
+            snap_to_edge: true,
+            snap_margin: 20.0,
+            auto_inject: true,
+            show_raw_text: true,
+            result_display_secs: 0,
+            theme: Theme::Dark,
+            accent_color: "4A9EFF".into(),
+            widget_opacity: 0.9,
+            audio_device: None,
+            max_recording_secs: 60,
+            audio_spill_threshold_secs: 60,
+            vad_threshold: 0.0005,
+            vad_backend: VadBackend::Energy,
+            noise_suppression: false,
+            preroll_secs: 1.0,
+            inject_method: InjectMethod::Clipboard,
+            inject_keystroke_delay_ms: 5,
+            inject_timeout_secs: 10,
+            append_to_note: false,
+            note_file_path: None,
+            webhook_enabled: false,
+            webhook_url: None,
+            check_for_updates: false,
+            control_api_enabled: false,
+            control_api_port: 8765,
+            control_api_token: None,
+            ipc_socket_enabled: false,
+            power_aware_mode: true,
+            power_saver_model: "thonburian-small".into(),
+            save_recordings: false,
+            recordings_max_files: 100,
+            recordings_max_mb: 500,
+            log_transcripts: true,
+            privacy_mode: false,
+        }
+    }
+}
+
+impl AppSettings {
+    pub fn load() -> Self {
+        let path = AppPaths::settings_path();
+        let mut settings = if path.exists() {
+            let content = std::fs::read_to_string(&path).unwrap_or_default();
+            toml::from_str(&content).unwrap_or_default()
+        } else {
+            Self::default()
+        };
+        if settings.migrate_secrets() {
+            let _ = settings.save();
+        }
+        settings
+    }
+
+    /// One-time migration of plaintext API keys into the OS keychain,
+    /// leaving only a `keyring:` reference behind in `settings.toml`. Safe
+    /// to call on every load — already-migrated fields are left alone.
+    /// Returns whether anything changed, so `load` knows to persist it.
+    fn migrate_secrets(&mut self) -> bool {
+        let mut migrated = secrets::migrate_field(&mut self.llm_api_key, secrets::PRIMARY);
+        for (i, provider) in self.llm_fallback_providers.iter_mut().enumerate() {
+            migrated |= secrets::migrate_field(&mut provider.api_key, &secrets::fallback(i));
+        }
+        migrated |= secrets::migrate_field(&mut self.stt_remote_api_key, secrets::STT_REMOTE);
+        migrated
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = AppPaths::settings_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string_pretty(self)?;
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// True when no settings file has been saved yet.
+    pub fn is_first_run() -> bool {
+        !AppPaths::settings_path().exists()
+    }
+
+    /// The operating mode the pipeline should actually run with —
+    /// `OperatingMode::Fast` whenever `privacy_mode` is on, since that's
+    /// the only mode that never calls out to a cloud LLM, regardless of
+    /// what `operating_mode` is configured to.
+    pub fn effective_operating_mode(&self) -> OperatingMode {
+        if self.privacy_mode {
+            OperatingMode::Fast
+        } else {
+            self.operating_mode
+        }
+    }
+
+    /// `effective_operating_mode`, additionally dropping to `Fast` when
+    /// `auto_mode_by_length` is on and `duration_secs` is under
+    /// `auto_mode_short_secs` — short push-to-talk commands skip LLM
+    /// correction even if the configured mode would otherwise run it.
+    pub fn effective_operating_mode_for_duration(&self, duration_secs: f32) -> OperatingMode {
+        let mode = self.effective_operating_mode();
+        if mode != OperatingMode::Fast
+            && self.auto_mode_by_length
+            && duration_secs < self.auto_mode_short_secs
+        {
+            OperatingMode::Fast
+        } else {
+            mode
+        }
+    }
+
+    /// Whether anything is allowed to persist to disk (history, saved
+    /// recordings, learned vocabulary, transcript content in the session
+    /// log) — always `false` under `privacy_mode`.
+    pub fn persist_enabled(&self) -> bool {
+        !self.privacy_mode
+    }
+
+    /// Checks every setting that would make the pipeline malfunction rather
+    /// than letting it fail confusingly later (e.g. mid-recording), and
+    /// reports every problem found instead of stopping at the first one, so
+    /// the UI can show the user a complete list to fix in one pass.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if self.llm_temperature < 0.0 || self.llm_temperature > 2.0 {
+            issues.push(ValidationIssue::new(
+                "llm_temperature",
+                "LLM temperature must be between 0.0 and 2.0",
+                "Set it to a value like 0.3 for consistent corrections",
+            ));
+        }
+        if self.llm_timeout_secs == 0 {
+            issues.push(ValidationIssue::new(
+                "llm_timeout_secs",
+                "LLM timeout must be greater than 0 seconds",
+                "Set it to at least 5 seconds",
+            ));
+        }
+        if self.stt_timeout_secs == 0 {
+            issues.push(ValidationIssue::new(
+                "stt_timeout_secs",
+                "STT timeout must be greater than 0 seconds",
+                "Set it to at least 30 seconds",
+            ));
+        }
+        if self.inject_timeout_secs == 0 {
+            issues.push(ValidationIssue::new(
+                "inject_timeout_secs",
+                "Inject timeout must be greater than 0 seconds",
+                "Set it to at least 5 seconds",
+            ));
+        }
+        if self.max_recording_secs == 0 {
+            issues.push(ValidationIssue::new(
+                "max_recording_secs",
+                "Max recording length must be greater than 0 seconds",
+                "Set it to at least 10 seconds",
+            ));
+        }
+        if self.context_window_size == 0 {
+            issues.push(ValidationIssue::new(
+                "context_window_size",
+                "Context window size must be at least 1 sentence",
+                "Set it to 3 for a short rolling context",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.vad_threshold) {
+            issues.push(ValidationIssue::new(
+                "vad_threshold",
+                "VAD threshold must be between 0.0 and 1.0",
+                "Set it to 0.0005 as a starting point and adjust by ear",
+            ));
+        }
+        if self.preroll_secs < 0.0 {
+            issues.push(ValidationIssue::new(
+                "preroll_secs",
+                "Pre-roll duration cannot be negative",
+                "Set it to 0 to disable pre-roll",
+            ));
+        }
+        if self.stt_best_of < 1 {
+            issues.push(ValidationIssue::new(
+                "stt_best_of",
+                "STT best-of must be at least 1",
+                "Set it to 1",
+            ));
+        }
+        if self.stt_beam_size < 1 {
+            issues.push(ValidationIssue::new(
+                "stt_beam_size",
+                "STT beam size must be at least 1",
+                "Set it to 5",
+            ));
+        }
+        if self.stt_n_threads < 0 {
+            issues.push(ValidationIssue::new(
+                "stt_n_threads",
+                "STT thread count cannot be negative",
+                "Set it to 0 to pick the thread count automatically",
+            ));
+        }
+        if stt::find_model(&self.stt_model).is_none() {
+            issues.push(ValidationIssue::new(
+                "stt_model",
+                format!("Unknown STT model id: {}", self.stt_model),
+                "Pick a model id from stt::ALL_MODELS, e.g. \"thonburian-medium\"",
+            ));
+        }
+        if hotkey::parse_combo(&self.push_to_talk_key).is_none() {
+            issues.push(ValidationIssue::new(
+                "push_to_talk_key",
+                format!("Unrecognized push-to-talk key: {}", self.push_to_talk_key),
+                "Use a combo like \"F9\" or \"Ctrl+Shift+T\"",
+            ));
+        }
+        if hotkey::parse_combo(&self.toggle_visibility_key).is_none() {
+            issues.push(ValidationIssue::new(
+                "toggle_visibility_key",
+                format!(
+                    "Unrecognized toggle-visibility key: {}",
+                    self.toggle_visibility_key
+                ),
+                "Use a combo like \"F9\" or \"Ctrl+Shift+T\"",
+            ));
+        }
+        if !self.pause_resume_key.is_empty()
+            && hotkey::parse_combo(&self.pause_resume_key).is_none()
+        {
+            issues.push(ValidationIssue::new(
+                "pause_resume_key",
+                format!("Unrecognized pause-resume key: {}", self.pause_resume_key),
+                "Use a combo like \"F10\", or clear it to disable pause/resume",
+            ));
+        }
+        if !self.translate_toggle_key.is_empty()
+            && hotkey::parse_combo(&self.translate_toggle_key).is_none()
+        {
+            issues.push(ValidationIssue::new(
+                "translate_toggle_key",
+                format!(
+                    "Unrecognized translate-toggle key: {}",
+                    self.translate_toggle_key
+                ),
+                "Use a combo like \"Ctrl+Shift+E\", or clear it to disable translate mode",
+            ));
+        }
+        for preset in &self.hotkey_presets {
+            if hotkey::parse_combo(&preset.key).is_none() {
+                issues.push(ValidationIssue::new(
+                    "hotkey_presets",
+                    format!(
+                        "Unrecognized key for hotkey preset \"{}\": {}",
+                        preset.id, preset.key
+                    ),
+                    "Use a combo like \"F10\" or \"Ctrl+Shift+E\"",
+                ));
+            }
+            if let Some(model) = &preset.stt_model {
+                if stt::find_model(model).is_none() {
+                    issues.push(ValidationIssue::new(
+                        "hotkey_presets",
+                        format!(
+                            "Unknown STT model id for hotkey preset \"{}\": {}",
+                            preset.id, model
+                        ),
+                        "Pick a model id from stt::ALL_MODELS, e.g. \"thonburian-medium\"",
+                    ));
+                }
+            }
+        }
+        if self.auto_mode_by_length && self.auto_mode_short_secs <= 0.0 {
+            issues.push(ValidationIssue::new(
+                "auto_mode_short_secs",
+                "Auto-mode-by-length threshold must be positive",
+                "Set it to something like 3.0, or disable auto_mode_by_length",
+            ));
+        }
+        if self.save_recordings && self.recordings_max_files == 0 {
+            issues.push(ValidationIssue::new(
+                "recordings_max_files",
+                "Recordings retention must allow at least 1 file",
+                "Set it to 100, or disable save_recordings",
+            ));
+        }
+        if parse_hex_color(&self.accent_color).is_none() {
+            issues.push(ValidationIssue::new(
+                "accent_color",
+                format!(
+                    "Accent color must be a 6-digit hex string, e.g. \"4A9EFF\": got \"{}\"",
+                    self.accent_color
+                ),
+                "Use a 6-digit hex string with no leading '#', e.g. \"4A9EFF\"",
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.widget_opacity) {
+            issues.push(ValidationIssue::new(
+                "widget_opacity",
+                "Widget opacity must be between 0.0 and 1.0",
+                "Set it to 0.9 for a mostly-opaque widget",
+            ));
+        }
+        if self.append_to_note
+            && self
+                .note_file_path
+                .as_ref()
+                .map_or(true, |p| p.trim().is_empty())
+        {
+            issues.push(ValidationIssue::new(
+                "note_file_path",
+                "Append-to-note is enabled but no note file is set",
+                "Set note_file_path to a file, e.g. \"~/notes/daily.md\"",
+            ));
+        }
+        if self.webhook_enabled
+            && self
+                .webhook_url
+                .as_ref()
+                .map_or(true, |u| u.trim().is_empty())
+        {
+            issues.push(ValidationIssue::new(
+                "webhook_url",
+                "Webhook is enabled but no URL is set",
+                "Set webhook_url to an endpoint, e.g. \"https://example.com/hooks/dictation\"",
+            ));
+        }
+        if self.control_api_enabled && self.control_api_port == 0 {
+            issues.push(ValidationIssue::new(
+                "control_api_port",
+                "Control API port must be greater than 0",
+                "Set it to a free local port, e.g. 8765",
+            ));
+        }
+        if self.power_aware_mode && self.power_saver_model.trim().is_empty() {
+            issues.push(ValidationIssue::new(
+                "power_saver_model",
+                "Power-aware mode is enabled but no battery-saver model is set",
+                "Set power_saver_model to a smaller model id, e.g. \"thonburian-small\"",
+            ));
+        }
+        if self.stt_remote_enabled && self.stt_remote_url.trim().is_empty() {
+            issues.push(ValidationIssue::new(
+                "stt_remote_url",
+                "Remote STT is enabled but no server URL is set",
+                "Set stt_remote_url to your whisper.cpp server, e.g. \"http://192.168.1.20:8080\"",
+            ));
+        }
+        if self.stt_vosk_enabled && self.stt_vosk_url.trim().is_empty() {
+            issues.push(ValidationIssue::new(
+                "stt_vosk_url",
+                "Vosk STT is enabled but no server URL is set",
+                "Set stt_vosk_url to your Vosk server, e.g. \"http://192.168.1.20:2700\"",
+            ));
+        }
+        if self.snap_margin < 0.0 {
+            issues.push(ValidationIssue::new(
+                "snap_margin",
+                "Snap margin cannot be negative",
+                "Set it to 20 pixels",
+            ));
+        }
+
+        issues
+    }
+}
+
+/// One problem found by `AppSettings::validate`, naming the offending field
+/// alongside a fix the user can act on immediately.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    /// The `AppSettings` field this issue is about, e.g. `"vad_threshold"`.
+    pub field: &'static str,
+    pub message: String,
+    pub suggestion: String,
+}
+
+impl ValidationIssue {
+    fn new(field: &'static str, message: impl Into<String>, suggestion: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+            suggestion: suggestion.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.message, self.suggestion)
+    }
+}
+
+/// Parses a 6-digit hex color string (no leading `#`) into RGB components.
+pub fn parse_hex_color(s: &str) -> Option<(u8, u8, u8)> {
+    if s.len() != 6 {
+        return None;
+    }
+    let r = u8::from_str_radix(&s[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&s[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&s[4..6], 16).ok()?;
+    Some((r, g, b))
+}
@@ -0,0 +1,44 @@
+//! Layered configuration on top of `settings.toml`, for scripted and kiosk
+//! deployments that can't (or shouldn't) hand-edit the config file:
+//!
+//! ```text
+//! CLI flag  >  VTT_* environment variable  >  settings.toml
+//! ```
+//!
+//! Each layer only overrides the fields it explicitly sets — anything not
+//! passed on the command line or through the environment keeps whatever
+//! `settings.toml` (or `AppSettings::default()`) already had.
+
+use super::{AppSettings, OperatingMode};
+
+/// Applies `--mode`/`--model`/`--hotkey` and their `VTT_MODE`/`VTT_MODEL`/
+/// `VTT_HOTKEY` environment equivalents to `settings`, CLI taking priority.
+/// Call once, right after `AppSettings::load()`.
+pub fn apply(settings: &mut AppSettings) {
+    if let Some(mode) = crate::cli::flag_arg("--mode").or_else(|| std::env::var("VTT_MODE").ok()) {
+        match parse_mode(&mode) {
+            Some(parsed) => settings.operating_mode = parsed,
+            None => log::warn!("Ignoring unrecognized operating mode override: {}", mode),
+        }
+    }
+
+    if let Some(model) = crate::cli::flag_arg("--model").or_else(|| std::env::var("VTT_MODEL").ok())
+    {
+        settings.stt_model = model;
+    }
+
+    if let Some(hotkey) =
+        crate::cli::flag_arg("--hotkey").or_else(|| std::env::var("VTT_HOTKEY").ok())
+    {
+        settings.push_to_talk_key = hotkey;
+    }
+}
+
+fn parse_mode(s: &str) -> Option<OperatingMode> {
+    match s.to_ascii_lowercase().as_str() {
+        "fast" => Some(OperatingMode::Fast),
+        "standard" => Some(OperatingMode::Standard),
+        "context" => Some(OperatingMode::Context),
+        _ => None,
+    }
+}
@@ -0,0 +1,205 @@
+//! Platform-specific config/data directory resolution.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// Set once by `main` from `cli::portable_mode()`, before any other
+/// `AppPaths` method runs — including the CLI batch paths
+/// (`cli::run`/`cli::run_benchmark`). Left unset (i.e. `false`) in tests,
+/// which don't care about portability.
+static PORTABLE: OnceLock<bool> = OnceLock::new();
+
+/// Resolved application directories — settings, user data, and models.
+pub struct AppPaths;
+
+impl AppPaths {
+    /// Enables portable mode process-wide. Must be called at most once,
+    /// before any other `AppPaths` method — later calls are ignored, same
+    /// as every other `OnceLock`-backed setting in this crate.
+    pub fn set_portable(enabled: bool) {
+        let _ = PORTABLE.set(enabled);
+    }
+
+    fn is_portable() -> bool {
+        *PORTABLE.get_or_init(|| false)
+    }
+
+    /// Directory the running executable lives in — the root every path
+    /// below is resolved relative to in portable mode, so config/models/
+    /// history all travel with the binary (a USB stick, a locked-down
+    /// machine with no writable home directory).
+    fn portable_root() -> PathBuf {
+        std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("."))
+    }
+
+    /// Directory for settings.toml and user-vocab.json.
+    /// Linux: ~/.config/voice-to-text/, Windows: %APPDATA%\voice-to-text\,
+    /// macOS: ~/Library/Application Support/voice-to-text/
+    /// Portable mode: `<exe_dir>/data/`.
+    pub fn config_dir() -> PathBuf {
+        if Self::is_portable() {
+            return Self::portable_root().join("data");
+        }
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("voice-to-text")
+    }
+
+    /// Directory for downloaded GGML/GGUF model files.
+    /// Linux: ~/.local/share/voice-to-text/models/
+    /// Portable mode: `<exe_dir>/data/models/`.
+    pub fn models_dir() -> PathBuf {
+        if Self::is_portable() {
+            return Self::portable_root().join("data").join("models");
+        }
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("voice-to-text")
+            .join("models")
+    }
+
+    pub fn settings_path() -> PathBuf {
+        Self::config_dir().join("settings.toml")
+    }
+
+    pub fn vocab_path() -> PathBuf {
+        Self::config_dir().join("user-vocab.json")
+    }
+
+    /// Per-profile vocabulary file for `AppProfile`-scoped `ContextManager`s.
+    pub fn vocab_path_for_profile(profile: &str) -> PathBuf {
+        Self::config_dir().join(format!("user-vocab-{}.json", profile))
+    }
+
+    /// Append-only log of past transcriptions (one JSON object per line).
+    pub fn history_path() -> PathBuf {
+        Self::config_dir().join("history.jsonl")
+    }
+
+    /// User-editable map of spoken phrases → dictation commands.
+    pub fn commands_path() -> PathBuf {
+        Self::config_dir().join("commands.toml")
+    }
+
+    /// User-editable domain keyword lists for `DomainDetector`.
+    pub fn domains_path() -> PathBuf {
+        Self::config_dir().join("domains.toml")
+    }
+
+    /// User-editable per-app output rules for `text::formatting::FormattingEngine`.
+    pub fn formatting_rules_path() -> PathBuf {
+        Self::config_dir().join("formatting.toml")
+    }
+
+    /// User-editable trigger phrase → expansion map for
+    /// `text::snippets::SnippetExpander`.
+    pub fn snippets_path() -> PathBuf {
+        Self::config_dir().join("snippets.toml")
+    }
+
+    /// Directory holding one TOML file per named `config::profiles`
+    /// (whole-settings) profile.
+    pub fn profiles_dir() -> PathBuf {
+        Self::config_dir().join("profiles")
+    }
+
+    /// Path to a single named profile's settings file.
+    pub fn profile_path(name: &str) -> PathBuf {
+        Self::profiles_dir().join(format!("{}.toml", name))
+    }
+
+    /// User-editable list of words `text::profanity` masks before
+    /// injection, one per line. Absent by default — masking is a no-op
+    /// until the user creates this file.
+    pub fn blocklist_path() -> PathBuf {
+        Self::config_dir().join("blocklist.txt")
+    }
+
+    /// Persisted daily token/request counters for `llm::usage::UsageTracker`.
+    pub fn usage_path() -> PathBuf {
+        Self::config_dir().join("llm-usage.json")
+    }
+
+    /// Crash-recovery checkpoint written by `pipeline::recovery` after each
+    /// stage past STT, and cleared once its text is actually injected.
+    pub fn recovery_path() -> PathBuf {
+        Self::config_dir().join("recovery.json")
+    }
+
+    /// Destination for a binary fetched by `updater::download_update`,
+    /// named after its version so re-downloading a different release
+    /// doesn't clobber one still waiting to be run.
+    pub fn update_download_path(version: &str) -> PathBuf {
+        let suffix = if cfg!(target_os = "windows") {
+            ".exe"
+        } else {
+            ""
+        };
+        Self::config_dir()
+            .join("updates")
+            .join(format!("voice-to-text-{}{}", version, suffix))
+    }
+
+    /// User-editable prompt overrides for `PromptBuilder`, e.g.
+    /// `system_th.txt` and `examples_th.txt`.
+    pub fn prompts_dir() -> PathBuf {
+        Self::config_dir().join("prompts")
+    }
+
+    /// Unix-domain socket for `control::socket`'s command interface, under
+    /// `$XDG_RUNTIME_DIR` (per-session, mode 0700, cleaned up by the OS on
+    /// logout). `None` when `$XDG_RUNTIME_DIR` isn't set — a machine
+    /// without a logind session (minimal WM, container, SSH-launched
+    /// instance) has no per-user private directory to put it in, and the
+    /// only fallback, the system temp dir, is world-writable: any other
+    /// local user could connect to the socket and drive the pipeline.
+    /// `control::socket` fails closed rather than falling back to it.
+    pub fn ipc_socket_path() -> Option<PathBuf> {
+        dirs::runtime_dir().map(|dir| dir.join("voice-to-text.sock"))
+    }
+
+    /// Exported transcripts written by `history::export`.
+    /// Portable mode: `<exe_dir>/data/exports/`.
+    pub fn exports_dir() -> PathBuf {
+        if Self::is_portable() {
+            return Self::portable_root().join("data").join("exports");
+        }
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("voice-to-text")
+            .join("exports")
+    }
+
+    /// Rotating per-session log files written by `logging::init`.
+    /// Portable mode: `<exe_dir>/data/logs/`.
+    pub fn logs_dir() -> PathBuf {
+        if Self::is_portable() {
+            return Self::portable_root().join("data").join("logs");
+        }
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("voice-to-text")
+            .join("logs")
+    }
+
+    /// Optional archive of raw captured utterances, for debugging
+    /// misrecognitions. Only populated when `AppSettings.save_recordings`
+    /// is enabled. Portable mode: `<exe_dir>/data/recordings/`.
+    pub fn recordings_dir() -> PathBuf {
+        if Self::is_portable() {
+            return Self::portable_root().join("data").join("recordings");
+        }
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("voice-to-text")
+            .join("recordings")
+    }
+
+    /// Ensure a directory exists, creating parents as needed.
+    pub fn ensure_dir(dir: &PathBuf) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)
+    }
+}
@@ -0,0 +1,44 @@
+//! Named, switchable whole-settings profiles ("Work-Medical",
+//! "Casual-Fast"), stored as separate TOML files under
+//! `AppPaths::profiles_dir()`. Distinct from `AppProfile` (per-app context
+//! matching picked by focus detection, see `llm::profiles`) — a config
+//! profile is a full swap of the active `AppSettings`, picked by the user.
+
+use super::{AppPaths, AppSettings};
+
+/// Names of every saved profile (file stem of each `.toml` under
+/// `profiles/`), sorted alphabetically.
+pub fn list() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(AppPaths::profiles_dir()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().and_then(|s| s.to_str()) == Some("toml"))
+        .filter_map(|e| {
+            e.path()
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+/// Load a saved profile's settings by name, or `None` if it doesn't exist
+/// or fails to parse.
+pub fn load(name: &str) -> Option<AppSettings> {
+    let content = std::fs::read_to_string(AppPaths::profile_path(name)).ok()?;
+    toml::from_str(&content).ok()
+}
+
+/// Save `settings` as a named profile, creating `profiles/` if needed.
+pub fn save(name: &str, settings: &AppSettings) -> anyhow::Result<()> {
+    let path = AppPaths::profile_path(name);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string_pretty(settings)?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! OS-keychain-backed storage for LLM provider API keys, so
+//! `settings.toml` never holds a plaintext secret at rest. A migrated key's
+//! TOML field holds a `keyring:<key id>` reference instead of the key
+//! itself; the real value lives in the platform keychain (macOS Keychain,
+//! Windows Credential Manager, or Linux Secret Service) under that id.
+
+const SERVICE: &str = "voice-to-text";
+const REFERENCE_PREFIX: &str = "keyring:";
+
+/// Key id for the primary provider's API key.
+pub const PRIMARY: &str = "llm-primary";
+
+/// Key id for a fallback provider's API key, indexed by its position in
+/// `AppSettings::llm_fallback_providers`.
+pub fn fallback(index: usize) -> String {
+    format!("llm-fallback-{index}")
+}
+
+/// Key id for `AppSettings::stt_remote_api_key`.
+pub const STT_REMOTE: &str = "stt-remote";
+
+fn entry(key_id: &str) -> anyhow::Result<keyring::Entry> {
+    Ok(keyring::Entry::new(SERVICE, key_id)?)
+}
+
+fn is_reference(value: &str) -> bool {
+    value.starts_with(REFERENCE_PREFIX)
+}
+
+fn reference(key_id: &str) -> String {
+    format!("{REFERENCE_PREFIX}{key_id}")
+}
+
+/// Stores `secret` in the OS keychain under `key_id` and returns the
+/// reference string to persist in its place in `settings.toml`.
+pub fn store(key_id: &str, secret: &str) -> anyhow::Result<String> {
+    entry(key_id)?.set_password(secret)?;
+    Ok(reference(key_id))
+}
+
+/// Resolves a settings field to the real secret: dereferences a migrated
+/// `keyring:` value via the OS keychain, or passes through an
+/// as-yet-unmigrated plaintext value unchanged. Returns `None` if the
+/// field is unset or the keychain lookup fails (key deleted outside the
+/// app, keychain locked, etc.).
+pub fn resolve(field: &Option<String>, key_id: &str) -> Option<String> {
+    match field {
+        Some(value) if is_reference(value) => entry(key_id).ok()?.get_password().ok(),
+        Some(value) => Some(value.clone()),
+        None => None,
+    }
+}
+
+/// Moves `field` into the OS keychain under `key_id` if it holds a
+/// plaintext secret, replacing it with a `keyring:` reference. No-op if
+/// already migrated or unset. Returns whether a migration happened, so the
+/// caller knows to persist the settings afterward.
+pub fn migrate_field(field: &mut Option<String>, key_id: &str) -> bool {
+    let Some(plaintext) = field.as_ref().filter(|v| !is_reference(v)) else {
+        return false;
+    };
+    match store(key_id, plaintext) {
+        Ok(reference) => {
+            *field = Some(reference);
+            true
+        }
+        Err(e) => {
+            log::warn!("failed to migrate API key '{key_id}' into OS keychain: {e}");
+            false
+        }
+    }
+}
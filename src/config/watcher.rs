@@ -0,0 +1,40 @@
+//! Detects external edits to `settings.toml` so power users can tweak
+//! settings in a text editor while the app is running.
+//!
+//! Polls the file's mtime on its own thread rather than pulling in a
+//! filesystem-events crate — `settings.toml` is edited by hand at most a
+//! few times per session, so a couple of seconds of latency doesn't matter
+//! and a stat() loop needs nothing beyond the standard library.
+
+use std::time::{Duration, SystemTime};
+
+use tokio::sync::mpsc;
+
+use super::AppPaths;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+fn last_modified() -> Option<SystemTime> {
+    std::fs::metadata(AppPaths::settings_path())
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+/// Spawns a background thread that notifies `tx` every time
+/// `settings.toml`'s mtime changes. The receiver is responsible for
+/// re-reading and revalidating the file — this only signals that it did.
+pub fn spawn_settings_watcher(tx: mpsc::Sender<()>) {
+    std::thread::spawn(move || {
+        let mut last_seen = last_modified();
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = last_modified();
+            if current != last_seen {
+                last_seen = current;
+                if tx.blocking_send(()).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+}
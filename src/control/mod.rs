@@ -0,0 +1,220 @@
+//! Optional localhost HTTP control API — lets external tools (Stream Deck
+//! plugins, scripts, launcher apps) drive recording and read status/history
+//! without going through the hotkey. Off by default; see
+//! `AppSettings.control_api_enabled`/`control_api_port`.
+//!
+//! Binding to `127.0.0.1` doesn't make this trusted: any web page open in
+//! the user's browser can also reach it, and `/history` returns full
+//! dictation history. Every request carrying a browser `Origin` header is
+//! rejected, and if `AppSettings.control_api_token` is set, requests must
+//! also carry a matching `X-Control-Token` header. See `reject`.
+
+#[cfg(target_os = "linux")]
+pub mod socket;
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+use tokio::sync::mpsc;
+
+use crate::audio::SharedAudioBuffer;
+use crate::history::HistoryStore;
+use crate::pipeline::{PipelineCommand, SharedSettings};
+
+/// Starts the control server on a background OS thread, bound to
+/// `127.0.0.1:<port>`. `/record/start` and `/record/stop` are translated
+/// into `PipelineCommand`s and forwarded over `command_tx`, fire-and-forget
+/// — same as the hotkey bridge in `main.rs`. `/status` and `/history` read
+/// directly from shared state instead, since they need an answer back on
+/// the same connection.
+pub fn spawn_control_server(
+    port: u16,
+    settings: SharedSettings,
+    audio_buffer: SharedAudioBuffer,
+    command_tx: mpsc::Sender<PipelineCommand>,
+) {
+    std::thread::spawn(move || {
+        let listener = match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!("Failed to bind control API to port {}: {}", port, e);
+                return;
+            }
+        };
+        log::info!("Control API listening on http://127.0.0.1:{}", port);
+
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let settings = settings.clone();
+            let audio_buffer = audio_buffer.clone();
+            let command_tx = command_tx.clone();
+            std::thread::spawn(move || {
+                handle_connection(stream, &settings, &audio_buffer, &command_tx)
+            });
+        }
+    });
+}
+
+/// Reads one HTTP/1.1 request off `stream`, dispatches it, and writes back
+/// a response. Requests are read-once/respond-once — no keep-alive, since
+/// this is a low-traffic local control channel, not a public server.
+fn handle_connection(
+    mut stream: TcpStream,
+    settings: &SharedSettings,
+    audio_buffer: &SharedAudioBuffer,
+    command_tx: &mpsc::Sender<PipelineCommand>,
+) {
+    let Ok(cloned) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(cloned);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).unwrap_or(0) == 0 {
+        return;
+    }
+
+    let mut origin = None;
+    let mut token = None;
+    loop {
+        let mut line = String::new();
+        match reader.read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) if line == "\r\n" || line == "\n" => break,
+            Ok(_) => {
+                if let Some((name, value)) = line.split_once(':') {
+                    match name.trim().to_ascii_lowercase().as_str() {
+                        "origin" => origin = Some(value.trim().to_string()),
+                        "x-control-token" => token = Some(value.trim().to_string()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("");
+
+    if let Some(status) = reject(settings, origin.as_deref(), token.as_deref()) {
+        let body = status_text(status).to_string();
+        let response = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            status_text(status),
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes());
+        return;
+    }
+
+    let (status, content_type, body) = match (method, path) {
+        ("POST", "/record/start") => {
+            let _ = command_tx.blocking_send(PipelineCommand::StartRecording);
+            (200, "text/plain", "ok".to_string())
+        }
+        ("POST", "/record/stop") => {
+            let _ = command_tx.blocking_send(PipelineCommand::StopRecording);
+            (200, "text/plain", "ok".to_string())
+        }
+        ("GET", "/status") => {
+            let is_recording = audio_buffer.lock().unwrap().is_recording;
+            let mode = settings.read().operating_mode;
+            let body = serde_json::json!({
+                "recording": is_recording,
+                "mode": mode,
+            })
+            .to_string();
+            (200, "application/json", body)
+        }
+        ("GET", "/history") => {
+            let entries = HistoryStore::default().load_all();
+            let body = serde_json::to_string(&entries).unwrap_or_else(|_| "[]".to_string());
+            (200, "application/json", body)
+        }
+        _ => (404, "text/plain", "not found".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        status_text(status),
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+}
+
+/// `Some(status)` if the request must be refused before it's dispatched,
+/// `None` if it's allowed through. Any `Origin` header is rejected outright
+/// — none of our legitimate clients (curl, Stream Deck, shell scripts) are
+/// web pages, so its presence means the request came from a browser, and
+/// `/record/start`/`/record/stop` are CORS-"simple" requests a malicious
+/// page can fire at `127.0.0.1` with no preflight. When
+/// `control_api_token` is configured, the request must also carry a
+/// matching `X-Control-Token` header.
+fn reject(settings: &SharedSettings, origin: Option<&str>, token: Option<&str>) -> Option<u16> {
+    if origin.is_some() {
+        return Some(403);
+    }
+    let expected = settings.read().control_api_token.clone();
+    if let Some(expected) = expected.filter(|t| !t.is_empty()) {
+        if token != Some(expected.as_str()) {
+            return Some(401);
+        }
+    }
+    None
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        _ => "Internal Server Error",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppSettings;
+    use std::sync::Arc;
+
+    fn settings_with_token(token: Option<&str>) -> SharedSettings {
+        let mut settings = AppSettings::default();
+        settings.control_api_token = token.map(|t| t.to_string());
+        Arc::new(parking_lot::RwLock::new(settings))
+    }
+
+    #[test]
+    fn any_origin_header_is_rejected_even_with_no_token_configured() {
+        let settings = settings_with_token(None);
+        assert_eq!(
+            reject(&settings, Some("https://evil.example"), None),
+            Some(403)
+        );
+    }
+
+    #[test]
+    fn no_token_configured_allows_requests_without_a_token() {
+        let settings = settings_with_token(None);
+        assert_eq!(reject(&settings, None, None), None);
+    }
+
+    #[test]
+    fn missing_or_wrong_token_is_rejected_when_one_is_configured() {
+        let settings = settings_with_token(Some("secret"));
+        assert_eq!(reject(&settings, None, None), Some(401));
+        assert_eq!(reject(&settings, None, Some("wrong")), Some(401));
+    }
+
+    #[test]
+    fn matching_token_is_allowed() {
+        let settings = settings_with_token(Some("secret"));
+        assert_eq!(reject(&settings, None, Some("secret")), None);
+    }
+}
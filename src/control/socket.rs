@@ -0,0 +1,113 @@
+//! Unix-domain-socket command interface mirroring the pipeline's
+//! start/stop/pause/resume/toggle/cancel commands, so window-manager
+//! keybindings and shell scripts can drive recording — notably on Wayland,
+//! where global hotkeys via `rdev` are unreliable. See
+//! `AppSettings.ipc_socket_enabled`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+
+use tokio::sync::mpsc;
+
+use crate::audio::SharedAudioBuffer;
+use crate::pipeline::PipelineCommand;
+
+/// Starts the socket listener on a background OS thread, bound to
+/// `socket_path` (`None` means `AppPaths::ipc_socket_path` had nowhere
+/// safe to put it — logged and skipped rather than falling back to a
+/// world-writable location). A stale socket file left behind by a
+/// previous unclean shutdown is removed before binding, and the socket is
+/// chmod'd to `0600` right after: any other local user connecting to it
+/// could otherwise fire `start`/`stop`/`cancel` at the pipeline.
+pub fn spawn_socket_listener(
+    socket_path: Option<PathBuf>,
+    audio_buffer: SharedAudioBuffer,
+    command_tx: mpsc::Sender<PipelineCommand>,
+) {
+    let Some(socket_path) = socket_path else {
+        log::error!(
+            "IPC socket enabled but $XDG_RUNTIME_DIR isn't set — refusing to fall back to a \
+             world-writable directory, not starting the listener"
+        );
+        return;
+    };
+
+    std::thread::spawn(move || {
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = match UnixListener::bind(&socket_path) {
+            Ok(listener) => listener,
+            Err(e) => {
+                log::error!(
+                    "Failed to bind IPC socket at {}: {}",
+                    socket_path.display(),
+                    e
+                );
+                return;
+            }
+        };
+        if let Err(e) =
+            std::fs::set_permissions(&socket_path, std::fs::Permissions::from_mode(0o600))
+        {
+            log::error!(
+                "Failed to restrict permissions on IPC socket at {}: {} — not starting the listener",
+                socket_path.display(),
+                e
+            );
+            return;
+        }
+        log::info!("IPC socket listening at {}", socket_path.display());
+
+        for stream in listener.incoming() {
+            let Ok(stream) = stream else { continue };
+            let audio_buffer = audio_buffer.clone();
+            let command_tx = command_tx.clone();
+            std::thread::spawn(move || handle_connection(stream, &audio_buffer, &command_tx));
+        }
+    });
+}
+
+/// Reads one newline-terminated command off `stream`, forwards it as a
+/// `PipelineCommand`, and writes back `ok` or an `error: ...` line. One
+/// command per connection, matching the simplicity of the callers (a
+/// keybinding running `socat - UNIX-CONNECT:...` or similar).
+fn handle_connection(
+    stream: UnixStream,
+    audio_buffer: &SharedAudioBuffer,
+    command_tx: &mpsc::Sender<PipelineCommand>,
+) {
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+        return;
+    }
+
+    let command = match line.trim() {
+        "start" => Some(PipelineCommand::StartRecording),
+        "stop" => Some(PipelineCommand::StopRecording),
+        "pause" => Some(PipelineCommand::PauseRecording),
+        "resume" => Some(PipelineCommand::ResumeRecording),
+        "cancel" => Some(PipelineCommand::Cancel),
+        "toggle" => {
+            let is_recording = audio_buffer.lock().unwrap().is_recording;
+            Some(if is_recording {
+                PipelineCommand::StopRecording
+            } else {
+                PipelineCommand::StartRecording
+            })
+        }
+        other => {
+            let _ = writeln!(writer, "error: unknown command \"{}\"", other);
+            None
+        }
+    };
+
+    if let Some(command) = command {
+        let _ = command_tx.blocking_send(command);
+        let _ = writeln!(writer, "ok");
+    }
+}
@@ -0,0 +1,147 @@
+//! Writes selected `HistoryEntry` records out as a single file, for
+//! sharing a transcript or (via `Srt`) turning a recorded clip into
+//! subtitles.
+
+use std::path::PathBuf;
+
+use crate::config::AppPaths;
+
+use super::HistoryEntry;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Txt,
+    Markdown,
+    Srt,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Txt => "txt",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Srt => "srt",
+        }
+    }
+}
+
+/// Renders `entries` (oldest first) in the given format.
+pub fn render(entries: &[HistoryEntry], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Txt => render_txt(entries),
+        ExportFormat::Markdown => render_markdown(entries),
+        ExportFormat::Srt => render_srt(entries),
+    }
+}
+
+fn render_txt(entries: &[HistoryEntry]) -> String {
+    entries
+        .iter()
+        .map(|e| match dialogue_lines(e) {
+            Some(lines) => lines.join("\n"),
+            None => e.best_text().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn render_markdown(entries: &[HistoryEntry]) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        match dialogue_lines(entry) {
+            Some(lines) => {
+                for line in lines {
+                    out.push_str("- ");
+                    out.push_str(&line);
+                    out.push('\n');
+                }
+            }
+            None => {
+                out.push_str("- ");
+                out.push_str(entry.best_text());
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Groups `entry`'s diarized segments into consecutive same-speaker runs
+/// and formats each as `"Speaker A: ..."`, for entries recorded with
+/// `AppSettings::stt_diarization_enabled` on. Returns `None` when the
+/// entry has no speaker labels, so callers fall back to `best_text()`.
+fn dialogue_lines(entry: &HistoryEntry) -> Option<Vec<String>> {
+    if entry.segments.iter().all(|s| s.speaker.is_none()) {
+        return None;
+    }
+
+    let mut lines: Vec<(String, String)> = Vec::new();
+    for segment in &entry.segments {
+        let speaker = segment.speaker.as_deref().unwrap_or("Speaker A");
+        match lines.last_mut() {
+            Some((last_speaker, text)) if last_speaker == speaker => {
+                text.push(' ');
+                text.push_str(segment.text.trim());
+            }
+            _ => lines.push((speaker.to_string(), segment.text.trim().to_string())),
+        }
+    }
+    Some(
+        lines
+            .into_iter()
+            .map(|(speaker, text)| format!("{speaker}: {text}"))
+            .collect(),
+    )
+}
+
+/// Concatenates each entry's Whisper segments into one subtitle timeline,
+/// offsetting each entry's segments by the running total of the previous
+/// entries' durations — correct when the selected entries were recorded
+/// back-to-back, approximate otherwise (there's no shared wall-clock
+/// timeline across separate recordings to fall back on).
+fn render_srt(entries: &[HistoryEntry]) -> String {
+    let mut out = String::new();
+    let mut offset_ms: u64 = 0;
+    let mut index = 1;
+
+    for entry in entries {
+        for segment in &entry.segments {
+            out.push_str(&format!("{index}\n"));
+            out.push_str(&format!(
+                "{} --> {}\n",
+                format_srt_timestamp(offset_ms + segment.start_ms),
+                format_srt_timestamp(offset_ms + segment.end_ms)
+            ));
+            out.push_str(segment.text.trim());
+            out.push_str("\n\n");
+            index += 1;
+        }
+        offset_ms += entry.duration_ms;
+    }
+
+    out
+}
+
+fn format_srt_timestamp(ms: u64) -> String {
+    let hours = ms / 3_600_000;
+    let minutes = (ms % 3_600_000) / 60_000;
+    let seconds = (ms % 60_000) / 1000;
+    let millis = ms % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02},{millis:03}")
+}
+
+/// Renders `entries` and writes them under `AppPaths::exports_dir()`,
+/// returning the file's path.
+pub fn write_export(entries: &[HistoryEntry], format: ExportFormat) -> std::io::Result<PathBuf> {
+    let dir = AppPaths::exports_dir();
+    std::fs::create_dir_all(&dir)?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let path = dir.join(format!("transcript-{timestamp}.{}", format.extension()));
+
+    std::fs::write(&path, render(entries, format))?;
+    Ok(path)
+}
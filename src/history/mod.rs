@@ -0,0 +1,96 @@
+//! Transcription history: append-only JSONL log for recall in the settings UI.
+
+pub mod export;
+pub mod stats;
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AppPaths, OperatingMode};
+use crate::stt::Segment;
+
+/// One past transcription, as recorded right after the pipeline finishes with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub raw_text: String,
+    pub corrected_text: Option<String>,
+    pub timestamp_secs: u64,
+    pub duration_ms: u64,
+    pub mode: OperatingMode,
+    /// Domain detected for this utterance by `DomainDetector`, if any. Old
+    /// entries logged before this field existed deserialize it as `None`.
+    #[serde(default)]
+    pub domain: Option<String>,
+    /// Whisper's segment timestamps for this utterance, used to render SRT
+    /// subtitles on export. Old entries logged before this field existed
+    /// deserialize it as empty.
+    #[serde(default)]
+    pub segments: Vec<Segment>,
+    /// Path to the saved WAV recording of this utterance, if
+    /// `AppSettings::save_recordings` was on when it was captured. Powers
+    /// the history panel's karaoke playback review — see `audio::player`.
+    /// Old entries and any entry recorded with saving off deserialize this
+    /// as `None`.
+    #[serde(default)]
+    pub recording_path: Option<PathBuf>,
+}
+
+impl HistoryEntry {
+    /// The text a user would want copied or re-injected — the corrected
+    /// version when one exists, otherwise the raw STT output.
+    pub fn best_text(&self) -> &str {
+        self.corrected_text.as_deref().unwrap_or(&self.raw_text)
+    }
+}
+
+/// Reads/appends the JSONL history log at `AppPaths::history_path()`.
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl Default for HistoryStore {
+    fn default() -> Self {
+        Self {
+            path: AppPaths::history_path(),
+        }
+    }
+}
+
+impl HistoryStore {
+    /// Append one entry, creating the file (and its parent directory) if needed.
+    pub fn append(&self, entry: &HistoryEntry) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let line = serde_json::to_string(entry)?;
+        writeln!(file, "{}", line)
+    }
+
+    /// Load every entry, oldest first. Malformed lines are skipped rather
+    /// than failing the whole read, since a partial write shouldn't hide
+    /// the rest of the log.
+    pub fn load_all(&self) -> Vec<HistoryEntry> {
+        let Ok(content) = std::fs::read_to_string(&self.path) else {
+            return Vec::new();
+        };
+        content
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    /// Load the `n` most recent entries, newest first.
+    pub fn recent(&self, n: usize) -> Vec<HistoryEntry> {
+        let mut entries = self.load_all();
+        entries.reverse();
+        entries.truncate(n);
+        entries
+    }
+}
@@ -0,0 +1,74 @@
+//! Dictation statistics for the settings panel's stats view. Computed on
+//! demand from `HistoryStore::load_all()` — the history log is already the
+//! append-only source of truth, so this is a read-only aggregation rather
+//! than a separately maintained store that could drift out of sync with it.
+
+use std::collections::HashMap;
+
+use super::HistoryEntry;
+
+const SECS_PER_DAY: u64 = 86_400;
+const SECS_PER_WEEK: u64 = 7 * SECS_PER_DAY;
+
+/// Aggregate stats over a set of history entries, for a given "now".
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DictationStats {
+    pub words_today: usize,
+    pub words_this_week: usize,
+    /// Mean STT `duration_ms` across all entries, or `None` if there are none.
+    pub avg_latency_ms: Option<f64>,
+    /// Fraction of entries where the LLM changed the raw transcription
+    /// (`corrected_text` present and different from `raw_text`), 0.0-1.0.
+    pub correction_rate: f64,
+    /// Detected domains ordered by utterance count, most-used first.
+    pub top_domains: Vec<(String, usize)>,
+}
+
+/// Computes `DictationStats` from `entries` as of `now_secs` (Unix time).
+pub fn compute(entries: &[HistoryEntry], now_secs: u64) -> DictationStats {
+    if entries.is_empty() {
+        return DictationStats::default();
+    }
+
+    let today_start = now_secs.saturating_sub(SECS_PER_DAY);
+    let week_start = now_secs.saturating_sub(SECS_PER_WEEK);
+
+    let mut words_today = 0;
+    let mut words_this_week = 0;
+    let mut total_latency_ms: u64 = 0;
+    let mut corrected_count = 0;
+    let mut domain_counts: HashMap<String, usize> = HashMap::new();
+
+    for entry in entries {
+        let word_count = entry.best_text().split_whitespace().count();
+        if entry.timestamp_secs >= today_start {
+            words_today += word_count;
+        }
+        if entry.timestamp_secs >= week_start {
+            words_this_week += word_count;
+        }
+
+        total_latency_ms += entry.duration_ms;
+
+        if let Some(corrected) = &entry.corrected_text {
+            if corrected != &entry.raw_text {
+                corrected_count += 1;
+            }
+        }
+
+        if let Some(domain) = &entry.domain {
+            *domain_counts.entry(domain.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let mut top_domains: Vec<(String, usize)> = domain_counts.into_iter().collect();
+    top_domains.sort_by(|a, b| b.1.cmp(&a.1));
+
+    DictationStats {
+        words_today,
+        words_this_week,
+        avg_latency_ms: Some(total_latency_ms as f64 / entries.len() as f64),
+        correction_rate: corrected_count as f64 / entries.len() as f64,
+        top_domains,
+    }
+}
@@ -0,0 +1,384 @@
+//! Global hotkey listener (rdev), running on its own thread because
+//! `rdev::listen()` blocks forever and cannot run as a tokio task.
+//!
+//! Tracks currently-held modifier keys itself — rdev only reports raw
+//! press/release events, so combinations like `Ctrl+Shift+T` require the
+//! listener to remember which modifiers are down when the base key fires.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    Ctrl,
+    Alt,
+    Shift,
+    Meta,
+}
+
+/// The physical input that fires a hotkey combo's base action — a keyboard
+/// key, or a mouse/foot-pedal button (most foot pedals and extra mouse
+/// buttons enumerate to the OS as `Mouse4`/`Mouse5`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trigger {
+    Key(rdev::Key),
+    MouseButton(rdev::Button),
+}
+
+/// A hotkey as configured by the user: zero or more modifiers plus one
+/// trigger (a base key or a mouse button).
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyCombo {
+    pub modifiers: HashSet<Modifier>,
+    pub trigger: Trigger,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HotkeyEvent {
+    PushToTalkPressed,
+    PushToTalkReleased,
+    ToggleVisibility,
+    /// Fired on press of the pause/resume key, so a mid-dictation pause
+    /// doesn't require holding push-to-talk down.
+    PauseResumeToggle,
+    /// Fired on press of the translate-toggle key, flipping
+    /// `AppSettings.translate_to_english` without opening settings.
+    TranslateToggle,
+    /// The push-to-talk trigger was pressed twice within
+    /// `DOUBLE_TAP_WINDOW` — lets users flip between Fast and Standard mode
+    /// without opening settings. Fired instead of, not alongside, the second
+    /// `PushToTalkPressed`.
+    DoubleTap,
+    /// One of `AppSettings::hotkey_presets` fired instead of the primary
+    /// `push_to_talk_key` — carries the preset's `id` so the orchestrator
+    /// knows which settings overrides to apply before recording starts. No
+    /// double-tap detection on preset keys; each preset is already an
+    /// explicit choice of mode.
+    PresetPushToTalkPressed(String),
+    PresetPushToTalkReleased(String),
+}
+
+/// Two push-to-talk presses within this window count as a double-tap.
+const DOUBLE_TAP_WINDOW: std::time::Duration = std::time::Duration::from_millis(400);
+
+/// How long the self-test waits for a first global input event before
+/// concluding the listener isn't actually receiving anything — the silent
+/// failure mode on macOS without Accessibility permission, and on some
+/// Wayland compositors.
+const SELF_TEST_WINDOW: Duration = Duration::from_secs(5);
+
+/// Health of the global hotkey listener, surfaced to the settings panel so
+/// users on macOS/Wayland can tell a silently-dead listener apart from one
+/// that's just waiting for a keypress.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HotkeyStatus {
+    /// Listener thread started; waiting out `SELF_TEST_WINDOW` for a first
+    /// event before deciding whether it's actually working.
+    Starting,
+    /// At least one global input event has been observed, so the listener is
+    /// definitely receiving from the OS.
+    Listening,
+    /// No global input event arrived within `SELF_TEST_WINDOW` — most likely
+    /// missing Accessibility permission (macOS) or an unsupported Wayland
+    /// session, since `rdev::listen` returns `Ok` and blocks forever even
+    /// when the OS is silently dropping every event.
+    NoEventsDetected,
+    /// `rdev::listen` returned an error outright.
+    Error(String),
+}
+
+pub type SharedHotkeyStatus = Arc<Mutex<HotkeyStatus>>;
+
+pub fn new_status() -> SharedHotkeyStatus {
+    Arc::new(Mutex::new(HotkeyStatus::Starting))
+}
+
+/// Maps a physical modifier key to the logical `Modifier` it represents, or
+/// `None` if it isn't a modifier at all.
+fn key_to_modifier(key: rdev::Key) -> Option<Modifier> {
+    use rdev::Key::*;
+    match key {
+        ControlLeft | ControlRight => Some(Modifier::Ctrl),
+        ShiftLeft | ShiftRight => Some(Modifier::Shift),
+        Alt | AltGr => Some(Modifier::Alt),
+        MetaLeft | MetaRight => Some(Modifier::Meta),
+        _ => None,
+    }
+}
+
+pub fn spawn_hotkey_listener(
+    tx: mpsc::Sender<HotkeyEvent>,
+    push_to_talk: KeyCombo,
+    toggle_visibility: Option<KeyCombo>,
+    pause_resume: Option<KeyCombo>,
+    translate_toggle: Option<KeyCombo>,
+    presets: Vec<(String, KeyCombo)>,
+    status: SharedHotkeyStatus,
+) {
+    {
+        let status = status.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(SELF_TEST_WINDOW);
+            let mut status = status.lock().unwrap();
+            if *status == HotkeyStatus::Starting {
+                *status = HotkeyStatus::NoEventsDetected;
+            }
+        });
+    }
+
+    std::thread::spawn(move || {
+        let mut held_modifiers: HashSet<Modifier> = HashSet::new();
+        let mut last_push_to_talk_press: Option<std::time::Instant> = None;
+
+        let mut send_push_to_talk_pressed = |tx: &mpsc::Sender<HotkeyEvent>| {
+            let now = std::time::Instant::now();
+            let is_double_tap = last_push_to_talk_press
+                .is_some_and(|last| now.duration_since(last) <= DOUBLE_TAP_WINDOW);
+            last_push_to_talk_press = Some(now);
+
+            if is_double_tap {
+                last_push_to_talk_press = None;
+                let _ = tx.blocking_send(HotkeyEvent::DoubleTap);
+            } else {
+                let _ = tx.blocking_send(HotkeyEvent::PushToTalkPressed);
+            }
+        };
+
+        let result = rdev::listen(move |event| {
+            // Any event at all proves the OS is actually delivering input to
+            // the listener — that's the thing missing Accessibility
+            // permission (or an unsupported Wayland session) silently breaks.
+            {
+                let mut status = status.lock().unwrap();
+                if *status == HotkeyStatus::Starting {
+                    *status = HotkeyStatus::Listening;
+                }
+            }
+
+            match event.event_type {
+                rdev::EventType::KeyPress(key) => {
+                    if let Some(modifier) = key_to_modifier(key) {
+                        held_modifiers.insert(modifier);
+                        return;
+                    }
+
+                    if push_to_talk.trigger == Trigger::Key(key)
+                        && held_modifiers == push_to_talk.modifiers
+                    {
+                        send_push_to_talk_pressed(&tx);
+                    }
+                    if let Some(toggle) = &toggle_visibility {
+                        if toggle.trigger == Trigger::Key(key) && held_modifiers == toggle.modifiers
+                        {
+                            let _ = tx.blocking_send(HotkeyEvent::ToggleVisibility);
+                        }
+                    }
+                    if let Some(pause_resume) = &pause_resume {
+                        if pause_resume.trigger == Trigger::Key(key)
+                            && held_modifiers == pause_resume.modifiers
+                        {
+                            let _ = tx.blocking_send(HotkeyEvent::PauseResumeToggle);
+                        }
+                    }
+                    if let Some(translate_toggle) = &translate_toggle {
+                        if translate_toggle.trigger == Trigger::Key(key)
+                            && held_modifiers == translate_toggle.modifiers
+                        {
+                            let _ = tx.blocking_send(HotkeyEvent::TranslateToggle);
+                        }
+                    }
+                    for (id, combo) in &presets {
+                        if combo.trigger == Trigger::Key(key) && held_modifiers == combo.modifiers {
+                            let _ =
+                                tx.blocking_send(HotkeyEvent::PresetPushToTalkPressed(id.clone()));
+                        }
+                    }
+                }
+
+                rdev::EventType::KeyRelease(key) => {
+                    if let Some(modifier) = key_to_modifier(key) {
+                        held_modifiers.remove(&modifier);
+                        return;
+                    }
+
+                    if push_to_talk.trigger == Trigger::Key(key) {
+                        let _ = tx.blocking_send(HotkeyEvent::PushToTalkReleased);
+                    }
+                    for (id, combo) in &presets {
+                        if combo.trigger == Trigger::Key(key) {
+                            let _ =
+                                tx.blocking_send(HotkeyEvent::PresetPushToTalkReleased(id.clone()));
+                        }
+                    }
+                }
+
+                // Mouse buttons and foot pedals (which enumerate as extra mouse
+                // buttons) drive push-to-talk the same way a key does, but never
+                // toggle visibility or pause/resume — those stay keyboard-only
+                // to avoid misfiring on ordinary clicks.
+                rdev::EventType::ButtonPress(button) => {
+                    if push_to_talk.trigger == Trigger::MouseButton(button)
+                        && held_modifiers == push_to_talk.modifiers
+                    {
+                        send_push_to_talk_pressed(&tx);
+                    }
+                    for (id, combo) in &presets {
+                        if combo.trigger == Trigger::MouseButton(button)
+                            && held_modifiers == combo.modifiers
+                        {
+                            let _ =
+                                tx.blocking_send(HotkeyEvent::PresetPushToTalkPressed(id.clone()));
+                        }
+                    }
+                }
+
+                rdev::EventType::ButtonRelease(button) => {
+                    if push_to_talk.trigger == Trigger::MouseButton(button) {
+                        let _ = tx.blocking_send(HotkeyEvent::PushToTalkReleased);
+                    }
+                    for (id, combo) in &presets {
+                        if combo.trigger == Trigger::MouseButton(button) {
+                            let _ =
+                                tx.blocking_send(HotkeyEvent::PresetPushToTalkReleased(id.clone()));
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+        });
+
+        if let Err(e) = result {
+            log::error!("Hotkey listener failed: {:?}", e);
+            *status.lock().unwrap() = HotkeyStatus::Error(format!("{:?}", e));
+        }
+    });
+}
+
+/// Spawns a fresh listener thread, e.g. after the user grants Accessibility
+/// permission (macOS) following a failed self-test and clicks "Restart" in
+/// the settings panel. `rdev` has no way to stop a running listener thread,
+/// so the old one (if it was actually silently failing) just keeps blocking
+/// forever without ever delivering an event — harmless to leave running.
+/// `status` is reset to `Starting` so the settings panel re-runs the
+/// self-test against the new thread instead of showing the stale result.
+pub fn restart_hotkey_listener(
+    tx: mpsc::Sender<HotkeyEvent>,
+    push_to_talk: KeyCombo,
+    toggle_visibility: Option<KeyCombo>,
+    pause_resume: Option<KeyCombo>,
+    translate_toggle: Option<KeyCombo>,
+    presets: Vec<(String, KeyCombo)>,
+    status: SharedHotkeyStatus,
+) {
+    *status.lock().unwrap() = HotkeyStatus::Starting;
+    spawn_hotkey_listener(
+        tx,
+        push_to_talk,
+        toggle_visibility,
+        pause_resume,
+        translate_toggle,
+        presets,
+        status,
+    );
+}
+
+/// Parse a hotkey name like "F9", "Ctrl+Shift+T", or "Mouse4" into a
+/// `KeyCombo`. Modifiers are matched case-sensitively against
+/// `Ctrl`/`Alt`/`Shift`/`Meta` (Meta also accepts `Cmd`/`Super`/`Win`); the
+/// last `+`-separated part is the trigger — a keyboard key or, for foot
+/// pedals and extra mouse buttons, `Mouse1`-`Mouse5`.
+pub fn parse_combo(name: &str) -> Option<KeyCombo> {
+    let mut parts: Vec<&str> = name.split('+').map(str::trim).collect();
+    let trigger_name = parts.pop()?;
+    let trigger = parse_trigger(trigger_name)?;
+
+    let mut modifiers = HashSet::new();
+    for part in parts {
+        let modifier = match part {
+            "Ctrl" | "Control" => Modifier::Ctrl,
+            "Alt" => Modifier::Alt,
+            "Shift" => Modifier::Shift,
+            "Meta" | "Cmd" | "Super" | "Win" => Modifier::Meta,
+            _ => return None,
+        };
+        modifiers.insert(modifier);
+    }
+
+    Some(KeyCombo { modifiers, trigger })
+}
+
+/// Parse a single trigger name into either a keyboard key or a mouse button.
+fn parse_trigger(name: &str) -> Option<Trigger> {
+    if let Some(button) = parse_mouse_button(name) {
+        return Some(Trigger::MouseButton(button));
+    }
+    parse_base_key(name).map(Trigger::Key)
+}
+
+/// Parse a mouse button name ("Mouse1".."Mouse5") into an rdev button.
+/// `Mouse1`/`Mouse2`/`Mouse3` map to the standard left/right/middle buttons;
+/// `Mouse4`/`Mouse5` are the side buttons most foot pedals and extra mouse
+/// buttons present as.
+fn parse_mouse_button(name: &str) -> Option<rdev::Button> {
+    use rdev::Button::*;
+    Some(match name {
+        "Mouse1" => Left,
+        "Mouse2" => Right,
+        "Mouse3" => Middle,
+        "Mouse4" => Unknown(4),
+        "Mouse5" => Unknown(5),
+        _ => return None,
+    })
+}
+
+/// Parse a single, non-modifier key name ("F9", "Space", "T", ...) into an rdev key.
+fn parse_base_key(name: &str) -> Option<rdev::Key> {
+    use rdev::Key::*;
+    Some(match name {
+        "F1" => F1,
+        "F2" => F2,
+        "F3" => F3,
+        "F4" => F4,
+        "F5" => F5,
+        "F6" => F6,
+        "F7" => F7,
+        "F8" => F8,
+        "F9" => F9,
+        "F10" => F10,
+        "F11" => F11,
+        "F12" => F12,
+        "Space" => Space,
+        "Tab" => Tab,
+        "A" => KeyA,
+        "B" => KeyB,
+        "C" => KeyC,
+        "D" => KeyD,
+        "E" => KeyE,
+        "F" => KeyF,
+        "G" => KeyG,
+        "H" => KeyH,
+        "I" => KeyI,
+        "J" => KeyJ,
+        "K" => KeyK,
+        "L" => KeyL,
+        "M" => KeyM,
+        "N" => KeyN,
+        "O" => KeyO,
+        "P" => KeyP,
+        "Q" => KeyQ,
+        "R" => KeyR,
+        "S" => KeyS,
+        "T" => KeyT,
+        "U" => KeyU,
+        "V" => KeyV,
+        "W" => KeyW,
+        "X" => KeyX,
+        "Y" => KeyY,
+        "Z" => KeyZ,
+        _ => return None,
+    })
+}
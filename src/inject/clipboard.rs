@@ -0,0 +1,75 @@
+//! Clipboard save/restore around a paste-based injection.
+
+use arboard::Clipboard;
+
+use super::InjectError;
+
+/// Saves the clipboard's original content on creation and restores it on drop.
+pub struct ClipboardGuard {
+    original_text: Option<String>,
+    clipboard: Clipboard,
+}
+
+impl ClipboardGuard {
+    pub fn new() -> Result<Self, InjectError> {
+        let mut clipboard =
+            Clipboard::new().map_err(|e| InjectError::ClipboardAccess(e.to_string()))?;
+        let original_text = clipboard.get_text().ok();
+        Ok(Self {
+            original_text,
+            clipboard,
+        })
+    }
+
+    pub fn set_text(&mut self, text: &str) -> Result<(), InjectError> {
+        self.clipboard
+            .set_text(text)
+            .map_err(|e| InjectError::ClipboardSet(e.to_string()))
+    }
+
+    /// Cancels the on-drop restore, leaving the just-set text on the
+    /// clipboard permanently instead of putting back whatever was there
+    /// before. Used when injection fails partway through — e.g. the target
+    /// window lost focus before paste could run — so the user can still
+    /// paste the result manually.
+    pub fn keep(mut self) {
+        self.original_text = None;
+    }
+}
+
+/// Reads up to `max_lines` trailing lines of the current clipboard text, for
+/// `AppSettings::target_context_enabled` — feeding the document the user is
+/// dictating into as context for `ContextManager::build_context`. There's no
+/// cross-platform way to read another app's live text selection without
+/// OS-specific accessibility permissions, so the clipboard (whatever the
+/// user last copied from that document) is the practical proxy for it.
+/// Returns `None` if the clipboard is unavailable or empty.
+pub fn read_recent_lines(max_lines: usize) -> Option<String> {
+    let mut clipboard = Clipboard::new().ok()?;
+    let text = clipboard.get_text().ok()?;
+    if text.trim().is_empty() {
+        return None;
+    }
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(max_lines);
+    Some(lines[start..].join("\n"))
+}
+
+/// Plain clipboard copy with no restore-on-drop — for actions like "Copy"
+/// in the history panel where overwriting the clipboard is the whole point.
+pub fn copy_to_clipboard(text: &str) -> Result<(), InjectError> {
+    let mut clipboard =
+        Clipboard::new().map_err(|e| InjectError::ClipboardAccess(e.to_string()))?;
+    clipboard
+        .set_text(text)
+        .map_err(|e| InjectError::ClipboardSet(e.to_string()))
+}
+
+impl Drop for ClipboardGuard {
+    fn drop(&mut self) {
+        if let Some(original) = self.original_text.take() {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            let _ = self.clipboard.set_text(original);
+        }
+    }
+}
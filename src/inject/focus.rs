@@ -0,0 +1,52 @@
+//! Best-effort lookup of the foreground window, used to pick a per-app
+//! context profile before injection (see `llm::profiles`) and to detect
+//! focus loss between recording start and paste (see `ensure_focus`).
+
+use active_win_pos_rs::get_active_window;
+
+/// Identifies the application currently holding keyboard focus.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusedWindow {
+    pub title: String,
+    pub process_name: String,
+}
+
+/// Queries the OS for the foreground window. Returns `None` if the
+/// platform API fails or nothing currently has focus, rather than erroring
+/// — callers should fall back to a default profile in that case.
+pub fn active_window() -> Option<FocusedWindow> {
+    let window = get_active_window().ok()?;
+    Some(FocusedWindow {
+        title: window.title,
+        process_name: window.app_name,
+    })
+}
+
+/// How many times to re-check the foreground window before giving up on a
+/// focus loss. Recovers from the brief flicker some window managers cause
+/// (e.g. a closing notification stealing focus for a frame).
+const REFOCUS_RETRIES: u32 = 3;
+/// Delay between refocus checks.
+const REFOCUS_RETRY_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Confirms `target` still holds keyboard focus, retrying briefly if it
+/// doesn't. Returns `true` once `target` is focused again (or was the whole
+/// time), `false` if it never comes back within the retry budget.
+///
+/// This only recovers from transient focus flicker — there is no platform
+/// binding in this crate yet (X11 `XSetInputFocus` / Win32
+/// `SetForegroundWindow` / AppKit `NSRunningApplication::activate`) to
+/// forcibly re-activate a window that lost focus for good, so a real
+/// switch away (e.g. the user clicked another app) is reported as lost
+/// rather than undone.
+pub fn ensure_focus(target: &FocusedWindow) -> bool {
+    for attempt in 0..=REFOCUS_RETRIES {
+        if active_window().as_ref() == Some(target) {
+            return true;
+        }
+        if attempt < REFOCUS_RETRIES {
+            std::thread::sleep(REFOCUS_RETRY_DELAY);
+        }
+    }
+    false
+}
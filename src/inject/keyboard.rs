@@ -0,0 +1,64 @@
+//! Ctrl+V / Cmd+V key simulation via enigo.
+
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+
+use super::{FocusedWindow, InjectError, TextInjector};
+
+/// Types text character-by-character via synthesized Unicode key events,
+/// instead of going through the clipboard. Useful for apps that don't
+/// accept Ctrl+V (terminals, VMs, remote desktops), but — per the caveat
+/// in `inject/mod.rs` — less reliable for Thai combining characters than
+/// `ClipboardInjector`, so it's opt-in via `InjectMethod::Keystroke`.
+pub struct KeystrokeInjector {
+    per_char_delay_ms: u64,
+}
+
+impl KeystrokeInjector {
+    pub fn new(per_char_delay_ms: u64) -> Self {
+        Self { per_char_delay_ms }
+    }
+}
+
+impl TextInjector for KeystrokeInjector {
+    fn inject(
+        &self,
+        text: &str,
+        _expected_focus: Option<&FocusedWindow>,
+    ) -> Result<(), InjectError> {
+        let mut enigo = Enigo::new(&Settings::default())
+            .map_err(|e| InjectError::KeySimulation(e.to_string()))?;
+
+        for ch in text.chars() {
+            enigo
+                .key(Key::Unicode(ch), Direction::Click)
+                .map_err(|e| InjectError::KeySimulation(e.to_string()))?;
+            if self.per_char_delay_ms > 0 {
+                std::thread::sleep(std::time::Duration::from_millis(self.per_char_delay_ms));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn simulate_paste() -> Result<(), InjectError> {
+    let mut enigo =
+        Enigo::new(&Settings::default()).map_err(|e| InjectError::KeySimulation(e.to_string()))?;
+
+    #[cfg(target_os = "macos")]
+    let modifier = Key::Meta;
+    #[cfg(not(target_os = "macos"))]
+    let modifier = Key::Control;
+
+    enigo
+        .key(modifier, Direction::Press)
+        .map_err(|e| InjectError::KeySimulation(e.to_string()))?;
+    enigo
+        .key(Key::Unicode('v'), Direction::Click)
+        .map_err(|e| InjectError::KeySimulation(e.to_string()))?;
+    enigo
+        .key(modifier, Direction::Release)
+        .map_err(|e| InjectError::KeySimulation(e.to_string()))?;
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! Text injection into the active window: clipboard set + paste simulation.
+//!
+//! Clipboard + Ctrl+V is used instead of direct key simulation because Thai
+//! combining characters (สระลอย, วรรณยุกต์) are unreliable to synthesize as
+//! individual key events across keyboard layouts.
+
+pub mod clipboard;
+pub mod focus;
+pub mod keyboard;
+pub mod note;
+#[cfg(target_os = "windows")]
+pub mod sendinput;
+#[cfg(target_os = "linux")]
+pub mod wayland;
+
+use std::sync::Arc;
+
+use clipboard::ClipboardGuard;
+
+pub use clipboard::{copy_to_clipboard, read_recent_lines};
+pub use focus::{active_window, ensure_focus, FocusedWindow};
+pub use keyboard::KeystrokeInjector;
+
+use crate::config::{AppSettings, InjectMethod};
+
+#[derive(Debug)]
+pub enum InjectError {
+    ClipboardAccess(String),
+    ClipboardSet(String),
+    KeySimulation(String),
+    TargetWindowLost,
+}
+
+impl std::fmt::Display for InjectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ClipboardAccess(e) => write!(f, "Cannot access clipboard: {}", e),
+            Self::ClipboardSet(e) => write!(f, "Cannot set clipboard text: {}", e),
+            Self::KeySimulation(e) => write!(f, "Cannot simulate key press: {}", e),
+            Self::TargetWindowLost => write!(f, "Target window lost focus"),
+        }
+    }
+}
+
+impl std::error::Error for InjectError {}
+
+/// Text Injector abstraction — platform-specific.
+pub trait TextInjector: Send + Sync {
+    /// Injects `text` into the currently focused application. `expected_focus`,
+    /// when set, is the window that was focused when recording started —
+    /// implementations that paste via a synthesized keystroke (currently
+    /// `ClipboardInjector`) verify it's still focused right before pasting,
+    /// so a window switch mid-pipeline doesn't paste into the wrong app.
+    /// Injectors that don't need this (`KeystrokeInjector`, `WaylandInjector`)
+    /// ignore it.
+    fn inject(&self, text: &str, expected_focus: Option<&FocusedWindow>)
+        -> Result<(), InjectError>;
+}
+
+pub struct ClipboardInjector {
+    delay_ms: u64,
+}
+
+impl Default for ClipboardInjector {
+    fn default() -> Self {
+        Self { delay_ms: 50 }
+    }
+}
+
+impl TextInjector for ClipboardInjector {
+    fn inject(
+        &self,
+        text: &str,
+        expected_focus: Option<&FocusedWindow>,
+    ) -> Result<(), InjectError> {
+        let mut guard = ClipboardGuard::new()?;
+        guard.set_text(text)?;
+
+        if let Some(target) = expected_focus {
+            if !focus::ensure_focus(target) {
+                // Text stays on the clipboard so the user can paste it
+                // manually once they're back in the right window.
+                guard.keep();
+                return Err(InjectError::TargetWindowLost);
+            }
+        }
+
+        std::thread::sleep(std::time::Duration::from_millis(self.delay_ms));
+        keyboard::simulate_paste()?;
+
+        Ok(())
+    }
+}
+
+/// Build the configured injector — clipboard+paste by default, or direct
+/// keystroke simulation for apps that reject paste. Under Wayland, the
+/// default path is transparently upgraded to `wayland::WaylandInjector`
+/// when a compatible tool is detected, since clipboard+Ctrl+V is unreliable
+/// there.
+pub fn build_injector(settings: &AppSettings) -> Arc<dyn TextInjector> {
+    match settings.inject_method {
+        InjectMethod::Clipboard => {
+            #[cfg(target_os = "linux")]
+            if wayland::is_available() {
+                return Arc::new(wayland::WaylandInjector::default());
+            }
+            Arc::new(ClipboardInjector::default())
+        }
+        InjectMethod::Keystroke => {
+            Arc::new(KeystrokeInjector::new(settings.inject_keystroke_delay_ms))
+        }
+        InjectMethod::UnicodeSendInput => {
+            #[cfg(target_os = "windows")]
+            {
+                Arc::new(sendinput::SendInputInjector::default())
+            }
+            #[cfg(not(target_os = "windows"))]
+            {
+                log::warn!(
+                    "unicode_sendinput is Windows-only; falling back to clipboard injection"
+                );
+                Arc::new(ClipboardInjector::default())
+            }
+        }
+    }
+}
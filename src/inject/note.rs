@@ -0,0 +1,51 @@
+//! Appends finished transcripts to a user-chosen file instead of (or
+//! alongside) injecting them into the focused window — e.g. a running
+//! daily notes Markdown file. See `AppSettings.append_to_note`.
+
+use std::io::Write;
+use std::path::Path;
+
+/// Appends `text` to `path`, preceded by a timestamp header, creating the
+/// file (and its parent directory) if needed.
+pub fn append(path: &Path, text: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let unix_secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "## {}\n{}\n", format_utc_timestamp(unix_secs), text)
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD HH:MM:SS UTC` without pulling in
+/// a date/time crate, using the standard days-since-epoch civil calendar
+/// algorithm (Howard Hinnant's `civil_from_days`).
+fn format_utc_timestamp(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+    let hour = secs_of_day / 3600;
+    let minute = (secs_of_day % 3600) / 60;
+    let second = secs_of_day % 60;
+    format!("{year:04}-{month:02}-{day:02} {hour:02}:{minute:02}:{second:02} UTC")
+}
+
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = if month <= 2 { y + 1 } else { y };
+    (year, month, day)
+}
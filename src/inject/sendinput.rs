@@ -0,0 +1,91 @@
+//! Windows-only injector using `SendInput` with `KEYEVENTF_UNICODE`, which
+//! synthesizes arbitrary Unicode characters directly at the keyboard-input
+//! layer without touching the clipboard at all. Unlike `ClipboardInjector`,
+//! there's no clipboard write for a clipboard-manager utility to race with
+//! and steal focus over.
+//!
+//! Bound directly against `user32.dll` — no `winapi`/`windows-sys`
+//! dependency, since this is the only Win32 call this crate needs.
+
+use super::{FocusedWindow, InjectError, TextInjector};
+
+#[repr(C)]
+struct KeybdInput {
+    w_vk: u16,
+    w_scan: u16,
+    dw_flags: u32,
+    time: u32,
+    dw_extra_info: usize,
+}
+
+#[repr(C)]
+struct Input {
+    r#type: u32,
+    ki: KeybdInput,
+    // `INPUT` is a C union of `MOUSEINPUT`/`KEYBDINPUT`/`HARDWAREINPUT`;
+    // `MOUSEINPUT` is the largest, 8 bytes bigger than `KEYBDINPUT` (32 vs
+    // 24 bytes on x64), so padding out to its size keeps the layout correct
+    // without needing the union types we don't use.
+    _padding: [u8; 8],
+}
+
+const INPUT_KEYBOARD: u32 = 1;
+const KEYEVENTF_UNICODE: u32 = 0x0004;
+const KEYEVENTF_KEYUP: u32 = 0x0002;
+
+#[link(name = "user32")]
+extern "system" {
+    fn SendInput(c_inputs: u32, p_inputs: *const Input, cb_size: i32) -> u32;
+}
+
+fn unicode_input(code_unit: u16, key_up: bool) -> Input {
+    Input {
+        r#type: INPUT_KEYBOARD,
+        ki: KeybdInput {
+            w_vk: 0,
+            w_scan: code_unit,
+            dw_flags: KEYEVENTF_UNICODE | if key_up { KEYEVENTF_KEYUP } else { 0 },
+            time: 0,
+            dw_extra_info: 0,
+        },
+        _padding: [0; 8],
+    }
+}
+
+/// Injects text via `SendInput`+`KEYEVENTF_UNICODE`, one UTF-16 code unit
+/// at a time (surrogate pairs are sent as two consecutive code units, which
+/// Windows recombines on the receiving end — the same trick TSF-based IMEs
+/// use for characters outside the BMP).
+#[derive(Default)]
+pub struct SendInputInjector;
+
+impl TextInjector for SendInputInjector {
+    fn inject(
+        &self,
+        text: &str,
+        _expected_focus: Option<&FocusedWindow>,
+    ) -> Result<(), InjectError> {
+        let mut buf = [0u16; 2];
+        for ch in text.chars() {
+            for &code_unit in ch.encode_utf16(&mut buf).iter() {
+                let events = [
+                    unicode_input(code_unit, false),
+                    unicode_input(code_unit, true),
+                ];
+                let sent = unsafe {
+                    SendInput(
+                        events.len() as u32,
+                        events.as_ptr(),
+                        std::mem::size_of::<Input>() as i32,
+                    )
+                };
+                if sent as usize != events.len() {
+                    return Err(InjectError::KeySimulation(
+                        "SendInput rejected one or more synthesized key events".into(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
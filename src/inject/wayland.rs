@@ -0,0 +1,66 @@
+//! Wayland injection backend, shelling out to `wtype` or `ydotool`.
+//!
+//! Clipboard + enigo's synthesized Ctrl+V doesn't reach most Wayland
+//! compositors — enigo's virtual-keyboard support is spotty and compositors
+//! vary in whether they honor it at all. `wtype`/`ydotool` talk to the
+//! virtual-keyboard protocol directly and are the tools the Wayland
+//! ecosystem already expects for this. `build_injector` auto-detects this
+//! backend and prefers it over `ClipboardInjector` when running under
+//! Wayland, only for the default `InjectMethod::Clipboard` path.
+
+use std::process::{Command, Stdio};
+
+use super::{FocusedWindow, InjectError, TextInjector};
+
+/// True when a Wayland session is running and at least one supported CLI
+/// tool is on `PATH`.
+pub fn is_available() -> bool {
+    std::env::var("WAYLAND_DISPLAY").is_ok()
+        && (command_exists("wtype") || command_exists("ydotool"))
+}
+
+fn command_exists(bin: &str) -> bool {
+    Command::new(bin)
+        .arg("--help")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+/// Types text via `wtype`, falling back to `ydotool type` if `wtype` isn't
+/// installed.
+#[derive(Default)]
+pub struct WaylandInjector;
+
+impl TextInjector for WaylandInjector {
+    fn inject(
+        &self,
+        text: &str,
+        _expected_focus: Option<&FocusedWindow>,
+    ) -> Result<(), InjectError> {
+        if command_exists("wtype") {
+            return run(Command::new("wtype").arg(text));
+        }
+        if command_exists("ydotool") {
+            return run(Command::new("ydotool").arg("type").arg(text));
+        }
+        Err(InjectError::KeySimulation(
+            "no Wayland injection tool (wtype/ydotool) found on PATH".into(),
+        ))
+    }
+}
+
+fn run(command: &mut Command) -> Result<(), InjectError> {
+    let status = command
+        .status()
+        .map_err(|e| InjectError::KeySimulation(e.to_string()))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(InjectError::KeySimulation(format!(
+            "injection command exited with {}",
+            status
+        )))
+    }
+}
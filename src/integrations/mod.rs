@@ -0,0 +1,4 @@
+//! Outbound integrations that other services can subscribe to — currently
+//! just a webhook callback fired after each utterance.
+
+pub mod webhook;
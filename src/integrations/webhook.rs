@@ -0,0 +1,40 @@
+//! Fires a JSON POST to a user-configured URL after each finished
+//! utterance, so dictations can be piped into n8n/Zapier/Obsidian plugins
+//! and other automations. See `AppSettings.webhook_enabled`.
+
+use serde::Serialize;
+
+const TIMEOUT_SECS: u64 = 10;
+
+/// Body sent to `webhook_url`.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub raw_text: String,
+    pub corrected_text: Option<String>,
+    pub timestamp_secs: u64,
+    pub duration_ms: u64,
+    pub domain: Option<String>,
+}
+
+/// POSTs `payload` to `url` on a detached task so the pipeline never waits
+/// on (or fails because of) a slow or unreachable webhook endpoint.
+/// Failures are logged and otherwise swallowed.
+pub fn fire(url: String, payload: WebhookPayload) {
+    tokio::spawn(async move {
+        let client = reqwest::Client::new();
+        let result = client
+            .post(&url)
+            .json(&payload)
+            .timeout(std::time::Duration::from_secs(TIMEOUT_SECS))
+            .send()
+            .await;
+
+        match result {
+            Ok(resp) if !resp.status().is_success() => {
+                log::warn!("Webhook call to {} returned {}", url, resp.status());
+            }
+            Err(e) => log::warn!("Webhook call to {} failed: {}", url, e),
+            Ok(_) => {}
+        }
+    });
+}
@@ -0,0 +1,187 @@
+//! Bounded cache of LLM correction results, keyed on the raw transcription
+//! plus a hash of the `CorrectionContext` it was corrected under — repeated
+//! short utterances ("ครับ", "โอเค", common phrases) skip the network
+//! round-trip once seen before under the same context.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+use super::context::CorrectionContext;
+
+/// Hit/miss counters for a `CorrectionCache`, surfaced in the stats panel.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CorrectionCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CorrectionCacheStats {
+    /// Fraction of lookups served from cache, 0.0-1.0. `0.0` with no lookups yet.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+/// `(raw_text, context_hash)` — the same raw text can legitimately correct
+/// differently depending on the rolling context, so both go into the key.
+type CacheKey = (String, u64);
+
+struct CacheState {
+    entries: HashMap<CacheKey, String>,
+    /// Recency order, oldest first, for least-recently-used eviction.
+    order: VecDeque<CacheKey>,
+    stats: CorrectionCacheStats,
+}
+
+/// Bounded `raw_text -> corrected_text` cache with LRU eviction once
+/// `capacity` is exceeded. A `capacity` of 0 disables caching: every lookup
+/// is reported as a miss and nothing is ever stored.
+pub struct CorrectionCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+impl CorrectionCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+                stats: CorrectionCacheStats::default(),
+            }),
+        }
+    }
+
+    /// Hashes the parts of `context` that feed the correction prompt, so the
+    /// cache key changes whenever the prompt built from it would.
+    fn context_hash(context: &CorrectionContext) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        context.previous_sentences.hash(&mut hasher);
+        context.domain.hash(&mut hasher);
+        context.domain_terms.hash(&mut hasher);
+        context.user_vocab.hash(&mut hasher);
+        context.target_context.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn get(&self, raw_text: &str, context: &CorrectionContext) -> Option<String> {
+        if self.capacity == 0 {
+            self.state.lock().unwrap().stats.misses += 1;
+            return None;
+        }
+
+        let key = (raw_text.to_string(), Self::context_hash(context));
+        let mut state = self.state.lock().unwrap();
+        if let Some(text) = state.entries.get(&key).cloned() {
+            state.stats.hits += 1;
+            state.order.retain(|k| k != &key);
+            state.order.push_back(key);
+            Some(text)
+        } else {
+            state.stats.misses += 1;
+            None
+        }
+    }
+
+    pub fn insert(&self, raw_text: &str, context: &CorrectionContext, corrected: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let key = (raw_text.to_string(), Self::context_hash(context));
+        let mut state = self.state.lock().unwrap();
+        if !state.entries.contains_key(&key) {
+            state.order.push_back(key.clone());
+            while state.order.len() > self.capacity {
+                if let Some(oldest) = state.order.pop_front() {
+                    state.entries.remove(&oldest);
+                }
+            }
+        }
+        state.entries.insert(key, corrected);
+    }
+
+    pub fn stats(&self) -> CorrectionCacheStats {
+        self.state.lock().unwrap().stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> CorrectionContext {
+        CorrectionContext {
+            previous_sentences: Vec::new(),
+            domain: None,
+            domain_terms: Vec::new(),
+            user_vocab: Vec::new(),
+            target_context: None,
+        }
+    }
+
+    #[test]
+    fn miss_then_hit_for_the_same_text_and_context() {
+        let cache = CorrectionCache::new(10);
+        let ctx = context();
+
+        assert_eq!(cache.get("สวัดดี", &ctx), None);
+        cache.insert("สวัดดี", &ctx, "สวัสดี".to_string());
+        assert_eq!(cache.get("สวัดดี", &ctx), Some("สวัสดี".to_string()));
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+    }
+
+    #[test]
+    fn same_text_under_a_different_context_is_a_separate_entry() {
+        let cache = CorrectionCache::new(10);
+        let mut other = context();
+        other.domain = Some("medical".to_string());
+
+        cache.insert("ปวดหัว", &context(), "ปวดหัว (ทั่วไป)".to_string());
+        assert_eq!(cache.get("ปวดหัว", &other), None);
+    }
+
+    #[test]
+    fn zero_capacity_disables_caching() {
+        let cache = CorrectionCache::new(0);
+        let ctx = context();
+
+        cache.insert("โอเค", &ctx, "โอเค".to_string());
+        assert_eq!(cache.get("โอเค", &ctx), None);
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_entry_once_over_capacity() {
+        let cache = CorrectionCache::new(2);
+        let ctx = context();
+
+        cache.insert("a", &ctx, "A".to_string());
+        cache.insert("b", &ctx, "B".to_string());
+        // Touch "a" so "b" becomes the least recently used.
+        assert_eq!(cache.get("a", &ctx), Some("A".to_string()));
+        cache.insert("c", &ctx, "C".to_string());
+
+        assert_eq!(cache.get("b", &ctx), None);
+        assert_eq!(cache.get("a", &ctx), Some("A".to_string()));
+        assert_eq!(cache.get("c", &ctx), Some("C".to_string()));
+    }
+
+    #[test]
+    fn hit_rate_reflects_hits_over_total_lookups() {
+        let stats = CorrectionCacheStats { hits: 3, misses: 1 };
+        assert_eq!(stats.hit_rate(), 0.75);
+        assert_eq!(CorrectionCacheStats::default().hit_rate(), 0.0);
+    }
+}
@@ -0,0 +1,760 @@
+//! Rolling context, domain detection, and user vocabulary for Context Mode.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AppPaths, DomainOverride};
+
+/// Context handed to the LLM corrector for a single correction call.
+pub struct CorrectionContext {
+    pub previous_sentences: Vec<String>,
+    pub domain: Option<String>,
+    /// Keyword list for `domain`, if detected — e.g. medical terms.
+    pub domain_terms: Vec<String>,
+    pub user_vocab: Vec<(String, String)>,
+    /// Trailing lines of the document the user is dictating into, if
+    /// `AppSettings::target_context_enabled` is on — see
+    /// `inject::clipboard::read_recent_lines`. Lets corrections match the
+    /// target document's existing terminology and tone. `None` when the
+    /// feature is off, unavailable, or nothing was captured.
+    pub target_context: Option<String>,
+}
+
+impl CorrectionContext {
+    /// Terms to bias Whisper's own decoding toward, via `initial_prompt`,
+    /// before the LLM correction pass even runs: the user's learned
+    /// vocabulary corrections plus the detected domain's keyword list.
+    pub fn initial_prompt(&self) -> Option<String> {
+        let mut terms: Vec<String> = self
+            .user_vocab
+            .iter()
+            .map(|(_, correction)| correction.clone())
+            .collect();
+        terms.extend(self.domain_terms.iter().cloned());
+
+        if terms.is_empty() {
+            None
+        } else {
+            Some(terms.join(" "))
+        }
+    }
+}
+
+pub struct ContextManager {
+    sentences: VecDeque<String>,
+    max_sentences: usize,
+    domain_detector: DomainDetector,
+    user_vocab: UserVocabulary,
+    last_activity: Instant,
+    silence_reset: Duration,
+}
+
+impl ContextManager {
+    pub fn new() -> Self {
+        Self::for_profile(None)
+    }
+
+    /// Builds a `ContextManager` whose vocabulary is scoped to `profile`
+    /// (see `AppProfile`), or the shared default vocabulary when `None`.
+    pub fn for_profile(profile: Option<&str>) -> Self {
+        let user_vocab = match profile {
+            Some(name) => UserVocabulary::load_or_default_for(name),
+            None => UserVocabulary::load_or_default(),
+        };
+        Self {
+            sentences: VecDeque::with_capacity(5),
+            max_sentences: 3,
+            domain_detector: DomainDetector::load_or_default(),
+            user_vocab,
+            last_activity: Instant::now(),
+            silence_reset: Duration::from_secs(120),
+        }
+    }
+
+    /// `target_context` is trailing text from the document the user is
+    /// dictating into (see `CorrectionContext::target_context`), captured
+    /// by the caller right before this is called since reading it is I/O
+    /// (a clipboard read) rather than state this manager tracks itself.
+    /// `domain_override` skips `DomainDetector::detect` in favor of a pinned
+    /// domain or no domain at all — see `config::DomainOverride`, resolved
+    /// by the caller from the active `AppProfile`/`AppSettings` before this
+    /// is called, for the same reason `target_context` is.
+    pub fn build_context(
+        &self,
+        target_context: Option<String>,
+        domain_override: Option<&DomainOverride>,
+    ) -> CorrectionContext {
+        let all_text = self.sentences.iter().cloned().collect::<Vec<_>>().join(" ");
+        let domain = match domain_override {
+            Some(DomainOverride::Locked(name)) => Some(name.clone()),
+            Some(DomainOverride::Disabled) => None,
+            None => self.domain_detector.detect(&all_text),
+        };
+        let domain_terms = domain
+            .as_deref()
+            .map(|d| self.domain_detector.keywords_for(d))
+            .unwrap_or_default();
+
+        CorrectionContext {
+            previous_sentences: self.sentences.iter().cloned().collect(),
+            domain,
+            domain_terms,
+            user_vocab: self.user_vocab.top_entries(5),
+            target_context,
+        }
+    }
+
+    pub fn push_sentence(&mut self, sentence: String) {
+        if self.last_activity.elapsed() > self.silence_reset {
+            self.sentences.clear();
+        }
+
+        self.sentences.push_back(sentence);
+        while self.sentences.len() > self.max_sentences {
+            self.sentences.pop_front();
+        }
+
+        self.last_activity = Instant::now();
+    }
+
+    pub fn reset(&mut self) {
+        self.sentences.clear();
+    }
+
+    /// Apply the learned vocabulary's deterministic replacements to `text`.
+    /// See `UserVocabulary::apply_replacements`.
+    pub fn apply_vocabulary(&self, text: &str) -> String {
+        self.user_vocab.apply_replacements(text)
+    }
+
+    /// Diff a user-edited final transcript against what the LLM produced,
+    /// and learn every changed word as a `UserVocabulary` correction.
+    /// Aligned by word position, so it only learns from edits that keep the
+    /// word count unchanged (the common case of swapping one wrong word for
+    /// the right one); an edit that inserts or deletes words learns nothing.
+    pub fn learn_correction(&mut self, llm_output: &str, user_edit: &str) {
+        let original_words: Vec<&str> = llm_output.split_whitespace().collect();
+        let edited_words: Vec<&str> = user_edit.split_whitespace().collect();
+
+        if original_words.len() != edited_words.len() {
+            return;
+        }
+
+        for (error, correction) in original_words.into_iter().zip(edited_words) {
+            if error != correction {
+                self.user_vocab
+                    .add(error.to_string(), correction.to_string());
+            }
+        }
+    }
+}
+
+impl Default for ContextManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A keyword entry in `domains.toml`. Accepts either a bare string (weight
+/// defaults to 1.0, for backward compatibility with pre-weighting configs)
+/// or a `{ term = "...", weight = ... }` table for a keyword that should
+/// count for more or less than the rest of its domain's list.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum KeywordSpec {
+    Term(String),
+    Weighted { term: String, weight: f32 },
+}
+
+impl KeywordSpec {
+    fn term(&self) -> &str {
+        match self {
+            KeywordSpec::Term(t) => t,
+            KeywordSpec::Weighted { term, .. } => term,
+        }
+    }
+
+    fn weight(&self) -> f32 {
+        match self {
+            KeywordSpec::Term(_) => 1.0,
+            KeywordSpec::Weighted { weight, .. } => *weight,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DomainConfig {
+    name: String,
+    keywords: Vec<KeywordSpec>,
+    /// Minimum weighted-keyword score, normalized by the utterance's word
+    /// count, for `detect` to report this domain. A fraction rather than a
+    /// raw count so short utterances (few words, one strong keyword) can
+    /// still cross it — the old "count >= N" rule effectively required N
+    /// distinct keyword hits regardless of how long the utterance was,
+    /// which never fired on short ones.
+    threshold: f32,
+}
+
+/// Deserialization shape for `domains.toml`: a list of `[[domain]]` tables.
+#[derive(Deserialize)]
+struct DomainsFile {
+    domain: Vec<DomainConfig>,
+}
+
+pub struct DomainDetector {
+    domains: Vec<DomainConfig>,
+}
+
+/// Strips common English inflectional suffixes so e.g. "servers"/"deployed"
+/// still match a "server"/"deploy" keyword. Deliberately crude (no
+/// dictionary, no irregular forms) — good enough for the short technical
+/// nouns/verbs domain keyword lists actually contain. Thai keywords don't
+/// go through this: Thai doesn't inflect words this way, and `word` is only
+/// stemmed when it's pure ASCII letters.
+fn stem_en(word: &str) -> String {
+    let lower = word.to_lowercase();
+    for suffix in ["ing", "ed", "es", "s"] {
+        if lower.len() > suffix.len() + 2 && lower.ends_with(suffix) {
+            return lower[..lower.len() - suffix.len()].to_string();
+        }
+    }
+    lower
+}
+
+fn is_ascii_word(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+impl DomainDetector {
+    pub fn new() -> Self {
+        fn kw(term: &str) -> KeywordSpec {
+            KeywordSpec::Term(term.to_string())
+        }
+        fn weighted(term: &str, weight: f32) -> KeywordSpec {
+            KeywordSpec::Weighted {
+                term: term.to_string(),
+                weight,
+            }
+        }
+
+        Self {
+            domains: vec![
+                DomainConfig {
+                    name: "medical".into(),
+                    keywords: vec![
+                        weighted("ผู้ป่วย", 1.5),
+                        kw("ยา"),
+                        kw("อาการ"),
+                        weighted("โรค", 1.5),
+                        weighted("แพทย์", 1.5),
+                        weighted("วินิจฉัย", 2.0),
+                        weighted("โรงพยาบาล", 1.5),
+                        weighted("เบาหวาน", 2.0),
+                        kw("ความดัน"),
+                    ],
+                    threshold: 0.15,
+                },
+                DomainConfig {
+                    name: "legal".into(),
+                    keywords: vec![
+                        weighted("กฎหมาย", 1.5),
+                        kw("สัญญา"),
+                        weighted("ศาล", 1.5),
+                        weighted("จำเลย", 2.0),
+                        weighted("โจทก์", 2.0),
+                        kw("คดี"),
+                        weighted("ข้อพิพาท", 1.5),
+                        weighted("พระราชบัญญัติ", 2.0),
+                    ],
+                    threshold: 0.15,
+                },
+                DomainConfig {
+                    name: "technical".into(),
+                    keywords: vec![
+                        kw("code"),
+                        kw("function"),
+                        kw("server"),
+                        weighted("deploy", 1.5),
+                        kw("database"),
+                        weighted("API", 1.5),
+                        weighted("bug", 1.5),
+                        kw("ซอฟต์แวร์"),
+                        kw("ระบบ"),
+                    ],
+                    threshold: 0.15,
+                },
+                DomainConfig {
+                    name: "finance".into(),
+                    keywords: vec![
+                        weighted("งบประมาณ", 1.5),
+                        kw("บัญชี"),
+                        weighted("ดอกเบี้ย", 1.5),
+                        kw("ลงทุน"),
+                        weighted("หุ้น", 1.5),
+                        weighted("ธนาคาร", 1.5),
+                        kw("invoice"),
+                        weighted("revenue", 1.5),
+                        weighted("budget", 1.5),
+                        kw("audit"),
+                    ],
+                    threshold: 0.15,
+                },
+                DomainConfig {
+                    name: "education".into(),
+                    keywords: vec![
+                        kw("นักเรียน"),
+                        kw("นักศึกษา"),
+                        weighted("หลักสูตร", 1.5),
+                        kw("การบ้าน"),
+                        weighted("สอบ", 1.5),
+                        kw("อาจารย์"),
+                        kw("lecture"),
+                        kw("syllabus"),
+                        weighted("assignment", 1.5),
+                        kw("semester"),
+                    ],
+                    threshold: 0.15,
+                },
+                DomainConfig {
+                    name: "gaming".into(),
+                    keywords: vec![
+                        weighted("เกม", 1.2),
+                        kw("ผู้เล่น"),
+                        weighted("ด่าน", 1.5),
+                        kw("เควส"),
+                        kw("respawn"),
+                        weighted("cooldown", 1.5),
+                        kw("guild"),
+                        weighted("matchmaking", 1.5),
+                        kw("nerf"),
+                        kw("buff"),
+                    ],
+                    threshold: 0.15,
+                },
+            ],
+        }
+    }
+
+    /// Counts how many times `term` occurs in `text`. English terms are
+    /// matched whole-word against `text`'s stemmed tokens (so "deploy"
+    /// matches "deployed"); everything else falls back to substring
+    /// counting, since Thai script has no reliable word-boundary split.
+    fn occurrences(text: &str, term: &str) -> usize {
+        if is_ascii_word(term) {
+            let stemmed = stem_en(term);
+            text.split_whitespace()
+                .filter(|w| is_ascii_word(w) && stem_en(w) == stemmed)
+                .count()
+        } else {
+            text.matches(term).count()
+        }
+    }
+
+    pub fn detect(&self, text: &str) -> Option<String> {
+        let word_count = text.split_whitespace().count().max(1) as f32;
+
+        self.domains
+            .iter()
+            .filter_map(|d| {
+                let score: f32 = d
+                    .keywords
+                    .iter()
+                    .map(|k| k.weight() * Self::occurrences(text, k.term()) as f32)
+                    .sum();
+                let normalized = score / word_count;
+                if normalized >= d.threshold {
+                    Some((d.name.clone(), normalized))
+                } else {
+                    None
+                }
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(name, _)| name)
+    }
+
+    /// Keyword list for a domain by name, or empty if unrecognized.
+    pub fn keywords_for(&self, name: &str) -> Vec<String> {
+        self.domains
+            .iter()
+            .find(|d| d.name == name)
+            .map(|d| d.keywords.iter().map(|k| k.term().to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Load `domains.toml`, falling back to the built-in domain set if it
+    /// doesn't exist or fails to parse.
+    pub fn load_or_default() -> Self {
+        let path = AppPaths::domains_path();
+        let domains = if path.exists() {
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| toml::from_str::<DomainsFile>(&content).ok())
+                .map(|file| file.domain)
+                .unwrap_or_else(|| Self::new().domains)
+        } else {
+            Self::new().domains
+        };
+        Self { domains }
+    }
+}
+
+impl Default for DomainDetector {
+    fn default() -> Self {
+        Self::load_or_default()
+    }
+}
+
+#[cfg(test)]
+mod domain_tests {
+    use super::*;
+
+    #[test]
+    fn stem_en_strips_the_matched_suffix() {
+        let cases = [
+            ("bugs", "bug"),
+            ("deployed", "deploy"),
+            ("servers", "server"),
+            ("running", "runn"), // crude: no doubled-consonant undo, see doc comment
+        ];
+        for (input, expected) in cases {
+            assert_eq!(stem_en(input), expected, "stemming {:?}", input);
+        }
+    }
+
+    #[test]
+    fn stem_en_guard_leaves_short_words_that_are_only_barely_longer_than_their_suffix() {
+        // len() > suffix.len() + 2 requires at least 3 letters of "stem" left
+        // over — a word only as long as suffix.len() + 2 fails that and is
+        // left alone, even though it superficially ends in the suffix.
+        assert_eq!(stem_en("was"), "was"); // "s" suffix, len 3 == 1 + 2
+        assert_eq!(stem_en("bus"), "bus"); // same boundary
+    }
+
+    #[test]
+    fn stem_en_strips_once_past_the_guard_boundary() {
+        // One letter longer than the "was"/"bus" boundary case is enough to
+        // cross the guard and get stripped.
+        assert_eq!(stem_en("bugs"), "bug"); // "s" suffix, len 4 > 1 + 2
+    }
+
+    #[test]
+    fn occurrences_matches_english_terms_by_stem_not_substring() {
+        let text = "we deployed the server yesterday and redeployed it today";
+        // "deploy" should match "deployed" and "redeployed" via stemming —
+        // wait, "redeployed" is a distinct token that doesn't stem to
+        // "deploy" (the "re" prefix isn't stripped), so only "deployed"
+        // counts.
+        assert_eq!(DomainDetector::occurrences(text, "deploy"), 1);
+        assert_eq!(DomainDetector::occurrences(text, "server"), 1);
+        assert_eq!(DomainDetector::occurrences(text, "database"), 0);
+    }
+
+    #[test]
+    fn occurrences_falls_back_to_substring_counting_for_non_ascii_terms() {
+        let text = "ผู้ป่วยมาโรงพยาบาลเพราะผู้ป่วยมีอาการไข้";
+        assert_eq!(DomainDetector::occurrences(text, "ผู้ป่วย"), 2);
+    }
+
+    #[test]
+    fn detect_picks_the_domain_whose_normalized_score_clears_its_threshold() {
+        let detector = DomainDetector::new();
+        let text = "the server had a bug in deploy";
+        assert_eq!(detector.detect(text), Some("technical".to_string()));
+    }
+
+    #[test]
+    fn detect_returns_none_when_no_domain_clears_its_threshold() {
+        let detector = DomainDetector::new();
+        // One weak (weight 1.0) keyword diluted across a long sentence
+        // stays under the 0.15 threshold.
+        let text = "I like to code today with my friends after school for fun";
+        assert_eq!(detector.detect(text), None);
+    }
+
+    #[test]
+    fn detect_is_inclusive_at_exactly_the_threshold() {
+        let detector = DomainDetector::new();
+        // "bug" (weight 1.5) once across exactly 10 words: 1.5 / 10 == 0.15,
+        // the technical domain's threshold exactly — `>=` must include it.
+        let text = "the bug was found by the team during testing today";
+        assert_eq!(text.split_whitespace().count(), 10);
+        assert_eq!(detector.detect(text), Some("technical".to_string()));
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct VocabEntry {
+    pub error: String,
+    pub correction: String,
+    pub frequency: u32,
+}
+
+pub struct UserVocabulary {
+    entries: Vec<VocabEntry>,
+    path: PathBuf,
+}
+
+impl UserVocabulary {
+    pub fn load_or_default() -> Self {
+        Self::load_from(AppPaths::vocab_path())
+    }
+
+    /// Loads (or starts fresh) the vocabulary scoped to a named `AppProfile`,
+    /// stored separately from the shared default vocabulary.
+    pub fn load_or_default_for(profile: &str) -> Self {
+        Self::load_from(AppPaths::vocab_path_for_profile(profile))
+    }
+
+    fn load_from(path: PathBuf) -> Self {
+        let entries = if path.exists() {
+            let data = std::fs::read_to_string(&path).unwrap_or_default();
+            serde_json::from_str(&data).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        Self { entries, path }
+    }
+
+    pub fn add(&mut self, error: String, correction: String) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.error == error) {
+            entry.correction = correction;
+            entry.frequency += 1;
+        } else {
+            self.entries.push(VocabEntry {
+                error,
+                correction,
+                frequency: 1,
+            });
+        }
+        self.save();
+    }
+
+    pub fn top_entries(&self, n: usize) -> Vec<(String, String)> {
+        let mut sorted = self.entries.clone();
+        sorted.sort_by(|a, b| b.frequency.cmp(&a.frequency));
+        sorted
+            .into_iter()
+            .take(n)
+            .map(|e| (e.error, e.correction))
+            .collect()
+    }
+
+    /// Deterministically substitute every learned `error → correction` pair
+    /// as an exact match, independent of whether the LLM runs. Guarantees
+    /// known fixes apply even in Fast mode or when the LLM declines to
+    /// change the text.
+    pub fn apply_replacements(&self, text: &str) -> String {
+        let mut output = text.to_string();
+        for entry in &self.entries {
+            if entry.error.is_empty() {
+                continue;
+            }
+            output = output.replace(&entry.error, &entry.correction);
+        }
+        output
+    }
+
+    fn save(&self) {
+        if let Some(parent) = self.path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(data) = serde_json::to_string_pretty(&self.entries) {
+            let _ = std::fs::write(&self.path, data);
+        }
+    }
+
+    /// Merges a shared `error,correction[,frequency]` CSV — e.g. a clinic's
+    /// standard terminology list — into this vocabulary and persists the
+    /// result. An `error` already present has its `frequency` summed with
+    /// the imported one and its `correction` overwritten with the imported
+    /// value, on the assumption that a deliberately shared list is more
+    /// authoritative than whatever this installation happened to learn on
+    /// its own. Rows missing a `correction` column are skipped. A leading
+    /// header row (`error,correction[,frequency]`, case-insensitive) is
+    /// recognized and skipped; frequency defaults to 1 when the column is
+    /// absent or unparseable. Returns the number of rows merged.
+    pub fn import_csv(&mut self, path: &Path) -> std::io::Result<usize> {
+        let content = std::fs::read_to_string(path)?;
+        let records = parse_csv(&content);
+        let mut imported = 0;
+
+        for (i, record) in records.iter().enumerate() {
+            if record.len() < 2 {
+                continue;
+            }
+            if i == 0
+                && record[0].eq_ignore_ascii_case("error")
+                && record[1].eq_ignore_ascii_case("correction")
+            {
+                continue;
+            }
+
+            let error = record[0].trim().to_string();
+            let correction = record[1].trim().to_string();
+            if error.is_empty() || correction.is_empty() {
+                continue;
+            }
+            let frequency: u32 = record
+                .get(2)
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(1);
+
+            if let Some(entry) = self.entries.iter_mut().find(|e| e.error == error) {
+                entry.correction = correction;
+                entry.frequency += frequency;
+            } else {
+                self.entries.push(VocabEntry {
+                    error,
+                    correction,
+                    frequency,
+                });
+            }
+            imported += 1;
+        }
+
+        self.save();
+        Ok(imported)
+    }
+
+    /// Writes this vocabulary out as `error,correction,frequency` CSV, for
+    /// sharing a standard terminology list across installations (the
+    /// counterpart to `import_csv`).
+    pub fn export_csv(&self, path: &Path) -> std::io::Result<()> {
+        let mut out = String::from("error,correction,frequency\n");
+        for entry in &self.entries {
+            out.push_str(&csv_field(&entry.error));
+            out.push(',');
+            out.push_str(&csv_field(&entry.correction));
+            out.push(',');
+            out.push_str(&entry.frequency.to_string());
+            out.push('\n');
+        }
+        std::fs::write(path, out)
+    }
+}
+
+/// Quotes `s` for a CSV field if it contains a comma, quote, or newline
+/// (RFC 4180 style, doubling embedded quotes), otherwise returns it as-is.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Minimal RFC 4180 CSV parser: comma-separated fields, `"..."` quoting
+/// with `""` as an escaped quote, quoted fields may contain embedded
+/// newlines. No external crate exists in this project's dependencies for
+/// this, and the format is small enough that hand-rolling it is simpler
+/// than adding one just for two methods.
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.push(c);
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod csv_tests {
+    use super::*;
+
+    fn vocab_at(path: PathBuf) -> UserVocabulary {
+        UserVocabulary {
+            entries: Vec::new(),
+            path,
+        }
+    }
+
+    #[test]
+    fn import_skips_row_with_empty_correction() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv_path = dir.path().join("terms.csv");
+        std::fs::write(&csv_path, "error,correction,frequency\nword,,5\n").unwrap();
+
+        let mut vocab = vocab_at(dir.path().join("vocab.json"));
+        let imported = vocab.import_csv(&csv_path).unwrap();
+
+        assert_eq!(imported, 0);
+        assert!(vocab.top_entries(10).is_empty());
+    }
+
+    #[test]
+    fn import_merges_existing_entry_by_summing_frequency() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv_path = dir.path().join("terms.csv");
+        std::fs::write(&csv_path, "error,correction,frequency\nสวัดดี,สวัสดี,3\n").unwrap();
+
+        let mut vocab = vocab_at(dir.path().join("vocab.json"));
+        vocab.add("สวัดดี".to_string(), "old correction".to_string());
+        let imported = vocab.import_csv(&csv_path).unwrap();
+
+        assert_eq!(imported, 1);
+        let entries = vocab.top_entries(10);
+        assert_eq!(entries, vec![("สวัดดี".to_string(), "สวัสดี".to_string())]);
+        // 1 from the earlier `add` plus 3 from the imported row.
+        assert_eq!(vocab.entries[0].frequency, 4);
+    }
+
+    #[test]
+    fn export_then_import_round_trips_a_comma_containing_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv_path = dir.path().join("terms.csv");
+
+        let mut source = vocab_at(dir.path().join("source.json"));
+        source.add("err".to_string(), "one, two".to_string());
+        source.export_csv(&csv_path).unwrap();
+
+        let mut dest = vocab_at(dir.path().join("dest.json"));
+        let imported = dest.import_csv(&csv_path).unwrap();
+
+        assert_eq!(imported, 1);
+        assert_eq!(
+            dest.top_entries(10),
+            vec![("err".to_string(), "one, two".to_string())]
+        );
+    }
+
+    #[test]
+    fn parse_csv_handles_quoted_field_with_embedded_comma_and_quote() {
+        let records = parse_csv("a,\"b, \"\"c\"\"\",d\n");
+        assert_eq!(records, vec![vec!["a", "b, \"c\"", "d"]]);
+    }
+}
@@ -0,0 +1,638 @@
+//! LLM-based STT post-correction backends.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use tokio::sync::mpsc;
+
+use crate::config::{CorrectionStyle, LlmProvider};
+
+use super::cache::{CorrectionCache, CorrectionCacheStats};
+use super::context::CorrectionContext;
+use super::prompt::PromptBuilder;
+use super::usage::UsageTracker;
+
+/// LLM Corrector abstraction — swap Ollama for OpenAI-compatible API, llama_cpp, or cloud.
+#[async_trait]
+pub trait LlmCorrector: Send + Sync {
+    async fn correct(&self, raw_text: &str, context: &CorrectionContext) -> anyhow::Result<String>;
+
+    /// Like `correct`, but sends each piece of the response down `partial_tx`
+    /// as it arrives, so the UI can show a correction growing in place
+    /// instead of a spinner. Backends that can't stream ignore `partial_tx`
+    /// and fall back to `correct`.
+    async fn correct_streaming(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+        partial_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<String> {
+        let _ = partial_tx;
+        self.correct(raw_text, context).await
+    }
+
+    /// Whether this provider currently looks reachable. Used to surface
+    /// per-provider status in the settings panel.
+    async fn health_check(&self) -> bool {
+        true
+    }
+
+    /// Human-readable label paired with `health_check()`, one entry per
+    /// provider this corrector talks to. `ChainCorrector` overrides this to
+    /// report on every provider in its failover chain.
+    async fn provider_status(&self) -> Vec<(String, bool)> {
+        vec![("default".to_string(), self.health_check().await)]
+    }
+
+    /// True once this provider's configured daily token budget (see
+    /// `llm::usage::UsageTracker`) is exhausted. Providers with no cost
+    /// model (Ollama, offline) never hit a budget and use the default
+    /// `false`.
+    async fn budget_exceeded(&self) -> bool {
+        false
+    }
+
+    /// Best-effort request to get the provider ready before the first real
+    /// correction — e.g. loading the model into memory. Called once at
+    /// startup when `AppSettings::warm_up_enabled` is set; failures are
+    /// logged and otherwise ignored, since a cold first request still works,
+    /// just slower. No-op for providers with nothing to warm up.
+    async fn warm_up(&self) {}
+
+    /// Hit/miss counters for the result cache wrapping this corrector, for
+    /// display in the stats panel. Only `CachingCorrector` returns `Some`;
+    /// every other implementation has no cache to report on.
+    async fn cache_stats(&self) -> Option<CorrectionCacheStats> {
+        None
+    }
+}
+
+/// Configuration for an LLM corrector — provider-agnostic.
+pub struct LlmCorrectorConfig {
+    pub provider: LlmProvider,
+    pub base_url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+    pub temperature: f32,
+    pub timeout_secs: u64,
+    pub target_language: String,
+    /// Daily token cap for cloud providers (`OpenAiCompatible`). 0 means
+    /// unlimited. Ignored by providers with no per-token cost.
+    pub daily_token_budget: u64,
+    /// Which alternate prompt `PromptBuilder` uses. See `CorrectionStyle`.
+    pub correction_style: CorrectionStyle,
+    /// Ollama's `keep_alive` request parameter — how long it holds the
+    /// model in memory after this request before unloading it, e.g. `"5m"`,
+    /// `"-1"` (forever), or `"0"` (unload immediately). Empty omits the
+    /// parameter and falls back to Ollama's own default. Ignored by every
+    /// other provider.
+    pub ollama_keep_alive: String,
+}
+
+/// Talks to Ollama or any OpenAI-compatible HTTP API.
+pub struct ApiCorrector {
+    config: LlmCorrectorConfig,
+    prompt_builder: PromptBuilder,
+    client: reqwest::Client,
+    usage: UsageTracker,
+}
+
+impl ApiCorrector {
+    pub fn from_config(config: LlmCorrectorConfig) -> Self {
+        Self {
+            prompt_builder: PromptBuilder::new(&config.target_language, config.correction_style),
+            client: reqwest::Client::new(),
+            usage: UsageTracker::load_or_default(),
+            config,
+        }
+    }
+
+    /// OpenAI-compatible API — POST /v1/chat/completions.
+    /// Covers: OpenAI, Groq, Together.ai, LM Studio, vLLM.
+    async fn correct_openai(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+    ) -> anyhow::Result<String> {
+        if self.usage.is_over_budget(self.config.daily_token_budget) {
+            anyhow::bail!("Daily LLM token budget exhausted");
+        }
+
+        let (system_msg, user_msg) = self.prompt_builder.build_chat(raw_text, context);
+
+        let mut req = self
+            .client
+            .post(format!("{}/v1/chat/completions", self.config.base_url))
+            .json(&serde_json::json!({
+                "model": self.config.model,
+                "messages": [
+                    {"role": "system", "content": system_msg},
+                    {"role": "user",   "content": user_msg}
+                ],
+                "temperature": self.config.temperature,
+                "max_tokens": 256
+            }))
+            .timeout(std::time::Duration::from_secs(self.config.timeout_secs));
+
+        if let Some(key) = &self.config.api_key {
+            req = req.bearer_auth(key);
+        }
+
+        let body: serde_json::Value = req.send().await?.json().await?;
+        let corrected = body["choices"][0]["message"]["content"]
+            .as_str()
+            .unwrap_or(raw_text)
+            .trim()
+            .to_string();
+
+        if let Some(tokens) = body["usage"]["total_tokens"].as_u64() {
+            self.usage.record(tokens);
+        }
+
+        Ok(corrected)
+    }
+
+    /// OpenAI-compatible API with `stream: true` — parses the response as
+    /// Server-Sent Events (`data: {...}` lines, terminated by `data: [DONE]`)
+    /// and forwards each delta down `partial_tx` as it arrives.
+    async fn correct_openai_streaming(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+        partial_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<String> {
+        if self.usage.is_over_budget(self.config.daily_token_budget) {
+            anyhow::bail!("Daily LLM token budget exhausted");
+        }
+
+        let (system_msg, user_msg) = self.prompt_builder.build_chat(raw_text, context);
+
+        let mut req = self
+            .client
+            .post(format!("{}/v1/chat/completions", self.config.base_url))
+            .json(&serde_json::json!({
+                "model": self.config.model,
+                "messages": [
+                    {"role": "system", "content": system_msg},
+                    {"role": "user",   "content": user_msg}
+                ],
+                "temperature": self.config.temperature,
+                "max_tokens": 256,
+                "stream": true
+            }))
+            .timeout(std::time::Duration::from_secs(self.config.timeout_secs));
+
+        if let Some(key) = &self.config.api_key {
+            req = req.bearer_auth(key);
+        }
+
+        let response = req.send().await?;
+        let mut stream = response.bytes_stream();
+        let mut line_buf = String::new();
+        let mut corrected = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            line_buf.push_str(&String::from_utf8_lossy(&chunk?));
+
+            while let Some(newline_pos) = line_buf.find('\n') {
+                let line = line_buf[..newline_pos].trim().to_string();
+                line_buf.drain(..=newline_pos);
+
+                let Some(data) = line.strip_prefix("data:") else {
+                    continue;
+                };
+                let data = data.trim();
+                if data == "[DONE]" {
+                    return Ok(corrected.trim().to_string());
+                }
+                if data.is_empty() {
+                    continue;
+                }
+
+                let event: serde_json::Value = serde_json::from_str(data)?;
+                if let Some(piece) = event["choices"][0]["delta"]["content"].as_str() {
+                    corrected.push_str(piece);
+                    let _ = partial_tx.send(corrected.clone()).await;
+                }
+            }
+        }
+
+        Ok(corrected.trim().to_string())
+    }
+
+    /// Health check — works for both Ollama and OpenAI-compatible.
+    pub async fn health_check(&self) -> bool {
+        let url = match self.config.provider {
+            LlmProvider::Ollama => format!("{}/api/tags", self.config.base_url),
+            LlmProvider::OpenAiCompatible => format!("{}/v1/models", self.config.base_url),
+            _ => return false,
+        };
+
+        let mut req = self
+            .client
+            .get(&url)
+            .timeout(std::time::Duration::from_secs(2));
+        if let Some(key) = &self.config.api_key {
+            req = req.bearer_auth(key);
+        }
+
+        req.send().await.is_ok()
+    }
+}
+
+#[async_trait]
+impl LlmCorrector for ApiCorrector {
+    async fn correct(&self, raw_text: &str, context: &CorrectionContext) -> anyhow::Result<String> {
+        match self.config.provider {
+            LlmProvider::OpenAiCompatible => self.correct_openai(raw_text, context).await,
+            _ => Ok(raw_text.to_string()),
+        }
+    }
+
+    async fn correct_streaming(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+        partial_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<String> {
+        match self.config.provider {
+            LlmProvider::OpenAiCompatible => {
+                self.correct_openai_streaming(raw_text, context, partial_tx)
+                    .await
+            }
+            _ => Ok(raw_text.to_string()),
+        }
+    }
+
+    async fn health_check(&self) -> bool {
+        ApiCorrector::health_check(self).await
+    }
+
+    async fn budget_exceeded(&self) -> bool {
+        matches!(self.config.provider, LlmProvider::OpenAiCompatible)
+            && self.usage.is_over_budget(self.config.daily_token_budget)
+    }
+}
+
+/// Talks to Ollama's native `/api/generate` endpoint, which streams its
+/// response as newline-delimited JSON rather than returning one chat object.
+pub struct OllamaCorrector {
+    config: LlmCorrectorConfig,
+    prompt_builder: PromptBuilder,
+    client: reqwest::Client,
+}
+
+impl OllamaCorrector {
+    pub fn from_config(config: LlmCorrectorConfig) -> Self {
+        Self {
+            prompt_builder: PromptBuilder::new(&config.target_language, config.correction_style),
+            client: reqwest::Client::new(),
+            config,
+        }
+    }
+}
+
+impl OllamaCorrector {
+    async fn correct_inner(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+        partial_tx: Option<&mpsc::Sender<String>>,
+    ) -> anyhow::Result<String> {
+        let prompt = self.prompt_builder.build(raw_text, context);
+
+        let mut body = serde_json::json!({
+            "model": self.config.model,
+            "prompt": prompt,
+            "options": { "temperature": self.config.temperature }
+        });
+        if !self.config.ollama_keep_alive.is_empty() {
+            body["keep_alive"] = serde_json::Value::String(self.config.ollama_keep_alive.clone());
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/api/generate", self.config.base_url))
+            .json(&body)
+            .timeout(std::time::Duration::from_secs(self.config.timeout_secs))
+            .send()
+            .await?;
+
+        let mut stream = response.bytes_stream();
+        let mut line_buf = String::new();
+        let mut corrected = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            line_buf.push_str(&String::from_utf8_lossy(&chunk?));
+
+            while let Some(newline_pos) = line_buf.find('\n') {
+                let line = line_buf[..newline_pos].trim().to_string();
+                line_buf.drain(..=newline_pos);
+                if line.is_empty() {
+                    continue;
+                }
+
+                let chunk: serde_json::Value = serde_json::from_str(&line)?;
+                if let Some(piece) = chunk["response"].as_str() {
+                    corrected.push_str(piece);
+                    if let Some(tx) = partial_tx {
+                        let _ = tx.send(corrected.clone()).await;
+                    }
+                }
+                if chunk["done"].as_bool().unwrap_or(false) {
+                    return Ok(corrected.trim().to_string());
+                }
+            }
+        }
+
+        Ok(corrected.trim().to_string())
+    }
+
+    pub async fn health_check(&self) -> bool {
+        self.client
+            .get(format!("{}/api/tags", self.config.base_url))
+            .timeout(std::time::Duration::from_secs(2))
+            .send()
+            .await
+            .is_ok()
+    }
+
+    /// Sends a prompt-less `/api/generate` request — Ollama's documented way
+    /// to load a model into memory without generating anything — so the
+    /// model is already resident before the first real dictation is
+    /// corrected instead of paying that load time on the critical path.
+    pub async fn warm_up(&self) {
+        let mut body = serde_json::json!({ "model": self.config.model });
+        if !self.config.ollama_keep_alive.is_empty() {
+            body["keep_alive"] = serde_json::Value::String(self.config.ollama_keep_alive.clone());
+        }
+        let result = self
+            .client
+            .post(format!("{}/api/generate", self.config.base_url))
+            .json(&body)
+            .timeout(std::time::Duration::from_secs(self.config.timeout_secs))
+            .send()
+            .await;
+        if let Err(e) = result {
+            log::warn!("Ollama warm-up request failed: {}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl LlmCorrector for OllamaCorrector {
+    async fn correct(&self, raw_text: &str, context: &CorrectionContext) -> anyhow::Result<String> {
+        self.correct_inner(raw_text, context, None).await
+    }
+
+    async fn correct_streaming(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+        partial_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<String> {
+        self.correct_inner(raw_text, context, Some(partial_tx))
+            .await
+    }
+
+    async fn health_check(&self) -> bool {
+        OllamaCorrector::health_check(self).await
+    }
+
+    async fn warm_up(&self) {
+        OllamaCorrector::warm_up(self).await
+    }
+}
+
+/// Tries an ordered list of correctors in turn, falling through to the next
+/// one on error or timeout. Built from a primary provider plus its
+/// configured fallback chain — see `AppSettings::llm_fallback_providers`.
+pub struct ChainCorrector {
+    correctors: Vec<(String, Arc<dyn LlmCorrector>)>,
+}
+
+#[async_trait]
+impl LlmCorrector for ChainCorrector {
+    async fn correct(&self, raw_text: &str, context: &CorrectionContext) -> anyhow::Result<String> {
+        let mut last_err = None;
+        for (label, corrector) in &self.correctors {
+            match corrector.correct(raw_text, context).await {
+                Ok(text) => return Ok(text),
+                Err(e) => {
+                    log::warn!("LLM provider '{}' failed, trying next: {}", label, e);
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("No LLM providers configured")))
+    }
+
+    async fn correct_streaming(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+        partial_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<String> {
+        let mut last_err = None;
+        for (label, corrector) in &self.correctors {
+            match corrector
+                .correct_streaming(raw_text, context, partial_tx)
+                .await
+            {
+                Ok(text) => return Ok(text),
+                Err(e) => {
+                    log::warn!("LLM provider '{}' failed, trying next: {}", label, e);
+                    last_err = Some(e);
+                }
+            }
+        }
+        Err(last_err.unwrap_or_else(|| anyhow::anyhow!("No LLM providers configured")))
+    }
+
+    async fn health_check(&self) -> bool {
+        for (_, corrector) in &self.correctors {
+            if corrector.health_check().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn provider_status(&self) -> Vec<(String, bool)> {
+        let mut statuses = Vec::with_capacity(self.correctors.len());
+        for (label, corrector) in &self.correctors {
+            statuses.push((label.clone(), corrector.health_check().await));
+        }
+        statuses
+    }
+
+    /// Only exhausted once every provider in the failover chain is — a
+    /// budget-capped primary still has fallbacks left to try.
+    async fn budget_exceeded(&self) -> bool {
+        for (_, corrector) in &self.correctors {
+            if !corrector.budget_exceeded().await {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Warms every provider in the failover chain, not just the primary —
+    /// there's no way to know in advance which one the first correction
+    /// will actually land on.
+    async fn warm_up(&self) {
+        for (_, corrector) in &self.correctors {
+            corrector.warm_up().await;
+        }
+    }
+}
+
+/// Wraps another corrector with a `CorrectionCache`, short-circuiting
+/// repeated identical `(raw_text, context)` pairs — e.g. common short
+/// utterances like "ครับ"/"โอเค" — instead of hitting the network every time.
+/// Sits outermost, above any `ChainCorrector`, so a hit is served regardless
+/// of which provider in the failover chain would otherwise have answered it.
+pub struct CachingCorrector {
+    inner: Arc<dyn LlmCorrector>,
+    cache: CorrectionCache,
+}
+
+impl CachingCorrector {
+    pub fn new(inner: Arc<dyn LlmCorrector>, capacity: usize) -> Self {
+        Self {
+            inner,
+            cache: CorrectionCache::new(capacity),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmCorrector for CachingCorrector {
+    async fn correct(&self, raw_text: &str, context: &CorrectionContext) -> anyhow::Result<String> {
+        if let Some(cached) = self.cache.get(raw_text, context) {
+            return Ok(cached);
+        }
+        let corrected = self.inner.correct(raw_text, context).await?;
+        self.cache.insert(raw_text, context, corrected.clone());
+        Ok(corrected)
+    }
+
+    async fn correct_streaming(
+        &self,
+        raw_text: &str,
+        context: &CorrectionContext,
+        partial_tx: &mpsc::Sender<String>,
+    ) -> anyhow::Result<String> {
+        if let Some(cached) = self.cache.get(raw_text, context) {
+            let _ = partial_tx.send(cached.clone()).await;
+            return Ok(cached);
+        }
+        let corrected = self
+            .inner
+            .correct_streaming(raw_text, context, partial_tx)
+            .await?;
+        self.cache.insert(raw_text, context, corrected.clone());
+        Ok(corrected)
+    }
+
+    async fn health_check(&self) -> bool {
+        self.inner.health_check().await
+    }
+
+    async fn provider_status(&self) -> Vec<(String, bool)> {
+        self.inner.provider_status().await
+    }
+
+    async fn budget_exceeded(&self) -> bool {
+        self.inner.budget_exceeded().await
+    }
+
+    async fn warm_up(&self) {
+        self.inner.warm_up().await
+    }
+
+    async fn cache_stats(&self) -> Option<CorrectionCacheStats> {
+        Some(self.cache.stats())
+    }
+}
+
+/// Passes text through unchanged. Used for `LlmProvider::LlamaCpp` (not yet
+/// implemented — see the `LlmProvider` doc comment) and `LlmProvider::Disabled`,
+/// so both behave like Fast mode instead of erroring if Context/Standard mode
+/// is selected alongside them.
+struct NoopCorrector;
+
+#[async_trait]
+impl LlmCorrector for NoopCorrector {
+    async fn correct(
+        &self,
+        raw_text: &str,
+        _context: &CorrectionContext,
+    ) -> anyhow::Result<String> {
+        Ok(raw_text.to_string())
+    }
+
+    async fn health_check(&self) -> bool {
+        false
+    }
+}
+
+/// Picks the corrector implementation matching `config.provider`. Both
+/// backends share `LlmCorrectorConfig`, so switching providers in settings
+/// doesn't require touching call sites.
+fn build_single_corrector(config: LlmCorrectorConfig) -> Arc<dyn LlmCorrector> {
+    match config.provider {
+        LlmProvider::Ollama => Arc::new(OllamaCorrector::from_config(config)),
+        LlmProvider::OpenAiCompatible => Arc::new(ApiCorrector::from_config(config)),
+        LlmProvider::Offline => Arc::new(super::offline::OfflineCorrector::new()),
+        LlmProvider::LlamaCpp | LlmProvider::Disabled => Arc::new(NoopCorrector),
+    }
+}
+
+/// Builds the primary corrector, wrapped in a `ChainCorrector` over
+/// `fallbacks` (tried in order on failure) when any are configured, then
+/// wrapped in a `CachingCorrector` unless `cache_capacity` is 0.
+pub fn build_corrector(
+    primary: LlmCorrectorConfig,
+    fallbacks: Vec<LlmCorrectorConfig>,
+    cache_capacity: usize,
+) -> Arc<dyn LlmCorrector> {
+    let corrector = if fallbacks.is_empty() {
+        build_single_corrector(primary)
+    } else {
+        let mut correctors = vec![("primary".to_string(), build_single_corrector(primary))];
+        for (i, fallback) in fallbacks.into_iter().enumerate() {
+            correctors.push((
+                format!("fallback-{}", i + 1),
+                build_single_corrector(fallback),
+            ));
+        }
+        Arc::new(ChainCorrector { correctors })
+    };
+
+    if cache_capacity == 0 {
+        corrector
+    } else {
+        Arc::new(CachingCorrector::new(corrector, cache_capacity))
+    }
+}
+
+/// Periodically calls `corrector.warm_up()` for the rest of the process's
+/// life, so an Ollama backend's `keep_alive` timer keeps getting refreshed
+/// during a dictation session even if the gaps between utterances are
+/// longer than the configured `keep_alive` duration. A no-op for every
+/// other provider, since their `warm_up` does nothing. Returns immediately
+/// if `interval_secs` is 0 — spawn it with `rt.spawn(...)`, the same way
+/// `PipelineOrchestrator::run` is spawned from `main`.
+pub async fn keep_alive_pinger(corrector: Arc<dyn LlmCorrector>, interval_secs: u64) {
+    if interval_secs == 0 {
+        return;
+    }
+    let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+    ticker.tick().await; // first tick fires immediately; the initial warm-up already covered it
+    loop {
+        ticker.tick().await;
+        corrector.warm_up().await;
+    }
+}
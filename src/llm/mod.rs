@@ -0,0 +1,20 @@
+//! LLM-based STT correction: prompt building, context management, provider backends.
+
+pub mod cache;
+pub mod context;
+pub mod corrector;
+pub mod offline;
+pub mod profiles;
+pub mod prompt;
+pub mod usage;
+
+pub use cache::{CorrectionCache, CorrectionCacheStats};
+pub use context::{ContextManager, CorrectionContext, DomainDetector, UserVocabulary};
+pub use corrector::{
+    build_corrector, keep_alive_pinger, ApiCorrector, CachingCorrector, ChainCorrector,
+    LlmCorrector, LlmCorrectorConfig, OllamaCorrector,
+};
+pub use offline::OfflineCorrector;
+pub use profiles::ProfileContextManager;
+pub use prompt::PromptBuilder;
+pub use usage::UsageTracker;
@@ -0,0 +1,72 @@
+//! Deterministic, offline post-processing for users without any LLM backend.
+
+use async_trait::async_trait;
+
+use super::context::CorrectionContext;
+use super::corrector::LlmCorrector;
+
+/// Thai filler particles Whisper transcribes literally but that carry no
+/// meaning in the final text.
+const FILLER_WORDS: &[&str] = &["เอ่อ", "อ่า", "อ่านะ", "เอิ่ม"];
+
+fn is_thai_char(c: char) -> bool {
+    matches!(c, '\u{0E00}'..='\u{0E7F}')
+}
+
+/// Rule-based STT cleanup with no network dependency: strips filler
+/// particles, rejoins syllables Whisper over-splits with spaces, and
+/// normalizes spacing at Thai/English and digit-run boundaries. Far less
+/// capable than an LLM pass, but gives users without any LLM backend
+/// configured cleanup beyond raw Whisper output.
+pub struct OfflineCorrector;
+
+impl OfflineCorrector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn clean(&self, text: &str) -> String {
+        let tokens = text
+            .split_whitespace()
+            .filter(|t| !FILLER_WORDS.contains(t));
+
+        let mut output = String::with_capacity(text.len());
+        let mut prev_last_char: Option<char> = None;
+
+        for token in tokens {
+            let Some(first_char) = token.chars().next() else {
+                continue;
+            };
+
+            if let Some(prev) = prev_last_char {
+                let join_syllables = is_thai_char(prev) && is_thai_char(first_char);
+                let join_digits = prev.is_ascii_digit() && first_char.is_ascii_digit();
+                if !join_syllables && !join_digits {
+                    output.push(' ');
+                }
+            }
+
+            output.push_str(token);
+            prev_last_char = token.chars().last();
+        }
+
+        output
+    }
+}
+
+impl Default for OfflineCorrector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LlmCorrector for OfflineCorrector {
+    async fn correct(
+        &self,
+        raw_text: &str,
+        _context: &CorrectionContext,
+    ) -> anyhow::Result<String> {
+        Ok(self.clean(raw_text))
+    }
+}
@@ -0,0 +1,63 @@
+//! Selects a `ContextManager` per `AppProfile`, based on whichever
+//! application currently has focus, so vocabulary learned while dictating
+//! into one app (e.g. a medical EMR) doesn't bleed into another (e.g. Slack).
+
+use std::collections::HashMap;
+
+use crate::config::AppProfile;
+use crate::inject::active_window;
+
+use super::context::ContextManager;
+
+/// Key (and profile name) used when no configured `AppProfile` matches the
+/// focused window, or focus can't be determined.
+const DEFAULT_PROFILE: &str = "default";
+
+pub struct ProfileContextManager {
+    profiles: Vec<AppProfile>,
+    contexts: HashMap<String, ContextManager>,
+}
+
+impl ProfileContextManager {
+    pub fn new(profiles: Vec<AppProfile>) -> Self {
+        let mut contexts = HashMap::new();
+        contexts.insert(DEFAULT_PROFILE.to_string(), ContextManager::new());
+        for profile in &profiles {
+            contexts.insert(
+                profile.name.clone(),
+                ContextManager::for_profile(Some(&profile.name)),
+            );
+        }
+        Self { profiles, contexts }
+    }
+
+    /// Name of the profile whose `match_pattern` is contained in the
+    /// focused window's title or process name, or `DEFAULT_PROFILE` if none
+    /// match (or the platform focus lookup fails). Public so callers can
+    /// look up profile-specific config (e.g. `AppProfile::domain_override`)
+    /// for whichever `ContextManager` `active()` is about to return.
+    pub fn active_profile_name(&self) -> String {
+        let Some(window) = active_window() else {
+            return DEFAULT_PROFILE.to_string();
+        };
+        let title = window.title.to_lowercase();
+        let process = window.process_name.to_lowercase();
+
+        self.profiles
+            .iter()
+            .find(|p| {
+                let pattern = p.match_pattern.to_lowercase();
+                title.contains(&pattern) || process.contains(&pattern)
+            })
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    /// The `ContextManager` for whichever application currently has focus.
+    pub fn active(&mut self) -> &mut ContextManager {
+        let name = self.active_profile_name();
+        self.contexts
+            .entry(name)
+            .or_insert_with(ContextManager::new)
+    }
+}
@@ -0,0 +1,408 @@
+//! Language-aware prompt construction for STT correction.
+
+use super::context::CorrectionContext;
+use crate::config::{AppPaths, CorrectionStyle};
+
+/// Thai — focuses on tone marks, homophones, filler words, Thai punctuation.
+const SYSTEM_INSTRUCTION_TH: &str = "\
+คุณคือระบบแก้ไขข้อความจาก Speech-to-Text สำหรับภาษาไทย
+หน้าที่: แก้ไขข้อผิดพลาดจากการถอดเสียง โดยรักษาความหมายเดิม
+
+กฎ:
+1. แก้ไขวรรณยุกต์และคำพ้องเสียงที่ผิด
+2. ลบคำอุทาน (เอ่อ, อ่า, อ่านะ, ครับ/ค่ะ ที่ไม่จำเป็น) ออก
+3. เพิ่มเครื่องหมายวรรคตอนที่เหมาะสม
+4. รักษาคำภาษาอังกฤษและศัพท์เทคนิค ไม่แปลงเป็นภาษาไทย
+5. ตอบเฉพาะข้อความที่แก้ไขแล้ว ไม่ต้องอธิบาย
+6. ถ้าข้อความถูกต้องแล้ว ให้ตอบข้อความเดิมกลับมา";
+
+/// Generic English / multilingual — handles filler words, punctuation, common STT errors.
+const SYSTEM_INSTRUCTION_EN: &str = "\
+You are a Speech-to-Text post-correction assistant.
+Task: Fix transcription errors while preserving the original meaning.
+
+Rules:
+1. Fix mis-transcribed words (homophones, wrong words that sound similar).
+2. Remove filler words (um, uh, like, you know, etc.).
+3. Add appropriate punctuation and capitalisation.
+4. Preserve technical terms, proper nouns, and code snippets exactly.
+5. Reply with ONLY the corrected text — no explanation.
+6. If the text is already correct, return it unchanged.";
+
+const FEW_SHOT_EXAMPLES_TH: &str = "
+Examples:
+Input: \"เอ่อ ผม เสร็จ งาน แล้ว นะ ครับ จะ ส่ง ให้ พรุ่งนี้\"
+Output: \"ผมเสร็จงานแล้ว จะส่งให้พรุ่งนี้\"
+
+Input: \"ไฟล์ มัน ไม่ โหลด เพราะ network connection มัน drop\"
+Output: \"ไฟล์ไม่โหลดเพราะ network connection drop\"
+
+Input: \"อ่า ผู้ป่วย มี ความดัน สูง 140 ต่อ 90\"
+Output: \"ผู้ป่วยมีความดันสูง 140/90\"
+";
+
+const FEW_SHOT_EXAMPLES_EN: &str = "
+Examples:
+Input: \"um I finished the report uh it should be ready by tomorrow\"
+Output: \"I finished the report. It should be ready by tomorrow.\"
+
+Input: \"the file won't load because the network connection like dropped\"
+Output: \"The file won't load because the network connection dropped.\"
+
+Input: \"the patient has hypertension one forty over ninety\"
+Output: \"The patient has hypertension 140/90.\"
+";
+
+/// Medical domain — same correction rules as the generic Thai instruction,
+/// but calls out drug names, dosages, and vital signs specifically since
+/// those are the terms an STT error is most costly to get wrong here.
+const SYSTEM_INSTRUCTION_MEDICAL_TH: &str = "\
+คุณคือระบบแก้ไขข้อความจาก Speech-to-Text สำหรับบันทึกทางการแพทย์ภาษาไทย
+หน้าที่: แก้ไขข้อผิดพลาดจากการถอดเสียง โดยรักษาความหมายเดิมและความแม่นยำทางคลินิก
+
+กฎ:
+1. แก้ไขวรรณยุกต์และคำพ้องเสียงที่ผิด โดยเฉพาะชื่อยา ขนาดยา และค่าสัญญาณชีพ
+2. ลบคำอุทาน (เอ่อ, อ่า, อ่านะ, ครับ/ค่ะ ที่ไม่จำเป็น) ออก
+3. เพิ่มเครื่องหมายวรรคตอนที่เหมาะสม รวมถึงหน่วยของค่าตัวเลข (มก., มม.ปรอท)
+4. รักษาศัพท์ทางการแพทย์และชื่อยาไว้ตามที่ผู้พูดออกเสียง ห้ามเดาหรือแก้ไขหากไม่แน่ใจ
+5. ตอบเฉพาะข้อความที่แก้ไขแล้ว ไม่ต้องอธิบาย
+6. ถ้าข้อความถูกต้องแล้ว ให้ตอบข้อความเดิมกลับมา";
+
+const SYSTEM_INSTRUCTION_MEDICAL_EN: &str = "\
+You are a Speech-to-Text post-correction assistant for medical dictation.
+Task: Fix transcription errors while preserving the original meaning and clinical accuracy.
+
+Rules:
+1. Fix mis-transcribed words, paying particular attention to drug names, dosages, and vital signs.
+2. Remove filler words (um, uh, like, you know, etc.).
+3. Add appropriate punctuation, capitalisation, and units (mg, mmHg).
+4. Preserve medical terminology and drug names as spoken — do not guess or \"correct\" them if unsure.
+5. Reply with ONLY the corrected text — no explanation.
+6. If the text is already correct, return it unchanged.";
+
+/// Legal domain — same rules, but the emphasis shifts to statute references,
+/// party names, and case terminology, where a wrong homophone changes meaning.
+const SYSTEM_INSTRUCTION_LEGAL_TH: &str = "\
+คุณคือระบบแก้ไขข้อความจาก Speech-to-Text สำหรับเอกสารทางกฎหมายภาษาไทย
+หน้าที่: แก้ไขข้อผิดพลาดจากการถอดเสียง โดยรักษาความหมายเดิมและความถูกต้องของศัพท์กฎหมาย
+
+กฎ:
+1. แก้ไขวรรณยุกต์และคำพ้องเสียงที่ผิด โดยเฉพาะชื่อคู่ความ เลขคดี และชื่อกฎหมาย
+2. ลบคำอุทาน (เอ่อ, อ่า, อ่านะ, ครับ/ค่ะ ที่ไม่จำเป็น) ออก
+3. เพิ่มเครื่องหมายวรรคตอนที่เหมาะสม
+4. รักษาศัพท์กฎหมายและชื่อพระราชบัญญัติไว้ตามที่ผู้พูดออกเสียง ห้ามเดาหากไม่แน่ใจ
+5. ตอบเฉพาะข้อความที่แก้ไขแล้ว ไม่ต้องอธิบาย
+6. ถ้าข้อความถูกต้องแล้ว ให้ตอบข้อความเดิมกลับมา";
+
+const SYSTEM_INSTRUCTION_LEGAL_EN: &str = "\
+You are a Speech-to-Text post-correction assistant for legal dictation.
+Task: Fix transcription errors while preserving the original meaning and legal terminology.
+
+Rules:
+1. Fix mis-transcribed words, paying particular attention to party names, case numbers, and statute names.
+2. Remove filler words (um, uh, like, you know, etc.).
+3. Add appropriate punctuation and capitalisation.
+4. Preserve legal terminology and statute names as spoken — do not guess them if unsure.
+5. Reply with ONLY the corrected text — no explanation.
+6. If the text is already correct, return it unchanged.";
+
+const FEW_SHOT_EXAMPLES_MEDICAL_TH: &str = "
+Examples:
+Input: \"อ่า ผู้ป่วย มี ความดัน สูง 140 ต่อ 90 ให้ ยา พารา 500 มก\"
+Output: \"ผู้ป่วยมีความดันสูง 140/90 ให้ยาพารา 500 มก.\"
+
+Input: \"คนไข้ มี อาการ เบาหวาน ครับ น้ำตาล ใน เลือด 180\"
+Output: \"คนไข้มีอาการเบาหวาน น้ำตาลในเลือด 180\"
+";
+
+const FEW_SHOT_EXAMPLES_MEDICAL_EN: &str = "
+Examples:
+Input: \"um the patient has hypertension one forty over ninety give paracetamol five hundred mg\"
+Output: \"The patient has hypertension 140/90. Give paracetamol 500 mg.\"
+";
+
+const FEW_SHOT_EXAMPLES_LEGAL_TH: &str = "
+Examples:
+Input: \"เอ่อ จำเลย ยื่น คำร้อง ต่อ ศาล ตาม มาตรา 157\"
+Output: \"จำเลยยื่นคำร้องต่อศาลตามมาตรา 157\"
+
+Input: \"โจทก์ ฟ้อง คดี หมายเลข ดำ ที่ 45 ปี 2568\"
+Output: \"โจทก์ฟ้องคดีหมายเลขดำที่ 45 ปี 2568\"
+";
+
+const FEW_SHOT_EXAMPLES_LEGAL_EN: &str = "
+Examples:
+Input: \"um the defendant filed a motion with the court under section one fifty seven\"
+Output: \"The defendant filed a motion with the court under Section 157.\"
+";
+
+/// Technical domain — Thai developers code-switch heavily, mixing English
+/// identifiers, commands, and jargon into otherwise-Thai sentences. The
+/// emphasis shifts from translation risk (medical/legal) to making sure
+/// embedded English/code tokens survive correction untouched.
+const SYSTEM_INSTRUCTION_TECHNICAL_TH: &str = "\
+คุณคือระบบแก้ไขข้อความจาก Speech-to-Text สำหรับการพูดคุยด้านเทคนิค/โปรแกรมมิ่งภาษาไทย
+หน้าที่: แก้ไขข้อผิดพลาดจากการถอดเสียง โดยรักษาความหมายเดิม
+
+กฎ:
+1. แก้ไขวรรณยุกต์และคำพ้องเสียงที่ผิดในส่วนภาษาไทยเท่านั้น
+2. ลบคำอุทาน (เอ่อ, อ่า, อ่านะ, ครับ/ค่ะ ที่ไม่จำเป็น) ออก
+3. เพิ่มเครื่องหมายวรรคตอนที่เหมาะสม
+4. คำภาษาอังกฤษ ชื่อฟังก์ชัน ชื่อตัวแปร คำสั่ง โค้ด และศัพท์เทคนิค ให้คงไว้ตามเดิมทุกตัวอักษร ห้ามแปลหรือแก้ไขตัวสะกด
+5. ตอบเฉพาะข้อความที่แก้ไขแล้ว ไม่ต้องอธิบาย
+6. ถ้าข้อความถูกต้องแล้ว ให้ตอบข้อความเดิมกลับมา";
+
+const SYSTEM_INSTRUCTION_TECHNICAL_EN: &str = "\
+You are a Speech-to-Text post-correction assistant for technical/programming dictation.
+Task: Fix transcription errors while preserving the original meaning.
+
+Rules:
+1. Fix mis-transcribed words (homophones, wrong words that sound similar).
+2. Remove filler words (um, uh, like, you know, etc.).
+3. Add appropriate punctuation and capitalisation.
+4. Preserve embedded English identifiers, function names, commands, code snippets, and technical jargon exactly as spoken — do not translate or \"correct\" their spelling.
+5. Reply with ONLY the corrected text — no explanation.
+6. If the text is already correct, return it unchanged.";
+
+const FEW_SHOT_EXAMPLES_TECHNICAL_TH: &str = "
+Examples:
+Input: \"เอ่อ ผม deploy เสร็จ แล้ว นะ ครับ แต่ database connection มัน timeout\"
+Output: \"ผม deploy เสร็จแล้ว แต่ database connection timeout\"
+
+Input: \"อ่า function นี้ มัน return null ตอน array มัน ว่าง\"
+Output: \"function นี้ return null ตอน array ว่าง\"
+";
+
+const FEW_SHOT_EXAMPLES_TECHNICAL_EN: &str = "
+Examples:
+Input: \"um I finished the deploy but the database connection like timed out\"
+Output: \"I finished the deploy, but the database connection timed out.\"
+";
+
+/// Structuring mode — reformats spoken enumerations into a Markdown list
+/// instead of just cleaning up the transcript. Domain-agnostic, so it takes
+/// the same instruction regardless of `ctx.domain`.
+const SYSTEM_INSTRUCTION_STRUCTURED_TH: &str = "\
+คุณคือระบบแก้ไขข้อความจาก Speech-to-Text และจัดรูปแบบเป็นรายการภาษาไทย
+หน้าที่: แก้ไขข้อผิดพลาดจากการถอดเสียง แล้วจัดข้อความที่พูดเป็นข้อ ๆ ให้เป็นรายการ Markdown
+
+กฎ:
+1. แก้ไขวรรณยุกต์และคำพ้องเสียงที่ผิด ลบคำอุทาน (เอ่อ, อ่า, ครับ/ค่ะ ที่ไม่จำเป็น)
+2. เมื่อผู้พูดแจกแจงเป็นข้อ (\"ข้อหนึ่ง ... ข้อสอง ...\", \"อย่างแรก ... อย่างที่สอง ...\") ให้จัดเป็นรายการลำดับเลขแบบ Markdown (1. 2. 3.)
+3. ถ้าผู้พูดไม่ได้แจกแจงเป็นข้อ แต่พูดถึงหลายประเด็นแยกกัน ให้จัดเป็นรายการหัวข้อย่อย (bullet list) แทน
+4. ถ้าเป็นประโยคเดียวไม่มีการแจกแจง ให้ตอบเป็นประโยคปกติ ไม่ต้องฝืนใส่รายการ
+5. ตอบเฉพาะข้อความที่จัดรูปแบบแล้ว ไม่ต้องอธิบาย";
+
+const SYSTEM_INSTRUCTION_STRUCTURED_EN: &str = "\
+You are a Speech-to-Text post-correction assistant that also reformats spoken enumerations into a Markdown list.
+Task: Fix transcription errors, then restructure the content into a list where appropriate.
+
+Rules:
+1. Fix mis-transcribed words and remove filler words (um, uh, like, you know, etc.).
+2. When the speaker enumerates items (\"first ... second ...\", \"one, ... two, ...\"), format them as a numbered Markdown list (1. 2. 3.).
+3. When the speaker covers several distinct points without explicit numbering, format them as a bulleted list instead.
+4. If there's nothing to enumerate, reply with a normal sentence — don't force a list onto a single thought.
+5. Reply with ONLY the reformatted text — no explanation.";
+
+const FEW_SHOT_EXAMPLES_STRUCTURED_TH: &str = "
+Examples:
+Input: \"เอ่อ วันนี้ต้องทำสามอย่าง ข้อหนึ่ง ส่งรายงาน ข้อสอง โทรหาลูกค้า ข้อสาม เช็คอีเมล\"
+Output: \"วันนี้ต้องทำสามอย่าง:\n1. ส่งรายงาน\n2. โทรหาลูกค้า\n3. เช็คอีเมล\"
+
+Input: \"อ่า ผมเสร็จงานแล้วนะครับ จะส่งให้พรุ่งนี้\"
+Output: \"ผมเสร็จงานแล้ว จะส่งให้พรุ่งนี้\"
+";
+
+const FEW_SHOT_EXAMPLES_STRUCTURED_EN: &str = "
+Examples:
+Input: \"um there are three things to do today first send the report second call the client third check email\"
+Output: \"Three things to do today:\n1. Send the report\n2. Call the client\n3. Check email\"
+
+Input: \"um I finished the report it should be ready by tomorrow\"
+Output: \"I finished the report. It should be ready by tomorrow.\"
+";
+
+/// SOAP-note mode — restructures a spoken clinical encounter into the
+/// Subjective/Objective/Assessment/Plan template, for the Medical domain.
+const SYSTEM_INSTRUCTION_SOAP_TH: &str = "\
+คุณคือระบบแก้ไขข้อความจาก Speech-to-Text และจัดรูปแบบเป็นบันทึกทางการแพทย์แบบ SOAP
+หน้าที่: แก้ไขข้อผิดพลาดจากการถอดเสียง แล้วจัดเนื้อหาเป็นหัวข้อ S/O/A/P
+
+กฎ:
+1. แก้ไขวรรณยุกต์และคำพ้องเสียงที่ผิด โดยเฉพาะชื่อยา ขนาดยา และค่าสัญญาณชีพ ลบคำอุทาน
+2. จัดเนื้อหาเป็นหัวข้อ Markdown ตามลำดับ: **Subjective**, **Objective**, **Assessment**, **Plan**
+3. ใส่เนื้อหาที่พูดถึงในหัวข้อที่ตรงกัน (อาการที่ผู้ป่วยเล่า = Subjective, ผลตรวจ/สัญญาณชีพ = Objective, การวินิจฉัย = Assessment, แผนการรักษา = Plan)
+4. ถ้าไม่มีเนื้อหาสำหรับหัวข้อใด ให้เว้นว่างหัวข้อนั้นไว้ ไม่ต้องเดา
+5. รักษาศัพท์ทางการแพทย์และชื่อยาไว้ตามที่ผู้พูดออกเสียง ห้ามเดาหากไม่แน่ใจ
+6. ตอบเฉพาะบันทึกที่จัดรูปแบบแล้ว ไม่ต้องอธิบาย";
+
+const SYSTEM_INSTRUCTION_SOAP_EN: &str = "\
+You are a Speech-to-Text post-correction assistant that also restructures a spoken clinical encounter into a SOAP note.
+Task: Fix transcription errors, then organize the content under Subjective/Objective/Assessment/Plan.
+
+Rules:
+1. Fix mis-transcribed words, paying particular attention to drug names, dosages, and vital signs. Remove filler words.
+2. Organize the content as Markdown headings, in order: **Subjective**, **Objective**, **Assessment**, **Plan**.
+3. File each part of what was said under the matching heading (patient-reported symptoms = Subjective, exam findings/vitals = Objective, diagnosis = Assessment, treatment plan = Plan).
+4. Leave a heading empty if nothing was said for it — don't invent content.
+5. Preserve medical terminology and drug names as spoken — do not guess or \"correct\" them if unsure.
+6. Reply with ONLY the formatted note — no explanation.";
+
+const FEW_SHOT_EXAMPLES_SOAP_TH: &str = "
+Examples:
+Input: \"อ่า คนไข้บอกว่าปวดหัวมาสามวัน ตรวจแล้วความดัน 140 ต่อ 90 ชีพจร 88 วินิจฉัยเป็นความดันโลหิตสูง ให้ยาลดความดัน กินเช้าเย็น\"
+Output: \"**Subjective**\nปวดหัวมา 3 วัน\n\n**Objective**\nความดัน 140/90, ชีพจร 88\n\n**Assessment**\nความดันโลหิตสูง\n\n**Plan**\nให้ยาลดความดัน กินเช้าเย็น\"
+";
+
+const FEW_SHOT_EXAMPLES_SOAP_EN: &str = "
+Examples:
+Input: \"um patient reports headache for three days blood pressure is one forty over ninety pulse eighty eight diagnosed as hypertension started on antihypertensive twice daily\"
+Output: \"**Subjective**\nHeadache for 3 days\n\n**Objective**\nBP 140/90, pulse 88\n\n**Assessment**\nHypertension\n\n**Plan**\nStarted on antihypertensive, twice daily\"
+";
+
+/// Builds prompts for the LLM corrector. Language-aware: selects the system
+/// instruction and few-shot examples based on `target_language`, further
+/// overridden by `style` when it's not `CorrectionStyle::Standard`.
+pub struct PromptBuilder {
+    language: String,
+    style: CorrectionStyle,
+    /// User override for the generic system instruction, loaded once at
+    /// startup from `AppPaths::prompts_dir()/system_<language>.txt`.
+    system_override: Option<String>,
+    /// User override for the generic few-shot examples, loaded once at
+    /// startup from `AppPaths::prompts_dir()/examples_<language>.txt`.
+    examples_override: Option<String>,
+}
+
+impl PromptBuilder {
+    pub fn new(language: &str, style: CorrectionStyle) -> Self {
+        Self {
+            language: language.to_string(),
+            style,
+            system_override: Self::load_override(language, "system"),
+            examples_override: Self::load_override(language, "examples"),
+        }
+    }
+
+    /// Read `<prompts_dir>/<kind>_<language>.txt`, if present. Power users
+    /// use this to override the built-in system instruction or few-shot
+    /// examples without recompiling.
+    fn load_override(language: &str, kind: &str) -> Option<String> {
+        let path = AppPaths::prompts_dir().join(format!("{}_{}.txt", kind, language));
+        std::fs::read_to_string(path).ok()
+    }
+
+    /// Build a flat prompt (for Ollama's native `/api/generate`).
+    pub fn build(&self, raw_text: &str, ctx: &CorrectionContext) -> String {
+        let domain = ctx.domain.as_deref();
+        let system = self.system_instruction(domain);
+        let examples = self.few_shot_examples(domain);
+        let mut prompt = String::with_capacity(2048);
+
+        prompt.push_str(system);
+        self.append_context_parts(&mut prompt, ctx, raw_text, examples);
+        prompt
+    }
+
+    /// Build chat messages (for OpenAI-compatible `/v1/chat/completions`).
+    pub fn build_chat(&self, raw_text: &str, ctx: &CorrectionContext) -> (String, String) {
+        let domain = ctx.domain.as_deref();
+        let system_msg = self.system_instruction(domain).to_string();
+        let examples = self.few_shot_examples(domain);
+        let mut user_msg = String::with_capacity(1024);
+        self.append_context_parts(&mut user_msg, ctx, raw_text, examples);
+        (system_msg, user_msg)
+    }
+
+    fn append_context_parts(
+        &self,
+        buf: &mut String,
+        ctx: &CorrectionContext,
+        raw_text: &str,
+        examples: &str,
+    ) {
+        if let Some(domain) = &ctx.domain {
+            buf.push_str(&format!("\nDomain: {}\n", domain));
+        }
+        if let Some(target) = &ctx.target_context {
+            buf.push_str("\nExisting document text (match its terminology and tone):\n");
+            buf.push_str(target);
+            buf.push('\n');
+        }
+        if !ctx.user_vocab.is_empty() {
+            buf.push_str("\nUser-specific terms:\n");
+            for (error, correct) in ctx.user_vocab.iter().take(5) {
+                buf.push_str(&format!("- \"{}\" → \"{}\"\n", error, correct));
+            }
+        }
+        buf.push_str(examples);
+        if !ctx.previous_sentences.is_empty() {
+            buf.push_str("\nPrevious context:\n");
+            for sent in ctx.previous_sentences.iter().rev().take(3) {
+                buf.push_str(&format!("- {}\n", sent));
+            }
+        }
+        buf.push_str(&format!(
+            "\nOriginal STT output:\n{}\n\nCorrected:\n",
+            raw_text
+        ));
+    }
+
+    /// `style`-specific instruction when `self.style` isn't `Standard` —
+    /// takes priority over domain, since structuring is an explicit user
+    /// choice rather than an inferred one. Falls back to the domain-specific
+    /// instruction when `domain` is a recognized specialty ("medical",
+    /// "legal", "technical"), then the user's override file, then the generic language
+    /// default.
+    fn system_instruction(&self, domain: Option<&str>) -> &str {
+        match (self.language.as_str(), self.style) {
+            ("th", CorrectionStyle::StructuredList) => return SYSTEM_INSTRUCTION_STRUCTURED_TH,
+            ("th", CorrectionStyle::SoapNote) => return SYSTEM_INSTRUCTION_SOAP_TH,
+            (_, CorrectionStyle::StructuredList) => return SYSTEM_INSTRUCTION_STRUCTURED_EN,
+            (_, CorrectionStyle::SoapNote) => return SYSTEM_INSTRUCTION_SOAP_EN,
+            (_, CorrectionStyle::Standard) => {}
+        }
+        match (self.language.as_str(), domain) {
+            ("th", Some("medical")) => SYSTEM_INSTRUCTION_MEDICAL_TH,
+            ("th", Some("legal")) => SYSTEM_INSTRUCTION_LEGAL_TH,
+            ("th", Some("technical")) => SYSTEM_INSTRUCTION_TECHNICAL_TH,
+            ("th", _) => self
+                .system_override
+                .as_deref()
+                .unwrap_or(SYSTEM_INSTRUCTION_TH),
+            (_, Some("medical")) => SYSTEM_INSTRUCTION_MEDICAL_EN,
+            (_, Some("legal")) => SYSTEM_INSTRUCTION_LEGAL_EN,
+            (_, Some("technical")) => SYSTEM_INSTRUCTION_TECHNICAL_EN,
+            (_, _) => self
+                .system_override
+                .as_deref()
+                .unwrap_or(SYSTEM_INSTRUCTION_EN),
+        }
+    }
+
+    fn few_shot_examples(&self, domain: Option<&str>) -> &str {
+        match (self.language.as_str(), self.style) {
+            ("th", CorrectionStyle::StructuredList) => return FEW_SHOT_EXAMPLES_STRUCTURED_TH,
+            ("th", CorrectionStyle::SoapNote) => return FEW_SHOT_EXAMPLES_SOAP_TH,
+            (_, CorrectionStyle::StructuredList) => return FEW_SHOT_EXAMPLES_STRUCTURED_EN,
+            (_, CorrectionStyle::SoapNote) => return FEW_SHOT_EXAMPLES_SOAP_EN,
+            (_, CorrectionStyle::Standard) => {}
+        }
+        match (self.language.as_str(), domain) {
+            ("th", Some("medical")) => FEW_SHOT_EXAMPLES_MEDICAL_TH,
+            ("th", Some("legal")) => FEW_SHOT_EXAMPLES_LEGAL_TH,
+            ("th", Some("technical")) => FEW_SHOT_EXAMPLES_TECHNICAL_TH,
+            ("th", _) => self
+                .examples_override
+                .as_deref()
+                .unwrap_or(FEW_SHOT_EXAMPLES_TH),
+            (_, Some("medical")) => FEW_SHOT_EXAMPLES_MEDICAL_EN,
+            (_, Some("legal")) => FEW_SHOT_EXAMPLES_LEGAL_EN,
+            (_, Some("technical")) => FEW_SHOT_EXAMPLES_TECHNICAL_EN,
+            (_, _) => self
+                .examples_override
+                .as_deref()
+                .unwrap_or(FEW_SHOT_EXAMPLES_EN),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Daily token/request usage tracking for cloud LLM providers, so a paid
+//! OpenAI-compatible API doesn't run up an unexpected bill.
+
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppPaths;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UsageState {
+    /// Days since the Unix epoch, used instead of a calendar date so this
+    /// module doesn't need a date/time dependency beyond `std`.
+    day: u64,
+    tokens: u64,
+    requests: u64,
+}
+
+impl UsageState {
+    fn fresh() -> Self {
+        Self {
+            day: epoch_day(),
+            tokens: 0,
+            requests: 0,
+        }
+    }
+}
+
+fn epoch_day() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() / 86_400)
+        .unwrap_or(0)
+}
+
+/// Rolls `state` over to a fresh day if `today` has moved past it, then
+/// records one request's token usage — split out of `UsageTracker::record`
+/// so the rollover boundary can be exercised with an arbitrary `today`
+/// instead of waiting for the real clock to cross midnight.
+fn record_at(state: &mut UsageState, today: u64, tokens: u64) {
+    if state.day != today {
+        *state = UsageState {
+            day: today,
+            tokens: 0,
+            requests: 0,
+        };
+    }
+    state.tokens += tokens;
+    state.requests += 1;
+}
+
+/// Tokens spent on `today`, or 0 if `state` is stale (from an earlier day).
+fn tokens_today_at(state: &UsageState, today: u64) -> u64 {
+    if state.day == today {
+        state.tokens
+    } else {
+        0
+    }
+}
+
+/// Whether `tokens_today` has reached `budget`. A budget of 0 means
+/// unlimited — always `false`.
+fn is_over_budget_at(tokens_today: u64, budget: u64) -> bool {
+    budget > 0 && tokens_today >= budget
+}
+
+/// Persists token/request counts for the current day to `llm-usage.json`,
+/// resetting automatically when the day rolls over.
+pub struct UsageTracker {
+    path: std::path::PathBuf,
+    state: Mutex<UsageState>,
+}
+
+impl UsageTracker {
+    pub fn load_or_default() -> Self {
+        let path = AppPaths::usage_path();
+        let state = if path.exists() {
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<UsageState>(&content).ok())
+                .unwrap_or_else(UsageState::fresh)
+        } else {
+            UsageState::fresh()
+        };
+        Self {
+            path,
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Records one request's token usage, rolling the tally over to a fresh
+    /// day first if needed.
+    pub fn record(&self, tokens: u64) {
+        let mut state = self.state.lock().unwrap();
+        record_at(&mut state, epoch_day(), tokens);
+        self.save(&state);
+    }
+
+    /// Tokens spent today, or 0 if nothing has been recorded since the last
+    /// day rollover.
+    pub fn tokens_today(&self) -> u64 {
+        let state = self.state.lock().unwrap();
+        tokens_today_at(&state, epoch_day())
+    }
+
+    /// Whether today's usage has reached `budget`. A budget of 0 means
+    /// unlimited — always returns `false`.
+    pub fn is_over_budget(&self, budget: u64) -> bool {
+        is_over_budget_at(self.tokens_today(), budget)
+    }
+
+    fn save(&self, state: &UsageState) {
+        if let Some(parent) = self.path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(data) = serde_json::to_string_pretty(state) {
+            let _ = std::fs::write(&self.path, data);
+        }
+    }
+}
+
+impl Default for UsageTracker {
+    fn default() -> Self {
+        Self::load_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_at_accumulates_within_the_same_day() {
+        let mut state = UsageState::fresh();
+        state.day = 100;
+        record_at(&mut state, 100, 50);
+        record_at(&mut state, 100, 25);
+        assert_eq!(state.tokens, 75);
+        assert_eq!(state.requests, 2);
+        assert_eq!(state.day, 100);
+    }
+
+    #[test]
+    fn record_at_resets_the_tally_when_the_day_rolls_over() {
+        let mut state = UsageState::fresh();
+        state.day = 100;
+        state.tokens = 900;
+        state.requests = 9;
+        record_at(&mut state, 101, 10);
+        assert_eq!(state.tokens, 10);
+        assert_eq!(state.requests, 1);
+        assert_eq!(state.day, 101);
+    }
+
+    #[test]
+    fn tokens_today_at_returns_zero_for_a_stale_day() {
+        let mut state = UsageState::fresh();
+        state.day = 100;
+        state.tokens = 500;
+        assert_eq!(tokens_today_at(&state, 100), 500);
+        assert_eq!(tokens_today_at(&state, 101), 0);
+    }
+
+    #[test]
+    fn is_over_budget_at_treats_a_zero_budget_as_unlimited() {
+        assert!(!is_over_budget_at(1_000_000, 0));
+    }
+
+    #[test]
+    fn is_over_budget_at_is_inclusive_of_the_boundary() {
+        assert!(!is_over_budget_at(99, 100));
+        assert!(is_over_budget_at(100, 100));
+        assert!(is_over_budget_at(101, 100));
+    }
+}
@@ -0,0 +1,111 @@
+//! Structured per-session logging on top of the existing `log` facade used
+//! everywhere else in the app. `logging::init` replaces `env_logger::init`:
+//! it prints the same formatted lines to stderr, but also mirrors them into
+//! a timestamped file under `AppPaths::logs_dir()`, so a bug report can
+//! attach the whole session's log instead of whatever scrolled past in the
+//! terminal. Old session files beyond `MAX_SESSION_LOGS` are pruned.
+
+use std::fs::File;
+use std::io::Write;
+use std::sync::Mutex;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::config::AppPaths;
+
+/// Session log files kept under `AppPaths::logs_dir()` before the oldest
+/// are deleted.
+const MAX_SESSION_LOGS: usize = 20;
+
+struct SessionLogger {
+    file: Mutex<File>,
+}
+
+impl Log for SessionLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let line = format!(
+            "[{} {} {}] {}",
+            unix_millis(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        eprintln!("{line}");
+        if let Ok(mut file) = self.file.lock() {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+
+    fn flush(&self) {
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.flush();
+        }
+    }
+}
+
+fn unix_millis() -> u128 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+/// Sets up the process-wide logger: stderr plus a rotating session log
+/// file. Call once at startup instead of `env_logger::init()`. Falls back
+/// to plain `env_logger` if the log directory or file can't be created.
+pub fn init() {
+    let level = std::env::var("RUST_LOG")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(LevelFilter::Info);
+
+    let dir = AppPaths::logs_dir();
+    if AppPaths::ensure_dir(&dir).is_ok() {
+        prune_old_sessions(&dir);
+        let path = dir.join(format!("session-{}.log", unix_millis()));
+        if let Ok(file) = File::create(&path) {
+            let logger = SessionLogger {
+                file: Mutex::new(file),
+            };
+            if log::set_boxed_logger(Box::new(logger)).is_ok() {
+                log::set_max_level(level);
+                return;
+            }
+        }
+    }
+    env_logger::init();
+}
+
+fn prune_old_sessions(dir: &std::path::Path) {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = read_dir
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok().map(|m| (e.path(), m)))
+        .collect();
+    entries.sort_by_key(|(_, meta)| meta.modified().ok());
+
+    while entries.len() >= MAX_SESSION_LOGS {
+        let (path, _) = entries.remove(0);
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// Redacts `text` for logging unless `AppSettings.log_transcripts` is
+/// enabled, so a shared session log doesn't leak what the user dictated by
+/// default while still showing stage timings and error context.
+pub fn redact_transcript(text: &str, log_transcripts: bool) -> String {
+    if log_transcripts {
+        text.to_string()
+    } else {
+        format!("[redacted, {} chars]", text.chars().count())
+    }
+}
@@ -0,0 +1,434 @@
+mod app;
+mod audio;
+mod cli;
+mod commands;
+mod config;
+mod control;
+mod history;
+mod hotkey;
+mod inject;
+mod integrations;
+mod llm;
+mod logging;
+mod pipeline;
+mod power;
+mod shutdown;
+mod stt;
+mod text;
+mod updater;
+
+use std::sync::{Arc, Mutex};
+
+use eframe::egui;
+use parking_lot::RwLock;
+
+use app::ThaiSttApp;
+use audio::{build_vad_engine, AudioBuffer, AudioCapture};
+use config::AppSettings;
+use hotkey::HotkeyEvent;
+use inject::build_injector;
+use llm::LlmCorrectorConfig;
+use pipeline::{Channels, PipelineCommand, PipelineOrchestrator};
+use stt::{ModelManager, WhisperEngine};
+
+fn native_options(settings: &AppSettings) -> eframe::NativeOptions {
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_always_on_top()
+        .with_decorations(false)
+        .with_transparent(true)
+        .with_inner_size([300.0, 80.0])
+        .with_min_inner_size([250.0, 50.0])
+        .with_resizable(false);
+
+    // Restored as-is; `ThaiSttApp` validates it against the actual monitor
+    // geometry on the first frame and clamps back on-screen if it doesn't
+    // fit (e.g. a monitor was unplugged since the position was saved).
+    if let Some((x, y)) = settings.widget_position {
+        viewport = viewport.with_position([x, y]);
+    }
+
+    eframe::NativeOptions {
+        viewport,
+        ..Default::default()
+    }
+}
+
+fn main() -> eframe::Result<()> {
+    config::AppPaths::set_portable(cli::portable_mode());
+    logging::init();
+
+    if let Some(path) = cli::transcribe_arg() {
+        cli::run(&path);
+    }
+
+    if let Some(path) = cli::benchmark_arg() {
+        cli::run_benchmark(&path);
+    }
+
+    let mut settings = AppSettings::load();
+    config::overrides::apply(&mut settings);
+    let channels = Channels::new();
+    let (hotkey_tx, mut hotkey_rx) = tokio::sync::mpsc::channel::<HotkeyEvent>(16);
+
+    let audio_buffer = Arc::new(Mutex::new(AudioBuffer::with_spill(
+        settings.max_recording_secs as usize,
+        settings.audio_spill_threshold_secs,
+    )));
+
+    let rt = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .expect("Failed to create tokio runtime");
+
+    let model_path = stt::find_model(&settings.stt_model)
+        .map(|m| m.local_path())
+        .unwrap_or_default();
+    if let Some(model) = stt::find_model(&settings.stt_model) {
+        stt::check_ram_budget(model).unwrap_or_else(|e| panic!("{}", e));
+
+        let needs_download = if model.is_downloaded() {
+            if let Err(e) = stt::verify_integrity(model) {
+                log::warn!("{} — re-downloading", e);
+                std::fs::remove_file(model.local_path()).ok();
+                true
+            } else {
+                false
+            }
+        } else {
+            true
+        };
+        if needs_download {
+            rt.block_on(stt::ModelDownloader::default().download(model, |_| {}))
+                .unwrap_or_else(|e| panic!("Failed to download {}: {}", model.display_name, e));
+        }
+    }
+    let mut whisper_engine = WhisperEngine::with_params(
+        &model_path.to_string_lossy(),
+        settings.use_gpu,
+        settings.hallucination_blocklist.clone(),
+        stt::TranscribeParams::from_settings(&settings),
+    )
+    .expect("Failed to load Whisper model");
+    log::info!("STT backend: {:?}", whisper_engine.active_backend());
+
+    if !settings.stt_threads_calibrated {
+        let best =
+            stt::calibration::calibrate(&whisper_engine, stt::WhisperEngine::optimal_threads());
+        log::info!("Thread calibration picked {} threads", best);
+        whisper_engine.set_n_threads(best);
+        settings.stt_n_threads = best;
+        settings.stt_threads_calibrated = true;
+        if let Err(e) = settings.save() {
+            log::warn!("Failed to persist calibrated thread count: {}", e);
+        }
+    }
+
+    if settings.warm_up_enabled {
+        whisper_engine.warm_up();
+    }
+
+    let stt_engine = Arc::new(ModelManager::new(
+        whisper_engine,
+        settings.hallucination_blocklist.clone(),
+    ));
+    let remote_stt = Arc::new(stt::RemoteSttEngine::new(
+        settings.stt_remote_url.clone(),
+        config::secrets::resolve(&settings.stt_remote_api_key, config::secrets::STT_REMOTE),
+    ));
+    let vosk_stt = Arc::new(stt::VoskEngine::new(settings.stt_vosk_url.clone()));
+
+    let llm_fallback_configs = settings
+        .llm_fallback_providers
+        .iter()
+        .enumerate()
+        .map(|(i, p)| LlmCorrectorConfig {
+            provider: p.provider.clone(),
+            base_url: p.base_url.clone(),
+            api_key: config::secrets::resolve(&p.api_key, &config::secrets::fallback(i)),
+            model: p.model.clone(),
+            temperature: settings.llm_temperature,
+            timeout_secs: settings.llm_timeout_secs,
+            target_language: settings.stt_language.clone(),
+            daily_token_budget: settings.llm_daily_token_budget,
+            correction_style: settings.llm_correction_style,
+            ollama_keep_alive: settings.ollama_keep_alive.clone(),
+        })
+        .collect();
+    let llm_corrector = llm::build_corrector(
+        LlmCorrectorConfig {
+            provider: settings.llm_provider.clone(),
+            base_url: settings.llm_base_url.clone(),
+            api_key: config::secrets::resolve(&settings.llm_api_key, config::secrets::PRIMARY),
+            model: settings.llm_model.clone(),
+            temperature: settings.llm_temperature,
+            timeout_secs: settings.llm_timeout_secs,
+            target_language: settings.stt_language.clone(),
+            daily_token_budget: settings.llm_daily_token_budget,
+            correction_style: settings.llm_correction_style,
+            ollama_keep_alive: settings.ollama_keep_alive.clone(),
+        },
+        llm_fallback_configs,
+        settings.llm_cache_size,
+    );
+
+    if settings.warm_up_enabled {
+        let warm_up_corrector = llm_corrector.clone();
+        rt.spawn(async move { warm_up_corrector.warm_up().await });
+    }
+    rt.spawn(llm::keep_alive_pinger(
+        llm_corrector.clone(),
+        settings.ollama_keep_alive_ping_secs,
+    ));
+
+    let vad = build_vad_engine(&settings);
+    let text_injector = build_injector(&settings);
+    let shared_settings = Arc::new(RwLock::new(settings.clone()));
+
+    let mut audio_capture =
+        AudioCapture::new(settings.preroll_secs).expect("Failed to initialize audio capture");
+    let input_level = audio_capture.level();
+    let preroll = audio_capture.preroll();
+
+    let orchestrator = PipelineOrchestrator::new(
+        audio_buffer.clone(),
+        stt_engine,
+        remote_stt,
+        vosk_stt,
+        llm_corrector,
+        vad,
+        text_injector,
+        shared_settings.clone(),
+        preroll,
+    );
+    rt.spawn(orchestrator.run(channels.command_rx, channels.result_tx));
+
+    if settings.control_api_enabled {
+        control::spawn_control_server(
+            settings.control_api_port,
+            shared_settings.clone(),
+            audio_buffer.clone(),
+            channels.command_tx.clone(),
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    if settings.ipc_socket_enabled {
+        control::socket::spawn_socket_listener(
+            config::AppPaths::ipc_socket_path(),
+            audio_buffer.clone(),
+            channels.command_tx.clone(),
+        );
+    }
+
+    power::spawn_power_monitor(channels.command_tx.clone());
+
+    if settings.check_for_updates {
+        let _ = channels
+            .command_tx
+            .try_send(PipelineCommand::CheckForUpdate);
+    }
+
+    // Bridge hotkey press/release into pipeline start/stop commands.
+    let bridge_command_tx = channels.command_tx.clone();
+    let (visibility_tx, visibility_rx) = tokio::sync::mpsc::channel::<()>(4);
+    let bridge_settings = shared_settings.clone();
+    rt.spawn(async move {
+        let mut paused = false;
+        while let Some(event) = hotkey_rx.recv().await {
+            let command = match event {
+                HotkeyEvent::PushToTalkPressed => pipeline::PipelineCommand::StartRecording,
+                HotkeyEvent::PushToTalkReleased => pipeline::PipelineCommand::StopRecording,
+                HotkeyEvent::ToggleVisibility => {
+                    let _ = visibility_tx.try_send(());
+                    continue;
+                }
+                HotkeyEvent::PauseResumeToggle => {
+                    paused = !paused;
+                    if paused {
+                        pipeline::PipelineCommand::PauseRecording
+                    } else {
+                        pipeline::PipelineCommand::ResumeRecording
+                    }
+                }
+                HotkeyEvent::DoubleTap => {
+                    // Skip the LLM correction pass for quick snippets without
+                    // opening the settings panel. `Context` mode also drops
+                    // to `Fast`, same as `Standard` — the double-tap gesture
+                    // only ever chooses between "correcting" and "not".
+                    let next_mode = match bridge_settings.read().operating_mode {
+                        config::OperatingMode::Fast => config::OperatingMode::Standard,
+                        config::OperatingMode::Standard | config::OperatingMode::Context => {
+                            config::OperatingMode::Fast
+                        }
+                    };
+                    pipeline::PipelineCommand::ChangeMode(next_mode)
+                }
+                HotkeyEvent::TranslateToggle => pipeline::PipelineCommand::ToggleTranslate,
+                HotkeyEvent::PresetPushToTalkPressed(id) => {
+                    let _ = bridge_command_tx
+                        .send(pipeline::PipelineCommand::ApplyPreset(id))
+                        .await;
+                    pipeline::PipelineCommand::StartRecording
+                }
+                HotkeyEvent::PresetPushToTalkReleased(_id) => {
+                    pipeline::PipelineCommand::StopRecording
+                }
+            };
+            let _ = bridge_command_tx.send(command).await;
+        }
+    });
+
+    // Reload settings.toml automatically whenever it's edited outside the app.
+    let (settings_changed_tx, mut settings_changed_rx) = tokio::sync::mpsc::channel::<()>(4);
+    config::watcher::spawn_settings_watcher(settings_changed_tx);
+    let reload_command_tx = channels.command_tx.clone();
+    rt.spawn(async move {
+        while settings_changed_rx.recv().await.is_some() {
+            let _ = reload_command_tx
+                .send(pipeline::PipelineCommand::ReloadConfig)
+                .await;
+        }
+    });
+
+    let toggle_visibility_combo = hotkey::parse_combo(&settings.toggle_visibility_key);
+    if toggle_visibility_combo.is_none() {
+        log::warn!(
+            "Unsupported toggle_visibility_key: {}",
+            settings.toggle_visibility_key
+        );
+    }
+
+    let pause_resume_combo = if settings.pause_resume_key.is_empty() {
+        None
+    } else {
+        let combo = hotkey::parse_combo(&settings.pause_resume_key);
+        if combo.is_none() {
+            log::warn!(
+                "Unsupported pause_resume_key: {}",
+                settings.pause_resume_key
+            );
+        }
+        combo
+    };
+
+    let translate_toggle_combo = if settings.translate_toggle_key.is_empty() {
+        None
+    } else {
+        let combo = hotkey::parse_combo(&settings.translate_toggle_key);
+        if combo.is_none() {
+            log::warn!(
+                "Unsupported translate_toggle_key: {}",
+                settings.translate_toggle_key
+            );
+        }
+        combo
+    };
+
+    let push_to_talk_combo = hotkey::parse_combo(&settings.push_to_talk_key);
+    if push_to_talk_combo.is_none() {
+        log::warn!(
+            "Unsupported push_to_talk_key: {}",
+            settings.push_to_talk_key
+        );
+    }
+
+    let preset_combos: Vec<(String, hotkey::KeyCombo)> = settings
+        .hotkey_presets
+        .iter()
+        .filter_map(|preset| {
+            let combo = hotkey::parse_combo(&preset.key);
+            if combo.is_none() {
+                log::warn!(
+                    "Unsupported key for hotkey preset \"{}\": {}",
+                    preset.id,
+                    preset.key
+                );
+            }
+            combo.map(|combo| (preset.id.clone(), combo))
+        })
+        .collect();
+
+    let hotkey_status = hotkey::new_status();
+    if let Some(combo) = push_to_talk_combo.clone() {
+        hotkey::spawn_hotkey_listener(
+            hotkey_tx.clone(),
+            combo,
+            toggle_visibility_combo.clone(),
+            pause_resume_combo.clone(),
+            translate_toggle_combo.clone(),
+            preset_combos.clone(),
+            hotkey_status.clone(),
+        );
+    }
+
+    // Lets the settings panel ask for a fresh listener thread after the user
+    // grants Accessibility permission (macOS) following a failed self-test.
+    let (restart_hotkey_tx, mut restart_hotkey_rx) = tokio::sync::mpsc::channel::<()>(4);
+    {
+        let hotkey_status = hotkey_status.clone();
+        rt.spawn(async move {
+            while restart_hotkey_rx.recv().await.is_some() {
+                if let Some(combo) = push_to_talk_combo.clone() {
+                    hotkey::restart_hotkey_listener(
+                        hotkey_tx.clone(),
+                        combo,
+                        toggle_visibility_combo.clone(),
+                        pause_resume_combo.clone(),
+                        translate_toggle_combo.clone(),
+                        preset_combos.clone(),
+                        hotkey_status.clone(),
+                    );
+                }
+            }
+        });
+    }
+
+    audio_capture
+        .start_recording(audio_buffer)
+        .expect("Failed to start audio stream");
+
+    if cli::headless_mode() {
+        log::info!(
+            "Running headless — no widget window; control via the hotkey, \
+             control API, or IPC socket, status via logs"
+        );
+        // Keep `audio_capture` (and its cpal stream) alive for the process
+        // lifetime instead of dropping it the instant this block ends —
+        // `ThaiSttApp` normally does this by holding it as a field.
+        let _audio_capture = audio_capture;
+        rt.block_on(shutdown::wait_for_ctrl_c(
+            shared_settings,
+            channels.command_tx,
+        ));
+        rt.shutdown_timeout(std::time::Duration::from_secs(3));
+        return Ok(());
+    }
+
+    let app = ThaiSttApp::new(
+        shared_settings,
+        channels.command_tx,
+        channels.result_rx,
+        visibility_rx,
+        input_level,
+        audio_capture,
+        hotkey_status,
+        restart_hotkey_tx,
+    );
+
+    let result = eframe::run_native(
+        "Thai STT",
+        native_options(&settings),
+        Box::new(|cc| {
+            shutdown::spawn_signal_listener(cc.egui_ctx.clone());
+            Ok(Box::new(app))
+        }),
+    );
+
+    // Give the orchestrator, control server, and other background tasks a
+    // bounded window to finish whatever `ThaiSttApp::on_exit` triggered
+    // (cancelling in-flight work, etc) instead of the process tearing them
+    // down mid-task the instant `main` returns.
+    rt.shutdown_timeout(std::time::Duration::from_secs(3));
+    result
+}
@@ -0,0 +1,1245 @@
+//! Orchestrates the audio → STT → LLM → inject pipeline and owns the shared
+//! settings/audio-buffer state that the UI and background threads coordinate through.
+
+pub mod recovery;
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use parking_lot::RwLock;
+use tokio::sync::mpsc;
+
+use crate::audio::{
+    compute_waveform, AudioValidator, RecordingStore, SharedAudioBuffer, SharedPreroll, VadEngine,
+};
+use crate::commands::CommandProcessor;
+use crate::config::{AppSettings, OperatingMode};
+use crate::history::{HistoryEntry, HistoryStore};
+use crate::inject::{self, FocusedWindow, TextInjector};
+use crate::llm::{LlmCorrector, ProfileContextManager};
+use crate::stt::{
+    self, ModelManager, Segment, SttEngine, TranscriptionResult as EngineTranscriptionResult,
+};
+
+pub type SharedSettings = Arc<RwLock<AppSettings>>;
+
+/// Commands from UI → Pipeline.
+pub enum PipelineCommand {
+    StartRecording,
+    StopRecording,
+    /// Stop appending captured audio to the buffer without finalizing it —
+    /// `StopRecording` still transcribes everything captured before the
+    /// pause plus anything captured after a following `ResumeRecording`.
+    PauseRecording,
+    ResumeRecording,
+    Cancel,
+    ChangeMode(OperatingMode),
+    /// Flip `AppSettings.translate_to_english`, fired by the
+    /// translate-toggle hotkey.
+    ToggleTranslate,
+    /// Re-read `settings.toml` from disk and apply it to the running
+    /// pipeline without restarting — issued after the settings panel saves.
+    ReloadConfig,
+    /// Re-inject a past transcription picked from the history panel,
+    /// bypassing STT/LLM entirely.
+    InjectText(String),
+    /// Load a different registered STT model and swap it in via
+    /// `ModelManager`, without restarting the app or blocking the UI thread.
+    SwitchModel(String),
+    /// Fired by a `HotkeyEvent::PresetPushToTalkPressed` just before its
+    /// paired `StartRecording` — looks the id up in
+    /// `AppSettings::hotkey_presets` and applies whichever
+    /// mode/model/language/translate/prompt fields it overrides, the same
+    /// way `ChangeMode`/`SwitchModel` do individually. A model override
+    /// hot-swaps via `ModelManager`, same as `SwitchModel`.
+    ApplyPreset(String),
+    /// The user edited a correction result in the UI; diff `corrected`
+    /// against `original` and learn the changed words as `UserVocabulary`.
+    LearnCorrection {
+        original: String,
+        corrected: String,
+    },
+    /// Ask each configured LLM provider (primary + fallback chain) whether
+    /// it's currently reachable, for display in the settings panel.
+    CheckLlmProviders,
+    /// Ask the LLM correction cache for its hit/miss counters, for display
+    /// in the stats panel.
+    CheckCacheStats,
+    /// Ask GitHub for the latest release and compare it against the
+    /// running version. See `updater`.
+    CheckForUpdate,
+    /// Download the asset from a previously-reported
+    /// `updater::UpdateInfo::download_url`.
+    DownloadUpdate(crate::updater::UpdateInfo),
+    /// Fired by `power::spawn_power_monitor` on every AC/battery
+    /// transition. Under `power_aware_mode`, drops to
+    /// `power_saver_model`/`OperatingMode::Fast` on `true`, restoring the
+    /// configured model/mode on `false`.
+    PowerSourceChanged(bool),
+}
+
+/// Results from Pipeline → UI.
+pub enum PipelineResult {
+    RecordingStarted,
+    RecordingPaused,
+    RecordingResumed,
+    RecordingStopped {
+        duration_secs: f32,
+    },
+    /// Live preview of the transcription while the user is still holding
+    /// push-to-talk. Superseded by `TranscriptionComplete` on release.
+    PartialTranscription(String),
+    /// RMS bars over the tail of the in-progress recording, for the
+    /// live waveform display. Superseded on the next tick.
+    WaveformUpdate(Vec<f32>),
+    TranscriptionComplete(TranscriptionResult),
+    /// A growing prefix of the LLM correction, sent as pieces arrive from a
+    /// streaming-capable corrector. Superseded by `CorrectionComplete`.
+    CorrectionPartial {
+        text: String,
+    },
+    CorrectionComplete(CorrectionResult),
+    /// Sent instead of auto-injecting when `AppSettings::auto_inject` is
+    /// false. Carries the fully post-processed text (commands already
+    /// applied) so a later `PipelineCommand::InjectText` injects exactly
+    /// what automatic injection would have.
+    InjectionPending(String),
+    InjectionComplete,
+    ConfigReloaded,
+    /// In-flight STT/LLM work was aborted by `PipelineCommand::Cancel`.
+    Cancelled,
+    /// Sent once, right after the pipeline starts, reporting which backend
+    /// actually loaded the STT model (`use_gpu` may have fallen back to CPU).
+    EngineReady {
+        stt_backend: String,
+    },
+    /// Reachability of each configured LLM provider, in failover order.
+    LlmProviderStatus(Vec<(String, bool)>),
+    /// Result of `PipelineCommand::CheckCacheStats`.
+    CacheStats(crate::llm::CorrectionCacheStats),
+    /// Result of `PipelineCommand::CheckForUpdate`.
+    UpdateStatus(crate::updater::UpdateStatus),
+    /// The configured daily token budget was hit; `operating_mode` has been
+    /// switched to `Fast` and stays there until changed again (it is not
+    /// restored when the day rolls over).
+    LlmBudgetExceeded,
+    Error(PipelineError),
+}
+
+/// How often the streaming preview re-transcribes the in-progress recording.
+const STREAMING_INTERVAL: std::time::Duration = std::time::Duration::from_millis(2000);
+/// Minimum samples (0.5s at 16kHz) before attempting a streaming pass.
+const STREAMING_MIN_SAMPLES: usize = 8_000;
+/// How often the waveform display refreshes while recording.
+const WAVEFORM_INTERVAL: std::time::Duration = std::time::Duration::from_millis(33);
+/// Number of bars shown in the live waveform.
+const WAVEFORM_BARS: usize = 24;
+/// Only the most recent second of audio is used for the waveform, so the
+/// bars track the live signal instead of averaging over the whole recording.
+const WAVEFORM_WINDOW_SAMPLES: usize = 16_000;
+/// How often `wait_for_cancel` re-checks the cancellation flag.
+const CANCEL_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+/// Recordings longer than this are split into silence-boundary chunks before
+/// transcription — Whisper's own training window, and the point beyond
+/// which a single pass reliably starts dropping or duplicating text.
+const CHUNK_THRESHOLD_SECS: u64 = 30;
+
+/// A recording that finished capturing and is waiting for its turn to go
+/// through STT/LLM/inject. Queued by `StopRecording` so the next
+/// `StartRecording` can begin immediately instead of waiting for the
+/// previous utterance's pipeline run to finish.
+struct QueuedUtterance {
+    audio: Vec<f32>,
+    /// Captured before denoising/VAD trimming so the length heuristic in
+    /// `AppSettings::effective_operating_mode_for_duration` sees how long the
+    /// user actually held push-to-talk, not how much speech survived
+    /// silence trimming.
+    duration_secs: f32,
+    recording_path: Option<PathBuf>,
+    focus: Option<FocusedWindow>,
+    persist_enabled: bool,
+    log_transcripts: bool,
+    /// This utterance's own cancel flag, captured from
+    /// `PipelineOrchestrator::active_cancel` at `StartRecording` time (see
+    /// its doc comment) — not the orchestrator-wide flag, so a later
+    /// recording arming a fresh flag for itself can't un-cancel this one
+    /// while it's still being processed.
+    cancel: Arc<AtomicBool>,
+}
+
+/// Swaps `slot` for a brand-new, unset cancel flag, arming the next
+/// recording without touching whatever flag is already inside — extracted
+/// out of `PipelineCommand::StartRecording` so the swap-not-mutate
+/// invariant it depends on (see `PipelineOrchestrator::active_cancel`'s
+/// doc comment) can be exercised directly in a test.
+fn arm_fresh_cancel_flag(slot: &Mutex<Arc<AtomicBool>>) {
+    *slot.lock().unwrap() = Arc::new(AtomicBool::new(false));
+}
+
+pub struct TranscriptionResult {
+    pub raw_text: String,
+    pub duration_ms: u128,
+    /// Per-segment text/timing/confidence, for highlighting low-confidence
+    /// words in the UI before LLM correction potentially masks them.
+    pub segments: Vec<Segment>,
+}
+
+pub struct CorrectionResult {
+    pub corrected_text: String,
+    pub duration_ms: u128,
+}
+
+#[derive(Debug)]
+pub enum PipelineError {
+    Audio(String),
+    Stt(String),
+    Llm(String),
+    Inject(String),
+    Internal(String),
+}
+
+/// Seconds since the Unix epoch, for timestamping history entries, webhook
+/// payloads, and recovery checkpoints.
+fn unix_now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Resolves once `cancel` is set, for racing against cancelable work with
+/// `tokio::select!`.
+async fn wait_for_cancel(cancel: &AtomicBool) {
+    while !cancel.load(Ordering::Relaxed) {
+        tokio::time::sleep(CANCEL_POLL_INTERVAL).await;
+    }
+}
+
+pub struct Channels {
+    pub command_tx: mpsc::Sender<PipelineCommand>,
+    pub command_rx: mpsc::Receiver<PipelineCommand>,
+    pub result_tx: mpsc::Sender<PipelineResult>,
+    pub result_rx: mpsc::Receiver<PipelineResult>,
+}
+
+impl Channels {
+    pub fn new() -> Self {
+        let (command_tx, command_rx) = mpsc::channel(16);
+        let (result_tx, result_rx) = mpsc::channel(16);
+        Self {
+            command_tx,
+            command_rx,
+            result_tx,
+            result_rx,
+        }
+    }
+}
+
+impl Default for Channels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct PipelineOrchestrator {
+    audio_buffer: SharedAudioBuffer,
+    stt_engine: Arc<ModelManager>,
+    /// Built once at startup from `AppSettings::stt_remote_url`/
+    /// `stt_remote_api_key`, like `llm_corrector`. Its URL and key don't
+    /// hot-reload — see the note on `PipelineCommand::ReloadConfig` below.
+    remote_stt: Arc<stt::RemoteSttEngine>,
+    /// Built once at startup from `AppSettings::stt_vosk_url`, same
+    /// restart-required caveat as `remote_stt`.
+    vosk_stt: Arc<stt::VoskEngine>,
+    llm_corrector: Arc<dyn LlmCorrector>,
+    vad: Arc<dyn VadEngine>,
+    text_injector: Arc<dyn TextInjector>,
+    context_manager: Mutex<ProfileContextManager>,
+    history: HistoryStore,
+    commands: CommandProcessor,
+    snippets: crate::text::snippets::SnippetExpander,
+    profanity: crate::text::ProfanityFilter,
+    formatting: crate::text::formatting::FormattingEngine,
+    recordings: RecordingStore,
+    settings: SharedSettings,
+    /// The cancel flag for whatever's currently live: the recording in
+    /// progress, or — once `drain_recording` queues it — the utterance the
+    /// worker is processing. `StartRecording` swaps in a brand-new `Arc` for
+    /// the recording it's about to start rather than resetting the existing
+    /// one, so an older queued utterance that already captured the previous
+    /// `Arc` into its own `QueuedUtterance::cancel` is unaffected — without
+    /// this, `PipelineCommand::Cancel` on utterance A followed immediately
+    /// by a new `StartRecording` for utterance B would reset the shared
+    /// flag back to `false` before A's STT/LLM pass ever observed it,
+    /// silently un-cancelling A. `PipelineCommand::Cancel` sets whatever
+    /// `Arc` is current here, which is A's own flag right up until B's
+    /// `StartRecording` replaces it.
+    active_cancel: Mutex<Arc<AtomicBool>>,
+    /// Foreground window captured at `StartRecording`, taken by
+    /// `drain_recording` and carried through the utterance queue so a focus
+    /// switch during a later recording doesn't paste into the wrong app.
+    recording_focus: Mutex<Option<FocusedWindow>>,
+    /// Continuously-filled pre-roll ring buffer, prepended to the recording
+    /// on `StartRecording` so word onsets before the hotkey press aren't
+    /// clipped.
+    preroll: SharedPreroll,
+    /// Mode/model in effect right before `PowerSourceChanged(true)` forced
+    /// battery-saver settings, restored on the matching `false`. `None`
+    /// while on AC (or power-aware mode never having kicked in).
+    pre_battery_state: Mutex<Option<(OperatingMode, String)>>,
+    /// Sending half of the utterance queue; `StopRecording` pushes onto it
+    /// instead of running STT/LLM/inject inline, so back-to-back dictation
+    /// isn't blocked on the previous utterance's pipeline run.
+    utterance_tx: mpsc::Sender<QueuedUtterance>,
+    /// Taken by `run` and handed to the queue-draining worker task. `None`
+    /// afterwards — `run` only runs once per orchestrator.
+    utterance_rx: Mutex<Option<mpsc::Receiver<QueuedUtterance>>>,
+}
+
+impl PipelineOrchestrator {
+    pub fn new(
+        audio_buffer: SharedAudioBuffer,
+        stt_engine: Arc<ModelManager>,
+        remote_stt: Arc<stt::RemoteSttEngine>,
+        vosk_stt: Arc<stt::VoskEngine>,
+        llm_corrector: Arc<dyn LlmCorrector>,
+        vad: Arc<dyn VadEngine>,
+        text_injector: Arc<dyn TextInjector>,
+        settings: SharedSettings,
+        preroll: SharedPreroll,
+    ) -> Self {
+        let (recordings, profiles) = {
+            let s = settings.read();
+            (
+                RecordingStore::new(s.recordings_max_files, s.recordings_max_mb),
+                s.profiles.clone(),
+            )
+        };
+        let (utterance_tx, utterance_rx) = mpsc::channel(8);
+        Self {
+            audio_buffer,
+            stt_engine,
+            remote_stt,
+            vosk_stt,
+            llm_corrector,
+            vad,
+            text_injector,
+            context_manager: Mutex::new(ProfileContextManager::new(profiles)),
+            history: HistoryStore::default(),
+            commands: CommandProcessor::default(),
+            snippets: crate::text::snippets::SnippetExpander::default(),
+            profanity: crate::text::ProfanityFilter::default(),
+            formatting: crate::text::formatting::FormattingEngine::default(),
+            recordings,
+            settings,
+            active_cancel: Mutex::new(Arc::new(AtomicBool::new(false))),
+            recording_focus: Mutex::new(None),
+            preroll,
+            pre_battery_state: Mutex::new(None),
+            utterance_tx,
+            utterance_rx: Mutex::new(Some(utterance_rx)),
+        }
+    }
+
+    /// Which engine should serve the next transcription. Status reporting
+    /// (`EngineReady`, `SwitchModel`, `PowerSourceChanged`) still describes
+    /// `stt_engine`'s local backend regardless of this toggle — an offloaded
+    /// backend only affects where the actual decode happens. Vosk takes
+    /// priority if both it and the whisper.cpp/OpenAI-compatible remote
+    /// backend are enabled at once (`validate()` doesn't forbid that
+    /// combination, since either alone is a valid config).
+    fn active_stt(&self) -> Arc<dyn SttEngine> {
+        let settings = self.settings.read();
+        if settings.stt_vosk_enabled {
+            self.vosk_stt.clone() as Arc<dyn SttEngine>
+        } else if settings.stt_remote_enabled {
+            self.remote_stt.clone() as Arc<dyn SttEngine>
+        } else {
+            self.stt_engine.current() as Arc<dyn SttEngine>
+        }
+    }
+
+    pub async fn run(
+        self,
+        mut command_rx: mpsc::Receiver<PipelineCommand>,
+        result_tx: mpsc::Sender<PipelineResult>,
+    ) {
+        let _ = result_tx
+            .send(PipelineResult::EngineReady {
+                stt_backend: format!("{:?}", self.stt_engine.current().active_backend()),
+            })
+            .await;
+
+        let this = Arc::new(self);
+
+        // Drains queued utterances one at a time, so recordings finish
+        // STT/LLM/inject in the order they were spoken even though the
+        // command loop below no longer waits for that to happen before
+        // accepting the next `StartRecording`.
+        let mut utterance_rx = this
+            .utterance_rx
+            .lock()
+            .unwrap()
+            .take()
+            .expect("run() only called once");
+        let worker = this.clone();
+        let worker_result_tx = result_tx.clone();
+        tokio::spawn(async move {
+            while let Some(utterance) = utterance_rx.recv().await {
+                worker.process_utterance(utterance, &worker_result_tx).await;
+            }
+        });
+
+        while let Some(cmd) = command_rx.recv().await {
+            match cmd {
+                PipelineCommand::StartRecording => {
+                    arm_fresh_cancel_flag(&this.active_cancel);
+                    *this.recording_focus.lock().unwrap() = inject::active_window();
+                    let preroll_audio = this.preroll.lock().unwrap().snapshot();
+                    {
+                        let mut buf = this.audio_buffer.lock().unwrap();
+                        buf.clear();
+                        buf.is_recording = true;
+                        buf.push_samples(&preroll_audio);
+                    }
+                    let _ = result_tx.send(PipelineResult::RecordingStarted).await;
+                    this.spawn_streaming_preview(result_tx.clone());
+                    this.spawn_waveform_updates(result_tx.clone());
+                }
+
+                PipelineCommand::StopRecording => {
+                    this.drain_recording(&result_tx).await;
+                }
+
+                PipelineCommand::PauseRecording => {
+                    this.audio_buffer.lock().unwrap().is_paused = true;
+                    let _ = result_tx.send(PipelineResult::RecordingPaused).await;
+                }
+
+                PipelineCommand::ResumeRecording => {
+                    this.audio_buffer.lock().unwrap().is_paused = false;
+                    let _ = result_tx.send(PipelineResult::RecordingResumed).await;
+                }
+
+                PipelineCommand::Cancel => {
+                    this.active_cancel
+                        .lock()
+                        .unwrap()
+                        .store(true, Ordering::Relaxed);
+                    {
+                        let mut buf = this.audio_buffer.lock().unwrap();
+                        buf.is_recording = false;
+                        buf.clear();
+                    }
+                    let _ = result_tx.send(PipelineResult::Cancelled).await;
+                }
+
+                PipelineCommand::ChangeMode(mode) => {
+                    this.settings.write().operating_mode = mode;
+                }
+
+                PipelineCommand::ToggleTranslate => {
+                    let mut settings = this.settings.write();
+                    settings.translate_to_english = !settings.translate_to_english;
+                }
+
+                PipelineCommand::ReloadConfig => {
+                    // Note: this refreshes mode/temperature/hotkey/etc, all of
+                    // which are read fresh from `settings` on each pipeline
+                    // run. The STT model hot-swaps via `SwitchModel`; the LLM
+                    // corrector's base URL, the remote/Vosk STT servers'
+                    // URLs, and the injection method still require a
+                    // restart until hot-swap lands for those too. Toggling
+                    // `stt_remote_enabled`/`stt_vosk_enabled` themselves do
+                    // take effect immediately, since `active_stt` reads
+                    // them fresh.
+                    let reloaded = AppSettings::load();
+                    let issues = reloaded.validate();
+                    if issues.is_empty() {
+                        *this.settings.write() = reloaded;
+                        let _ = result_tx.send(PipelineResult::ConfigReloaded).await;
+                    } else {
+                        let msg = issues
+                            .iter()
+                            .map(|issue| issue.to_string())
+                            .collect::<Vec<_>>()
+                            .join("; ");
+                        let _ = result_tx
+                            .send(PipelineResult::Error(PipelineError::Internal(msg)))
+                            .await;
+                    }
+                }
+
+                PipelineCommand::InjectText(text) => {
+                    let injector = this.text_injector.clone();
+                    let inject_result =
+                        tokio::task::spawn_blocking(move || injector.inject(&text, None)).await;
+                    match inject_result {
+                        Ok(Ok(())) => {
+                            recovery::clear();
+                            let _ = result_tx.send(PipelineResult::InjectionComplete).await;
+                        }
+                        Ok(Err(e)) => {
+                            let _ = result_tx
+                                .send(PipelineResult::Error(PipelineError::Inject(e.to_string())))
+                                .await;
+                        }
+                        Err(e) => {
+                            let _ = result_tx
+                                .send(PipelineResult::Error(PipelineError::Internal(
+                                    e.to_string(),
+                                )))
+                                .await;
+                        }
+                    }
+                }
+
+                PipelineCommand::SwitchModel(model_id) => {
+                    let manager = this.stt_engine.clone();
+                    let use_gpu = this.settings.read().use_gpu;
+                    let params = stt::TranscribeParams::from_settings(&this.settings.read());
+                    let model_id_for_load = model_id.clone();
+                    let switch_result = tokio::task::spawn_blocking(move || {
+                        manager.switch(&model_id_for_load, use_gpu, params)
+                    })
+                    .await;
+
+                    match switch_result {
+                        Ok(Ok(())) => {
+                            this.settings.write().stt_model = model_id;
+                            let _ = result_tx
+                                .send(PipelineResult::EngineReady {
+                                    stt_backend: format!(
+                                        "{:?}",
+                                        this.stt_engine.current().active_backend()
+                                    ),
+                                })
+                                .await;
+                        }
+                        Ok(Err(e)) => {
+                            let _ = result_tx
+                                .send(PipelineResult::Error(PipelineError::Stt(e.to_string())))
+                                .await;
+                        }
+                        Err(e) => {
+                            let _ = result_tx
+                                .send(PipelineResult::Error(PipelineError::Internal(
+                                    e.to_string(),
+                                )))
+                                .await;
+                        }
+                    }
+                }
+
+                PipelineCommand::ApplyPreset(id) => {
+                    let preset = this
+                        .settings
+                        .read()
+                        .hotkey_presets
+                        .iter()
+                        .find(|p| p.id == id)
+                        .cloned();
+                    let Some(preset) = preset else {
+                        log::warn!("Unknown hotkey preset id: {}", id);
+                        continue;
+                    };
+
+                    if let Some(mode) = preset.operating_mode {
+                        this.settings.write().operating_mode = mode;
+                    }
+                    if let Some(language) = preset.stt_language {
+                        this.settings.write().stt_language = language;
+                    }
+                    if let Some(translate) = preset.translate_to_english {
+                        this.settings.write().translate_to_english = translate;
+                    }
+                    if let Some(style) = preset.llm_correction_style {
+                        this.settings.write().llm_correction_style = style;
+                    }
+
+                    if let Some(model) = preset.stt_model {
+                        if model == this.settings.read().stt_model {
+                            continue;
+                        }
+                        let manager = this.stt_engine.clone();
+                        let use_gpu = this.settings.read().use_gpu;
+                        let params = stt::TranscribeParams::from_settings(&this.settings.read());
+                        let model_for_load = model.clone();
+                        let switch_result = tokio::task::spawn_blocking(move || {
+                            manager.switch(&model_for_load, use_gpu, params)
+                        })
+                        .await;
+
+                        match switch_result {
+                            Ok(Ok(())) => {
+                                this.settings.write().stt_model = model;
+                                let _ = result_tx
+                                    .send(PipelineResult::EngineReady {
+                                        stt_backend: format!(
+                                            "{:?}",
+                                            this.stt_engine.current().active_backend()
+                                        ),
+                                    })
+                                    .await;
+                            }
+                            Ok(Err(e)) => {
+                                let _ = result_tx
+                                    .send(PipelineResult::Error(PipelineError::Stt(e.to_string())))
+                                    .await;
+                            }
+                            Err(e) => {
+                                let _ = result_tx
+                                    .send(PipelineResult::Error(PipelineError::Internal(
+                                        e.to_string(),
+                                    )))
+                                    .await;
+                            }
+                        }
+                    }
+                }
+
+                PipelineCommand::LearnCorrection {
+                    original,
+                    corrected,
+                } => {
+                    if this.settings.read().persist_enabled() {
+                        this.context_manager
+                            .lock()
+                            .unwrap()
+                            .active()
+                            .learn_correction(&original, &corrected);
+                    }
+                }
+
+                PipelineCommand::CheckLlmProviders => {
+                    if this.settings.read().privacy_mode {
+                        let _ = result_tx
+                            .send(PipelineResult::LlmProviderStatus(Vec::new()))
+                            .await;
+                    } else {
+                        let statuses = this.llm_corrector.provider_status().await;
+                        let _ = result_tx
+                            .send(PipelineResult::LlmProviderStatus(statuses))
+                            .await;
+                    }
+                }
+
+                PipelineCommand::CheckCacheStats => {
+                    let stats = this.llm_corrector.cache_stats().await.unwrap_or_default();
+                    let _ = result_tx.send(PipelineResult::CacheStats(stats)).await;
+                }
+
+                PipelineCommand::CheckForUpdate => {
+                    let _ = result_tx
+                        .send(PipelineResult::UpdateStatus(
+                            crate::updater::UpdateStatus::Checking,
+                        ))
+                        .await;
+                    let status =
+                        match crate::updater::check_for_update(env!("CARGO_PKG_VERSION")).await {
+                            Ok(Some(info)) => crate::updater::UpdateStatus::Available(info),
+                            Ok(None) => crate::updater::UpdateStatus::UpToDate,
+                            Err(e) => crate::updater::UpdateStatus::Error(e),
+                        };
+                    let _ = result_tx.send(PipelineResult::UpdateStatus(status)).await;
+                }
+
+                PipelineCommand::DownloadUpdate(info) => {
+                    let _ = result_tx
+                        .send(PipelineResult::UpdateStatus(
+                            crate::updater::UpdateStatus::Downloading,
+                        ))
+                        .await;
+                    let dest = crate::config::AppPaths::update_download_path(&info.version);
+                    if let Some(parent) = dest.parent() {
+                        let _ = tokio::fs::create_dir_all(parent).await;
+                    }
+                    let status = match crate::updater::download_update(&info, &dest).await {
+                        Ok(()) => crate::updater::UpdateStatus::Downloaded(dest),
+                        Err(e) => crate::updater::UpdateStatus::Error(e),
+                    };
+                    let _ = result_tx.send(PipelineResult::UpdateStatus(status)).await;
+                }
+
+                PipelineCommand::PowerSourceChanged(on_battery) => {
+                    if !this.settings.read().power_aware_mode {
+                        continue;
+                    }
+
+                    let target = if on_battery {
+                        if this.pre_battery_state.lock().unwrap().is_some() {
+                            // Already in battery-saver mode; nothing to do.
+                            continue;
+                        }
+                        let (mode, model, saver_model) = {
+                            let settings = this.settings.read();
+                            (
+                                settings.operating_mode,
+                                settings.stt_model.clone(),
+                                settings.power_saver_model.clone(),
+                            )
+                        };
+                        *this.pre_battery_state.lock().unwrap() = Some((mode, model));
+                        this.settings.write().operating_mode = OperatingMode::Fast;
+                        saver_model
+                    } else {
+                        let Some((mode, model)) = this.pre_battery_state.lock().unwrap().take()
+                        else {
+                            continue;
+                        };
+                        this.settings.write().operating_mode = mode;
+                        model
+                    };
+
+                    if target == this.settings.read().stt_model {
+                        continue;
+                    }
+                    let manager = this.stt_engine.clone();
+                    let use_gpu = this.settings.read().use_gpu;
+                    let params = stt::TranscribeParams::from_settings(&this.settings.read());
+                    let target_for_load = target.clone();
+                    let switch_result = tokio::task::spawn_blocking(move || {
+                        manager.switch(&target_for_load, use_gpu, params)
+                    })
+                    .await;
+                    match switch_result {
+                        Ok(Ok(())) => {
+                            this.settings.write().stt_model = target;
+                            let _ = result_tx
+                                .send(PipelineResult::EngineReady {
+                                    stt_backend: format!(
+                                        "{:?}",
+                                        this.stt_engine.current().active_backend()
+                                    ),
+                                })
+                                .await;
+                        }
+                        Ok(Err(e)) => {
+                            let _ = result_tx
+                                .send(PipelineResult::Error(PipelineError::Stt(e.to_string())))
+                                .await;
+                        }
+                        Err(e) => {
+                            let _ = result_tx
+                                .send(PipelineResult::Error(PipelineError::Internal(
+                                    e.to_string(),
+                                )))
+                                .await;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// While the user holds push-to-talk, periodically re-transcribe the
+    /// growing audio buffer and emit `PartialTranscription` previews. Stops
+    /// on its own once `AudioBuffer::is_recording` flips back to false.
+    fn spawn_streaming_preview(&self, result_tx: mpsc::Sender<PipelineResult>) {
+        let audio_buffer = self.audio_buffer.clone();
+        let model_manager = self.stt_engine.clone();
+        let language = self.settings.read().stt_language.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(STREAMING_INTERVAL).await;
+
+                let (snapshot, still_recording) = {
+                    let buf = audio_buffer.lock().unwrap();
+                    (buf.snapshot(), buf.is_recording)
+                };
+                if !still_recording {
+                    break;
+                }
+                if snapshot.len() < STREAMING_MIN_SAMPLES {
+                    continue;
+                }
+
+                let stt = model_manager.current();
+                let language = language.clone();
+                let partial = tokio::task::spawn_blocking(move || {
+                    stt.transcribe_streaming(&snapshot, &language)
+                })
+                .await;
+
+                if let Ok(Ok(result)) = partial {
+                    if result_tx
+                        .send(PipelineResult::PartialTranscription(result.text))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    /// While the user holds push-to-talk, sample the tail of the audio
+    /// buffer on a short timer and push waveform bars for the recording
+    /// view to animate. Stops on its own once recording ends.
+    fn spawn_waveform_updates(&self, result_tx: mpsc::Sender<PipelineResult>) {
+        let audio_buffer = self.audio_buffer.clone();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(WAVEFORM_INTERVAL).await;
+
+                let (snapshot, still_recording) = {
+                    let buf = audio_buffer.lock().unwrap();
+                    (buf.snapshot(), buf.is_recording)
+                };
+                if !still_recording {
+                    break;
+                }
+
+                let tail_start = snapshot.len().saturating_sub(WAVEFORM_WINDOW_SAMPLES);
+                let bars = compute_waveform(&snapshot[tail_start..], WAVEFORM_BARS);
+                if result_tx
+                    .send(PipelineResult::WaveformUpdate(bars))
+                    .await
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Finalizes the just-stopped recording (saves it, if enabled) and hands
+    /// it off to the utterance queue, so the caller's command loop can
+    /// accept the next `StartRecording` without waiting for STT/LLM/inject.
+    async fn drain_recording(&self, result_tx: &mpsc::Sender<PipelineResult>) {
+        let persist_enabled = self.settings.read().persist_enabled();
+        let log_transcripts = self.settings.read().log_transcripts && persist_enabled;
+        let audio = {
+            let mut buf = self.audio_buffer.lock().unwrap();
+            buf.is_recording = false;
+            buf.drain()
+        };
+        let focus = self.recording_focus.lock().unwrap().take();
+
+        let duration = audio.len() as f32 / 16_000.0;
+        let _ = result_tx
+            .send(PipelineResult::RecordingStopped {
+                duration_secs: duration,
+            })
+            .await;
+
+        let recording_path = if persist_enabled && self.settings.read().save_recordings {
+            match self.recordings.save(&audio) {
+                Ok(path) => Some(path),
+                Err(e) => {
+                    log::warn!("Failed to save recording: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let utterance = QueuedUtterance {
+            audio,
+            duration_secs: duration,
+            recording_path,
+            focus,
+            persist_enabled,
+            log_transcripts,
+            cancel: self.active_cancel.lock().unwrap().clone(),
+        };
+        if self.utterance_tx.send(utterance).await.is_err() {
+            log::warn!("utterance queue worker is gone, dropping recording");
+        }
+    }
+
+    /// Runs STT/LLM/inject for one queued utterance. Called only from the
+    /// single worker task spawned in `run`, so utterances are always
+    /// processed — and injected — in the order they were recorded.
+    async fn process_utterance(
+        &self,
+        utterance: QueuedUtterance,
+        result_tx: &mpsc::Sender<PipelineResult>,
+    ) {
+        let utterance_start = std::time::Instant::now();
+        let QueuedUtterance {
+            audio,
+            duration_secs,
+            recording_path,
+            focus,
+            persist_enabled,
+            log_transcripts,
+            cancel,
+        } = utterance;
+
+        let language = self.settings.read().stt_language.clone();
+        let translate_to_english = self.settings.read().translate_to_english;
+        let audio = if self.settings.read().noise_suppression {
+            crate::audio::denoise(&audio)
+        } else {
+            audio
+        };
+        let audio_clone = self.vad.trim_silence(&audio).to_vec();
+
+        if let Err(e) = AudioValidator::validate(&audio_clone) {
+            let _ = result_tx
+                .send(PipelineResult::Error(PipelineError::Audio(
+                    e.localized_message(&language),
+                )))
+                .await;
+            return;
+        }
+
+        let target_context = {
+            let settings = self.settings.read();
+            if settings.target_context_enabled && !settings.privacy_mode {
+                inject::read_recent_lines(settings.target_context_lines)
+            } else {
+                None
+            }
+        };
+        let mut context_manager = self.context_manager.lock().unwrap();
+        let profile_name = context_manager.active_profile_name();
+        let domain_override = {
+            let settings = self.settings.read();
+            settings
+                .profiles
+                .iter()
+                .find(|p| p.name == profile_name)
+                .and_then(|p| p.domain_override.clone())
+                .or_else(|| settings.domain_override.clone())
+        };
+        let context = context_manager
+            .active()
+            .build_context(target_context, domain_override.as_ref());
+        drop(context_manager);
+        let initial_prompt = context.initial_prompt();
+
+        let stt = self.active_stt();
+        let stt_cancel = cancel.clone();
+        let stt_timeout = std::time::Duration::from_secs(self.settings.read().stt_timeout_secs);
+        let stt_task = tokio::task::spawn_blocking(move || {
+            if audio_clone.len() > CHUNK_THRESHOLD_SECS as usize * 16_000 {
+                stt::transcribe_chunked_parallel(
+                    &audio_clone,
+                    CHUNK_THRESHOLD_SECS,
+                    stt::default_worker_count(),
+                    |chunk| {
+                        stt.transcribe_primed(
+                            chunk,
+                            &language,
+                            initial_prompt.as_deref(),
+                            translate_to_english,
+                            &stt_cancel,
+                        )
+                    },
+                )
+            } else {
+                stt.transcribe_primed(
+                    &audio_clone,
+                    &language,
+                    initial_prompt.as_deref(),
+                    translate_to_english,
+                    &stt_cancel,
+                )
+            }
+        });
+        let stt_result: Result<anyhow::Result<EngineTranscriptionResult>, tokio::task::JoinError> =
+            match tokio::time::timeout(stt_timeout, stt_task).await {
+                Ok(joined) => joined,
+                Err(_) => {
+                    // The blocking task keeps running until the Whisper abort
+                    // callback next checks `cancel`, then finishes and its
+                    // result is simply dropped — there's no way to actually
+                    // kill a `spawn_blocking` task early.
+                    cancel.store(true, Ordering::Relaxed);
+                    let _ = result_tx
+                        .send(PipelineResult::Error(PipelineError::Stt(format!(
+                            "Transcription timed out after {}s",
+                            stt_timeout.as_secs()
+                        ))))
+                        .await;
+                    return;
+                }
+            };
+
+        if cancel.load(Ordering::Relaxed) {
+            let _ = result_tx.send(PipelineResult::Cancelled).await;
+            return;
+        }
+
+        let mut result = match stt_result {
+            Ok(Ok(r)) => r,
+            Ok(Err(e)) => {
+                let _ = result_tx
+                    .send(PipelineResult::Error(PipelineError::Stt(e.to_string())))
+                    .await;
+                return;
+            }
+            Err(e) => {
+                let _ = result_tx
+                    .send(PipelineResult::Error(PipelineError::Internal(
+                        e.to_string(),
+                    )))
+                    .await;
+                return;
+            }
+        };
+        if self.settings.read().stt_diarization_enabled {
+            stt::diarize::diarize(&audio_clone, &mut result.segments);
+        }
+
+        let raw_text = self
+            .context_manager
+            .lock()
+            .unwrap()
+            .active()
+            .apply_vocabulary(&result.text);
+        log::info!(
+            "stt stage complete in {}ms: {}",
+            result.duration_ms,
+            crate::logging::redact_transcript(&raw_text, log_transcripts)
+        );
+        let _ = result_tx
+            .send(PipelineResult::TranscriptionComplete(TranscriptionResult {
+                raw_text: raw_text.clone(),
+                duration_ms: result.duration_ms,
+                segments: result.segments.clone(),
+            }))
+            .await;
+        if persist_enabled {
+            recovery::save(&recovery::RecoveryState {
+                raw_text: raw_text.clone(),
+                corrected_text: None,
+                final_text: None,
+                timestamp_secs: unix_now_secs(),
+            });
+        }
+
+        let mut mode = self
+            .settings
+            .read()
+            .effective_operating_mode_for_duration(duration_secs);
+        if translate_to_english {
+            // The correction prompts are Thai-specific; Whisper's translate
+            // task already produced final English text, so there's nothing
+            // for the LLM pass to do.
+            mode = OperatingMode::Fast;
+        }
+        if mode != OperatingMode::Fast && self.llm_corrector.budget_exceeded().await {
+            self.settings.write().operating_mode = OperatingMode::Fast;
+            mode = OperatingMode::Fast;
+            let _ = result_tx.send(PipelineResult::LlmBudgetExceeded).await;
+        }
+        let mut corrected_text: Option<String> = None;
+        let final_text = if mode != OperatingMode::Fast {
+            let (partial_tx, mut partial_rx) = mpsc::channel::<String>(16);
+            let forward_tx = result_tx.clone();
+            let forward_task = tokio::spawn(async move {
+                while let Some(text) = partial_rx.recv().await {
+                    let _ = forward_tx
+                        .send(PipelineResult::CorrectionPartial { text })
+                        .await;
+                }
+            });
+
+            let correction_start = std::time::Instant::now();
+            let correction = tokio::select! {
+                result = self.llm_corrector.correct_streaming(&raw_text, &context, &partial_tx) => Some(result),
+                _ = wait_for_cancel(&cancel) => None,
+            };
+            drop(partial_tx);
+            let _ = forward_task.await;
+            match correction {
+                Some(Ok(corrected)) => {
+                    log::info!(
+                        "llm stage complete in {}ms: {}",
+                        correction_start.elapsed().as_millis(),
+                        crate::logging::redact_transcript(&corrected, log_transcripts)
+                    );
+                    self.context_manager
+                        .lock()
+                        .unwrap()
+                        .active()
+                        .push_sentence(corrected.clone());
+                    let _ = result_tx
+                        .send(PipelineResult::CorrectionComplete(CorrectionResult {
+                            corrected_text: corrected.clone(),
+                            duration_ms: 0,
+                        }))
+                        .await;
+                    corrected_text = Some(corrected.clone());
+                    if persist_enabled {
+                        recovery::save(&recovery::RecoveryState {
+                            raw_text: raw_text.clone(),
+                            corrected_text: corrected_text.clone(),
+                            final_text: None,
+                            timestamp_secs: unix_now_secs(),
+                        });
+                    }
+                    corrected
+                }
+                Some(Err(_)) => raw_text.clone(),
+                None => {
+                    let _ = result_tx.send(PipelineResult::Cancelled).await;
+                    return;
+                }
+            }
+        } else {
+            raw_text.clone()
+        };
+
+        let timestamp_secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        if persist_enabled {
+            let _ = self.history.append(&HistoryEntry {
+                raw_text: raw_text.clone(),
+                corrected_text: corrected_text.clone(),
+                timestamp_secs,
+                duration_ms: result.duration_ms as u64,
+                mode,
+                domain: context.domain.clone(),
+                segments: result.segments.clone(),
+                recording_path: recording_path.clone(),
+            });
+        }
+
+        let (webhook_enabled, webhook_url) = {
+            let settings = self.settings.read();
+            (settings.webhook_enabled, settings.webhook_url.clone())
+        };
+        if webhook_enabled {
+            if let Some(url) = webhook_url.filter(|u| !u.trim().is_empty()) {
+                crate::integrations::webhook::fire(
+                    url,
+                    crate::integrations::webhook::WebhookPayload {
+                        raw_text: raw_text.clone(),
+                        corrected_text: corrected_text.clone(),
+                        timestamp_secs,
+                        duration_ms: result.duration_ms as u64,
+                        domain: context.domain.clone(),
+                    },
+                );
+            }
+        }
+
+        let final_text = crate::text::normalize(&final_text, &self.settings.read());
+        let final_text = self.commands.apply(&final_text);
+        let final_text = self.snippets.apply(&final_text);
+        let final_text = self.profanity.mask(&final_text);
+
+        log::info!(
+            "utterance complete in {}ms: {}",
+            utterance_start.elapsed().as_millis(),
+            crate::logging::redact_transcript(&final_text, log_transcripts)
+        );
+
+        {
+            let (append_to_note, note_file_path) = {
+                let settings = self.settings.read();
+                (settings.append_to_note, settings.note_file_path.clone())
+            };
+            if append_to_note {
+                if let Some(path) = note_file_path.filter(|p| !p.trim().is_empty()) {
+                    if let Err(e) =
+                        crate::inject::note::append(std::path::Path::new(&path), &final_text)
+                    {
+                        log::warn!("Failed to append to note file: {}", e);
+                    }
+                }
+            }
+        }
+
+        let target_focus = focus;
+        let final_text = self.formatting.apply(&final_text, target_focus.as_ref());
+
+        if persist_enabled {
+            recovery::save(&recovery::RecoveryState {
+                raw_text: raw_text.clone(),
+                corrected_text: corrected_text.clone(),
+                final_text: Some(final_text.clone()),
+                timestamp_secs: unix_now_secs(),
+            });
+        }
+
+        if !self.settings.read().auto_inject {
+            let _ = result_tx
+                .send(PipelineResult::InjectionPending(final_text))
+                .await;
+            return;
+        }
+
+        let inject_timeout =
+            std::time::Duration::from_secs(self.settings.read().inject_timeout_secs);
+        let injector = self.text_injector.clone();
+        let inject_task = tokio::task::spawn_blocking(move || {
+            injector.inject(&final_text, target_focus.as_ref())
+        });
+        let inject_result = match tokio::time::timeout(inject_timeout, inject_task).await {
+            Ok(joined) => joined,
+            Err(_) => {
+                let _ = result_tx
+                    .send(PipelineResult::Error(PipelineError::Inject(format!(
+                        "Injection timed out after {}s",
+                        inject_timeout.as_secs()
+                    ))))
+                    .await;
+                return;
+            }
+        };
+
+        match inject_result {
+            Ok(Ok(())) => {
+                if persist_enabled {
+                    recovery::clear();
+                }
+                let _ = result_tx.send(PipelineResult::InjectionComplete).await;
+            }
+            Ok(Err(e)) => {
+                let _ = result_tx
+                    .send(PipelineResult::Error(PipelineError::Inject(e.to_string())))
+                    .await;
+            }
+            Err(e) => {
+                let _ = result_tx
+                    .send(PipelineResult::Error(PipelineError::Internal(
+                        e.to_string(),
+                    )))
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reproduces the race the swap-not-mutate design in `active_cancel`'s
+    /// doc comment exists for: an utterance captures its own cancel flag at
+    /// `drain_recording` time, `Cancel` sets that captured flag, and a
+    /// `StartRecording` for the *next* utterance must not un-cancel it.
+    #[test]
+    fn cancel_on_current_utterance_is_unaffected_by_a_later_start_recording() {
+        let active_cancel: Mutex<Arc<AtomicBool>> = Mutex::new(Arc::new(AtomicBool::new(false)));
+
+        // drain_recording captures whatever flag is current when the
+        // utterance is queued.
+        let utterance_cancel = active_cancel.lock().unwrap().clone();
+
+        // PipelineCommand::Cancel targets "whatever's current" — still this
+        // utterance's flag at this point.
+        active_cancel.lock().unwrap().store(true, Ordering::Relaxed);
+        assert!(utterance_cancel.load(Ordering::Relaxed));
+
+        // PipelineCommand::StartRecording arms a fresh flag for the next
+        // recording...
+        arm_fresh_cancel_flag(&active_cancel);
+
+        // ...without resetting the already-cancelled utterance's flag.
+        assert!(utterance_cancel.load(Ordering::Relaxed));
+        assert!(!active_cancel.lock().unwrap().load(Ordering::Relaxed));
+    }
+}
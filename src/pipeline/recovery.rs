@@ -0,0 +1,65 @@
+//! Crash-recovery checkpoint. `process_utterance` overwrites this file after
+//! each stage past STT — the point where losing the process would otherwise
+//! lose real work — and clears it once the text is actually injected. On the
+//! next launch, `ThaiSttApp::new` loads it so the widget can offer "recover
+//! last dictation" instead of the text silently vanishing.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::AppPaths;
+
+/// The furthest-along known state of the utterance that was in flight when
+/// the checkpoint was last written.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryState {
+    pub raw_text: String,
+    pub corrected_text: Option<String>,
+    /// Fully post-processed text (commands/snippets/profanity/formatting
+    /// applied), once available — this is what recovery's Inject button
+    /// sends, matching exactly what automatic injection would have sent.
+    pub final_text: Option<String>,
+    pub timestamp_secs: u64,
+}
+
+impl RecoveryState {
+    /// The text a user would want copied or re-injected — the most
+    /// fully-processed version available.
+    pub fn best_text(&self) -> &str {
+        self.final_text
+            .as_deref()
+            .or(self.corrected_text.as_deref())
+            .unwrap_or(&self.raw_text)
+    }
+}
+
+fn path() -> PathBuf {
+    AppPaths::recovery_path()
+}
+
+/// Overwrites the checkpoint with the latest known state of the in-flight
+/// utterance.
+pub fn save(state: &RecoveryState) {
+    let Ok(json) = serde_json::to_string(state) else {
+        return;
+    };
+    if let Some(parent) = path().parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = std::fs::write(path(), json) {
+        log::warn!("Failed to write recovery checkpoint: {}", e);
+    }
+}
+
+/// Loads the last checkpoint, if any. Called once at startup.
+pub fn load() -> Option<RecoveryState> {
+    let content = std::fs::read_to_string(path()).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Removes the checkpoint once its text has been injected, copied, or
+/// dismissed, so a normal run doesn't leave a stale recovery prompt behind.
+pub fn clear() {
+    let _ = std::fs::remove_file(path());
+}
@@ -0,0 +1,48 @@
+//! Reads `/sys/class/power_supply/`: a `Mains`-type supply's `online` file
+//! tells us AC/battery directly; failing that, any `Battery`-type supply's
+//! `status` file ("Discharging" vs. everything else) is used instead.
+
+use std::path::Path;
+
+use super::PowerSource;
+
+const POWER_SUPPLY_DIR: &str = "/sys/class/power_supply";
+
+pub fn query() -> Option<PowerSource> {
+    let entries = std::fs::read_dir(POWER_SUPPLY_DIR).ok()?;
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        match read_trimmed(&path.join("type")).as_deref() {
+            Some("Mains") => {
+                if let Some(online) = read_trimmed(&path.join("online")) {
+                    return Some(if online == "1" {
+                        PowerSource::Ac
+                    } else {
+                        PowerSource::Battery
+                    });
+                }
+            }
+            Some("Battery") => {
+                if let Some(status) = read_trimmed(&path.join("status")) {
+                    return Some(if status == "Discharging" {
+                        PowerSource::Battery
+                    } else {
+                        PowerSource::Ac
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // No usable power_supply device found — likely a desktop with no
+    // battery, so `power_aware_mode` should never kick in.
+    None
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
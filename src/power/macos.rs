@@ -0,0 +1,23 @@
+//! Shells out to `pmset -g batt`, whose first line reads
+//! `Now drawing from 'AC Power'` or `Now drawing from 'Battery Power'` on
+//! every Mac since this has been a stable, undocumented-but-unchanged CLI
+//! for years — cheaper than binding IOKit's power source APIs for a single
+//! on/off-battery check.
+
+use std::process::Command;
+
+use super::PowerSource;
+
+pub fn query() -> Option<PowerSource> {
+    let output = Command::new("pmset").arg("-g").arg("batt").output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first_line = stdout.lines().next()?;
+
+    if first_line.contains("Battery Power") {
+        Some(PowerSource::Battery)
+    } else if first_line.contains("AC Power") {
+        Some(PowerSource::Ac)
+    } else {
+        None
+    }
+}
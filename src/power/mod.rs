@@ -0,0 +1,70 @@
+//! Best-effort AC power / battery detection, used by `power_aware_mode` to
+//! automatically drop to a lighter STT model and `OperatingMode::Fast`
+//! while a laptop is running unplugged. No crate does this cross-platform
+//! without pulling in a fair amount of extra machinery, so each backend is
+//! a small hand-rolled query instead.
+
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(target_os = "macos")]
+mod macos;
+#[cfg(target_os = "windows")]
+mod windows;
+
+#[cfg(target_os = "linux")]
+use linux::query;
+#[cfg(target_os = "macos")]
+use macos::query;
+#[cfg(target_os = "windows")]
+use windows::query;
+
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::pipeline::PipelineCommand;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PowerSource {
+    Ac,
+    Battery,
+}
+
+/// Queries the OS for the current power source. Returns `None` on a
+/// desktop with no battery, or if the platform query fails — callers
+/// should treat that the same as `Ac` (i.e. do nothing).
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+pub fn power_source() -> Option<PowerSource> {
+    query()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn power_source() -> Option<PowerSource> {
+    None
+}
+
+/// How often to re-check the power source.
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Polls `power_source` on a background thread and notifies the pipeline
+/// only when it changes, mirroring `config::watcher`'s mtime-polling
+/// approach rather than pulling in a platform power-event API.
+pub fn spawn_power_monitor(command_tx: mpsc::Sender<PipelineCommand>) {
+    std::thread::spawn(move || {
+        let mut last = power_source();
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = power_source();
+            if current != last {
+                last = current;
+                let on_battery = current == Some(PowerSource::Battery);
+                if command_tx
+                    .blocking_send(PipelineCommand::PowerSourceChanged(on_battery))
+                    .is_err()
+                {
+                    return;
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,47 @@
+//! Bound directly against `kernel32.dll`'s `GetSystemPowerStatus` — no
+//! `winapi`/`windows-sys` dependency, since this is the only Win32 call
+//! this module needs (same approach as `inject::sendinput`).
+
+use super::PowerSource;
+
+#[repr(C)]
+struct SystemPowerStatus {
+    ac_line_status: u8,
+    battery_flag: u8,
+    battery_life_percent: u8,
+    system_status_flag: u8,
+    battery_life_time: u32,
+    battery_full_life_time: u32,
+}
+
+#[link(name = "kernel32")]
+extern "system" {
+    fn GetSystemPowerStatus(status: *mut SystemPowerStatus) -> i32;
+}
+
+/// `AC_LINE_STATUS` values from `winbase.h`: 0 = offline (battery),
+/// 1 = online (AC), 255 = unknown.
+const AC_LINE_OFFLINE: u8 = 0;
+const AC_LINE_ONLINE: u8 = 1;
+
+pub fn query() -> Option<PowerSource> {
+    let mut status = SystemPowerStatus {
+        ac_line_status: 0,
+        battery_flag: 0,
+        battery_life_percent: 0,
+        system_status_flag: 0,
+        battery_life_time: 0,
+        battery_full_life_time: 0,
+    };
+
+    let ok = unsafe { GetSystemPowerStatus(&mut status) };
+    if ok == 0 {
+        return None;
+    }
+
+    match status.ac_line_status {
+        AC_LINE_ONLINE => Some(PowerSource::Ac),
+        AC_LINE_OFFLINE => Some(PowerSource::Battery),
+        _ => None,
+    }
+}
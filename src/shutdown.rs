@@ -0,0 +1,45 @@
+//! Coordinates graceful shutdown: a Ctrl+C (SIGINT, or the platform
+//! equivalent) is routed through the same window-close path as a normal
+//! close, so `ThaiSttApp::on_exit` always gets a chance to stop the mic
+//! stream, cancel in-flight pipeline work, and flush settings before the
+//! process exits — instead of the runtime and its background tasks being
+//! dropped mid-flight.
+
+use eframe::egui;
+use tokio::sync::mpsc;
+
+use crate::pipeline::{PipelineCommand, SharedSettings};
+
+/// Spawns a task that waits for Ctrl+C and asks the window to close, so
+/// `eframe::App::on_exit` runs instead of the process dying immediately.
+/// `ctx` is the `egui::Context` handed to the app's creation closure.
+pub fn spawn_signal_listener(ctx: egui::Context) {
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            log::info!("Received interrupt signal, closing window");
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+    });
+}
+
+/// Headless-mode equivalent of `spawn_signal_listener` — there's no
+/// `egui::Context` to route the close through since there's no window, so
+/// this waits for Ctrl+C directly and runs the same shutdown sequence
+/// before returning.
+pub async fn wait_for_ctrl_c(settings: SharedSettings, command_tx: mpsc::Sender<PipelineCommand>) {
+    if tokio::signal::ctrl_c().await.is_ok() {
+        log::info!("Received interrupt signal, shutting down");
+        shutdown(&settings, &command_tx);
+    }
+}
+
+/// Cancels whatever the pipeline is in the middle of and flushes settings to
+/// disk. Called from `ThaiSttApp::on_exit` — history doesn't need a flush of
+/// its own since `HistoryStore::append` opens, writes, and closes the file
+/// on every call.
+pub fn shutdown(settings: &SharedSettings, command_tx: &mpsc::Sender<PipelineCommand>) {
+    let _ = command_tx.try_send(PipelineCommand::Cancel);
+    if let Err(e) = settings.read().save() {
+        log::warn!("Failed to save settings during shutdown: {}", e);
+    }
+}
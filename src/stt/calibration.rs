@@ -0,0 +1,49 @@
+//! One-time thread-count calibration. `WhisperEngine::optimal_threads`'s
+//! static physical-core guess (capped at 8) is a reasonable default but
+//! guesses badly on hybrid CPUs where some of those "physical" cores are
+//! slow efficiency cores, so on first run we instead benchmark decoding a
+//! short clip of silence at every thread count from 1 up to that guess and
+//! keep whichever was actually fastest.
+//!
+//! Pinning the decode thread pool to specific performance cores is not
+//! attempted here — there's no portable, dependency-free way to enumerate
+//! P-cores vs. E-cores across Linux/macOS/Windows, and picking the fastest
+//! thread *count* already captures most of the benefit on today's hybrid
+//! CPUs, since whisper.cpp's scheduler tends to get squeezed off P-cores
+//! by the OS once the thread count exceeds them anyway.
+
+use std::time::{Duration, Instant};
+
+use super::engine::WhisperEngine;
+
+/// Length of the synthetic silent clip benchmarked at each thread count —
+/// long enough for thread scaling to show up, short enough that first-run
+/// calibration doesn't noticeably delay startup.
+const BENCHMARK_SECS: usize = 3;
+
+/// Time decoding `BENCHMARK_SECS` of silence at every thread count from 1
+/// to `max_threads` and return whichever was fastest. Falls back to
+/// `max_threads` if every attempt errors (e.g. a model that fails to
+/// decode silence at all, which would fail identically at any thread
+/// count).
+pub fn calibrate(engine: &WhisperEngine, max_threads: i32) -> i32 {
+    let audio = vec![0.0f32; BENCHMARK_SECS * 16_000];
+    let mut best_threads = max_threads;
+    let mut best_elapsed = Duration::MAX;
+
+    for threads in 1..=max_threads.max(1) {
+        let start = Instant::now();
+        if let Err(e) = engine.benchmark_decode(&audio, threads) {
+            log::warn!("Thread calibration: {} threads failed: {}", threads, e);
+            continue;
+        }
+        let elapsed = start.elapsed();
+        log::info!("Thread calibration: {} threads -> {:?}", threads, elapsed);
+        if elapsed < best_elapsed {
+            best_elapsed = elapsed;
+            best_threads = threads;
+        }
+    }
+
+    best_threads
+}
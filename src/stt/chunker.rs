@@ -0,0 +1,182 @@
+//! Splits long recordings into chunks at silence boundaries before handing
+//! them to Whisper, which reliably starts dropping and duplicating text well
+//! past its 30s training window, then stitches the per-chunk results back
+//! into one `TranscriptionResult` with continuous timestamps.
+
+use std::sync::atomic::Ordering;
+
+use super::engine::{Segment, TranscriptionResult};
+use super::model::ModelSize;
+
+/// Frame size used when searching for a quiet spot to cut on, matching
+/// `audio::VadDetector`'s 30ms energy-per-frame window.
+const FRAME_SIZE: usize = 480;
+/// How far back from a hard cutoff to search for a quieter frame, so a chunk
+/// boundary lands between words rather than mid-syllable.
+const BOUNDARY_SEARCH_SECS: usize = 3;
+
+fn frame_energy(chunk: &[f32]) -> f32 {
+    chunk.iter().map(|s| s * s).sum::<f32>() / chunk.len().max(1) as f32
+}
+
+/// Splits `audio` into `(start, end)` sample ranges no longer than
+/// `max_chunk_samples`, cutting each at the quietest frame found within the
+/// last `BOUNDARY_SEARCH_SECS` seconds before the hard cutoff.
+fn split_at_silence(audio: &[f32], max_chunk_samples: usize) -> Vec<(usize, usize)> {
+    if max_chunk_samples == 0 || audio.len() <= max_chunk_samples {
+        return vec![(0, audio.len())];
+    }
+
+    let search_window = BOUNDARY_SEARCH_SECS * 16_000;
+    let mut ranges = Vec::new();
+    let mut start = 0;
+
+    while start < audio.len() {
+        let hard_cutoff = (start + max_chunk_samples).min(audio.len());
+        if hard_cutoff == audio.len() {
+            ranges.push((start, audio.len()));
+            break;
+        }
+
+        let search_start = hard_cutoff.saturating_sub(search_window).max(start);
+        let boundary = audio[search_start..hard_cutoff]
+            .chunks(FRAME_SIZE)
+            .enumerate()
+            .min_by(|(_, a), (_, b)| frame_energy(a).total_cmp(&frame_energy(b)))
+            .map(|(i, _)| search_start + i * FRAME_SIZE)
+            .filter(|&boundary| boundary > start)
+            .unwrap_or(hard_cutoff);
+
+        ranges.push((start, boundary));
+        start = boundary;
+    }
+
+    ranges
+}
+
+/// Merges per-chunk results (in chunk order, each tagged with its start
+/// sample offset) into one `TranscriptionResult` with segment timestamps
+/// relative to the whole recording.
+fn stitch(chunks: Vec<(usize, TranscriptionResult)>) -> TranscriptionResult {
+    let mut text = String::new();
+    let mut segments = Vec::new();
+    let mut duration_ms = 0u128;
+    let mut model_size = ModelSize::Medium;
+
+    for (start, chunk_result) in chunks {
+        let offset_ms = (start as u64 * 1000) / 16_000;
+
+        duration_ms += chunk_result.duration_ms;
+        model_size = chunk_result.model_size;
+        if !text.is_empty() && !chunk_result.text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(&chunk_result.text);
+
+        segments.extend(chunk_result.segments.into_iter().map(|s| Segment {
+            start_ms: s.start_ms + offset_ms,
+            end_ms: s.end_ms + offset_ms,
+            ..s
+        }));
+    }
+
+    TranscriptionResult {
+        text,
+        segments,
+        duration_ms,
+        model_size,
+    }
+}
+
+/// Transcribes `audio` in pieces of at most `max_chunk_secs`, calling
+/// `transcribe` once per piece, and stitches the results into one
+/// `TranscriptionResult` with segment timestamps offset to stay relative to
+/// the whole recording. `transcribe` can be any of `SttEngine`'s methods
+/// (plain, cancelable, or primed) via a closure, so callers keep whichever
+/// cancellation/prompting behavior they already use.
+pub fn transcribe_chunked(
+    audio: &[f32],
+    max_chunk_secs: u64,
+    mut transcribe: impl FnMut(&[f32]) -> anyhow::Result<TranscriptionResult>,
+) -> anyhow::Result<TranscriptionResult> {
+    let max_chunk_samples = max_chunk_secs as usize * 16_000;
+    let ranges = split_at_silence(audio, max_chunk_samples);
+
+    let mut chunks = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        if end <= start {
+            continue;
+        }
+        chunks.push((start, transcribe(&audio[start..end])?));
+    }
+
+    Ok(stitch(chunks))
+}
+
+/// Number of chunks to transcribe at once in `transcribe_chunked_parallel`,
+/// balancing latency against the RAM each concurrent Whisper state needs
+/// (a large model's decode state alone can run into the hundreds of MB).
+pub fn default_worker_count() -> usize {
+    const MAX_WORKERS: usize = 4;
+    num_cpus::get_physical().clamp(1, MAX_WORKERS)
+}
+
+/// Like `transcribe_chunked`, but runs up to `worker_count` chunks
+/// concurrently across a bounded thread pool instead of one at a time —
+/// dramatically cuts end-to-end latency for multi-chunk dictations on
+/// multi-core machines. `transcribe` must be safe to call from several
+/// threads at once (an `SttEngine` behind a shared reference already is,
+/// since the trait requires `Send + Sync`).
+pub fn transcribe_chunked_parallel(
+    audio: &[f32],
+    max_chunk_secs: u64,
+    worker_count: usize,
+    transcribe: impl Fn(&[f32]) -> anyhow::Result<TranscriptionResult> + Sync,
+) -> anyhow::Result<TranscriptionResult> {
+    let max_chunk_samples = max_chunk_secs as usize * 16_000;
+    let ranges = split_at_silence(audio, max_chunk_samples);
+    let worker_count = worker_count.max(1).min(ranges.len().max(1));
+
+    let mut results: Vec<Option<anyhow::Result<TranscriptionResult>>> =
+        (0..ranges.len()).map(|_| None).collect();
+    let next_index = std::sync::atomic::AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..worker_count)
+            .map(|_| {
+                let ranges = &ranges;
+                let transcribe = &transcribe;
+                let next_index = &next_index;
+                scope.spawn(move || {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(&(start, end)) = ranges.get(i) else {
+                            break;
+                        };
+                        if end <= start {
+                            continue;
+                        }
+                        done.push((i, transcribe(&audio[start..end])));
+                    }
+                    done
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            for (i, result) in handle.join().expect("chunk worker thread panicked") {
+                results[i] = Some(result);
+            }
+        }
+    });
+
+    let mut chunks = Vec::with_capacity(results.len());
+    for (result, &(start, _)) in results.into_iter().zip(ranges.iter()) {
+        if let Some(result) = result {
+            chunks.push((start, result?));
+        }
+    }
+
+    Ok(stitch(chunks))
+}
@@ -0,0 +1,26 @@
+//! Per-segment code-switch detection: flags whether a Whisper segment is
+//! predominantly English/Latin script rather than Thai, so downstream
+//! post-processing can treat it as a token to preserve verbatim. Whisper
+//! doesn't expose per-segment language probabilities through whisper-rs, so
+//! this counts Latin vs. Thai letters, using the same Thai character range
+//! as `text::spacing`.
+
+fn is_thai(c: char) -> bool {
+    ('\u{0E01}'..='\u{0E5B}').contains(&c)
+}
+
+/// True when at least half of `text`'s alphabetic characters are Latin
+/// script rather than Thai. A segment with no alphabetic characters at all
+/// (pure numbers/punctuation) is not considered English.
+pub fn is_english_segment(text: &str) -> bool {
+    let mut thai = 0usize;
+    let mut latin = 0usize;
+    for c in text.chars() {
+        if is_thai(c) {
+            thai += 1;
+        } else if c.is_ascii_alphabetic() {
+            latin += 1;
+        }
+    }
+    latin > 0 && latin >= thai
+}
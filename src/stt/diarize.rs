@@ -0,0 +1,139 @@
+//! Lightweight energy-based speaker diarization for two-party dictation
+//! (e.g. doctor/patient), useful alongside the Medical/Legal domains
+//! `DomainDetector` already targets. A real diarization pass would need
+//! speaker embeddings from a trained model, which this repo has no
+//! offline-friendly way to ship or run; clustering each segment's average
+//! recording level into two groups is a workable proxy for a two-person,
+//! single-microphone setup (the near speaker is consistently louder than
+//! the far one) without adding a model or dependency.
+
+use super::engine::Segment;
+
+pub const SPEAKER_A: &str = "Speaker A";
+pub const SPEAKER_B: &str = "Speaker B";
+
+/// Labels each segment in `segments` as [`SPEAKER_A`] (louder cluster) or
+/// [`SPEAKER_B`] (quieter cluster) by clustering RMS amplitude into two
+/// groups, using `audio` — the full 16kHz mono recording the segments'
+/// timestamps are relative to. Leaves `segment.speaker` untouched (`None`)
+/// if there are fewer than two segments, or if every segment is equally
+/// loud and there's nothing to distinguish.
+pub fn diarize(audio: &[f32], segments: &mut [Segment]) {
+    if segments.len() < 2 {
+        return;
+    }
+
+    let energies: Vec<f32> = segments.iter().map(|s| segment_rms(audio, s)).collect();
+    let low_start = energies.iter().cloned().fold(f32::MAX, f32::min);
+    let high_start = energies.iter().cloned().fold(f32::MIN, f32::max);
+    if (high_start - low_start).abs() < f32::EPSILON {
+        return;
+    }
+
+    let (mut low, mut high) = (low_start, high_start);
+    // A handful of Lloyd's-algorithm iterations is plenty to converge two
+    // means over this few points.
+    for _ in 0..10 {
+        let (mut low_sum, mut low_n, mut high_sum, mut high_n) = (0.0f32, 0u32, 0.0f32, 0u32);
+        for &e in &energies {
+            if (e - low).abs() <= (e - high).abs() {
+                low_sum += e;
+                low_n += 1;
+            } else {
+                high_sum += e;
+                high_n += 1;
+            }
+        }
+        if low_n > 0 {
+            low = low_sum / low_n as f32;
+        }
+        if high_n > 0 {
+            high = high_sum / high_n as f32;
+        }
+    }
+
+    for (segment, &e) in segments.iter_mut().zip(&energies) {
+        let label = if (e - high).abs() <= (e - low).abs() {
+            SPEAKER_A
+        } else {
+            SPEAKER_B
+        };
+        segment.speaker = Some(label.to_string());
+    }
+}
+
+/// RMS amplitude of the audio samples spanning `segment`'s timestamps,
+/// which are always in the 16kHz timeline `Segment`/`TranscribeParams`
+/// use throughout (see `audio::player`'s doc comment on the same
+/// convention).
+fn segment_rms(audio: &[f32], segment: &Segment) -> f32 {
+    let start = (segment.start_ms as usize * 16).min(audio.len());
+    let end = (segment.end_ms as usize * 16).min(audio.len());
+    if start >= end {
+        return 0.0;
+    }
+    let slice = &audio[start..end];
+    (slice.iter().map(|s| s * s).sum::<f32>() / slice.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(start_ms: u64, end_ms: u64) -> Segment {
+        Segment {
+            text: String::new(),
+            start_ms,
+            end_ms,
+            avg_confidence: 1.0,
+            is_english: false,
+            words: Vec::new(),
+            speaker: None,
+        }
+    }
+
+    #[test]
+    fn leaves_speaker_untouched_with_fewer_than_two_segments() {
+        let mut segments = vec![segment(0, 1000)];
+        diarize(&vec![0.5f32; 16_000], &mut segments);
+        assert_eq!(segments[0].speaker, None);
+    }
+
+    #[test]
+    fn leaves_speaker_untouched_when_every_segment_is_equally_loud() {
+        let audio = vec![0.5f32; 32_000];
+        let mut segments = vec![segment(0, 1000), segment(1000, 2000)];
+        diarize(&audio, &mut segments);
+        assert_eq!(segments[0].speaker, None);
+        assert_eq!(segments[1].speaker, None);
+    }
+
+    #[test]
+    fn labels_the_louder_cluster_speaker_a_and_the_quieter_one_speaker_b() {
+        let mut audio = vec![0.0f32; 48_000];
+        // First second: quiet. Second second: loud. Third second: quiet again,
+        // so the clusters aren't just "first half vs second half".
+        for sample in audio[16_000..32_000].iter_mut() {
+            *sample = 0.9;
+        }
+        for sample in audio[0..16_000].iter_mut() {
+            *sample = 0.05;
+        }
+        for sample in audio[32_000..48_000].iter_mut() {
+            *sample = 0.05;
+        }
+        let mut segments = vec![segment(0, 1000), segment(1000, 2000), segment(2000, 3000)];
+        diarize(&audio, &mut segments);
+        assert_eq!(segments[0].speaker.as_deref(), Some(SPEAKER_B));
+        assert_eq!(segments[1].speaker.as_deref(), Some(SPEAKER_A));
+        assert_eq!(segments[2].speaker.as_deref(), Some(SPEAKER_B));
+    }
+
+    #[test]
+    fn segment_rms_is_zero_for_an_out_of_range_or_empty_span() {
+        let audio = vec![1.0f32; 1_000];
+        assert_eq!(segment_rms(&audio, &segment(0, 0)), 0.0);
+        assert_eq!(segment_rms(&audio, &segment(500, 500)), 0.0);
+        assert_eq!(segment_rms(&audio, &segment(1000, 2000)), 0.0);
+    }
+}
@@ -0,0 +1,169 @@
+//! Downloads GGML model files into `AppPaths::models_dir`, with resume
+//! support and progress reporting for a setup wizard / settings UI.
+
+use std::io::SeekFrom;
+
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+use super::model::ModelInfo;
+
+/// Progress snapshot delivered to the caller-supplied callback.
+#[derive(Debug, Clone, Copy)]
+pub struct DownloadProgress {
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl DownloadProgress {
+    pub fn fraction(&self) -> f32 {
+        if self.total_bytes == 0 {
+            0.0
+        } else {
+            self.downloaded_bytes as f32 / self.total_bytes as f32
+        }
+    }
+}
+
+pub enum DownloadError {
+    Network(String),
+    Io(String),
+    SizeMismatch { expected: u64, actual: u64 },
+    HashMismatch { expected: String, actual: String },
+}
+
+impl std::fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Network(e) => write!(f, "Network error: {}", e),
+            Self::Io(e) => write!(f, "I/O error: {}", e),
+            Self::SizeMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Downloaded size {} does not match expected {} bytes",
+                    actual, expected
+                )
+            }
+            Self::HashMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "Downloaded file's sha256 {} does not match expected {}",
+                    actual, expected
+                )
+            }
+        }
+    }
+}
+
+pub struct ModelDownloader {
+    client: reqwest::Client,
+}
+
+impl Default for ModelDownloader {
+    fn default() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+impl ModelDownloader {
+    /// Download `model` into its registry-defined local path, calling
+    /// `on_progress` after every chunk. Resumes a partial download found on
+    /// disk via an HTTP Range request, then verifies the final file size
+    /// against `model.file_size_mb`.
+    pub async fn download(
+        &self,
+        model: &ModelInfo,
+        mut on_progress: impl FnMut(DownloadProgress) + Send,
+    ) -> Result<(), DownloadError> {
+        let dest = model.local_path();
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| DownloadError::Io(e.to_string()))?;
+        }
+
+        let mut existing_bytes = tokio::fs::metadata(&dest)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        let mut request = self.client.get(model.source_url);
+        if existing_bytes > 0 {
+            request = request.header("Range", format!("bytes={}-", existing_bytes));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| DownloadError::Network(e.to_string()))?;
+        if response.status().as_u16() != 206 {
+            // Server ignored our Range request (or file changed) — restart from scratch.
+            existing_bytes = 0;
+        }
+
+        let total_bytes = existing_bytes + response.content_length().unwrap_or(0);
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(&dest)
+            .await
+            .map_err(|e| DownloadError::Io(e.to_string()))?;
+        file.seek(SeekFrom::Start(existing_bytes))
+            .await
+            .map_err(|e| DownloadError::Io(e.to_string()))?;
+
+        let mut downloaded = existing_bytes;
+        let mut stream = response.bytes_stream();
+        use futures_util::StreamExt;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| DownloadError::Network(e.to_string()))?;
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| DownloadError::Io(e.to_string()))?;
+            downloaded += chunk.len() as u64;
+            on_progress(DownloadProgress {
+                downloaded_bytes: downloaded,
+                total_bytes,
+            });
+        }
+
+        let expected_bytes = model.file_size_mb * 1_048_576;
+        let actual_bytes = tokio::fs::metadata(&dest)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        // Registry sizes are approximate (rounded MB), so allow slack rather
+        // than failing on off-by-a-few-KB mismatches.
+        let tolerance = expected_bytes / 20; // 5%
+        if actual_bytes.abs_diff(expected_bytes) > tolerance {
+            return Err(DownloadError::SizeMismatch {
+                expected: expected_bytes,
+                actual: actual_bytes,
+            });
+        }
+
+        if model.sha256.is_empty() {
+            log::warn!(
+                "{} has no recorded sha256 — skipping integrity check",
+                model.display_name
+            );
+        } else {
+            let dest_for_hash = dest.clone();
+            let actual_hash =
+                tokio::task::spawn_blocking(move || super::sha256::hash_file(&dest_for_hash))
+                    .await
+                    .map_err(|e| DownloadError::Io(e.to_string()))?
+                    .map_err(|e| DownloadError::Io(e.to_string()))?;
+            if actual_hash != model.sha256 {
+                return Err(DownloadError::HashMismatch {
+                    expected: model.sha256.to_string(),
+                    actual: actual_hash,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
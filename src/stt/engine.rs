@@ -0,0 +1,563 @@
+//! Whisper-backed STT engine.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use whisper_rs::{
+    FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters, WhisperState,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::model::ModelSize;
+use super::sanity;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Segment {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Mean per-token probability whisper.cpp assigned this segment, in
+    /// `0.0..=1.0`. Lower values mean the decoder was less sure — useful for
+    /// flagging words worth a second look before trusting LLM correction.
+    pub avg_confidence: f32,
+    /// Whether this segment is predominantly English/Latin script rather
+    /// than Thai — Thai developers code-switch heavily, and segments like
+    /// this should be preserved verbatim rather than run through
+    /// Thai-specific post-processing. See `codeswitch::is_english_segment`.
+    /// Old entries logged before this field existed deserialize it as
+    /// `false`.
+    #[serde(default)]
+    pub is_english: bool,
+    /// Per-word timings within this segment, populated only when
+    /// `TranscribeParams::word_timestamps` was set for the decode that
+    /// produced it. Empty otherwise, including for every entry logged
+    /// before this field existed. Powers the history panel's word-by-word
+    /// karaoke playback review — see `audio::player`.
+    #[serde(default)]
+    pub words: Vec<WordTiming>,
+    /// Speaker label assigned by `diarize::diarize`, when
+    /// `AppSettings::stt_diarization_enabled` is on. `None` for
+    /// undiarized entries and every entry logged before this field
+    /// existed.
+    #[serde(default)]
+    pub speaker: Option<String>,
+}
+
+/// Start/end timestamp for a single decoded word, derived from whisper.cpp's
+/// per-token timestamps (see `WhisperEngine::segment_words`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub word: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+pub struct TranscriptionResult {
+    pub text: String,
+    pub segments: Vec<Segment>,
+    pub duration_ms: u128,
+    pub model_size: ModelSize,
+}
+
+/// STT Engine abstraction — swap Whisper for another engine.
+pub trait SttEngine: Send + Sync {
+    fn transcribe(&self, audio: &[f32], language: &str) -> anyhow::Result<TranscriptionResult>;
+
+    /// Transcribe a partial (still-growing) audio buffer for a live preview.
+    /// Default implementation just runs a normal pass over what's captured
+    /// so far; engines with true incremental decoding can override this to
+    /// reuse state between calls instead of re-decoding from scratch.
+    fn transcribe_streaming(
+        &self,
+        audio: &[f32],
+        language: &str,
+    ) -> anyhow::Result<TranscriptionResult> {
+        self.transcribe(audio, language)
+    }
+
+    /// Like `transcribe`, but polls `cancel` during decoding and returns
+    /// early once it's set. Engines that can't abort mid-inference fall
+    /// back to running to completion.
+    fn transcribe_cancelable(
+        &self,
+        audio: &[f32],
+        language: &str,
+        cancel: &Arc<AtomicBool>,
+    ) -> anyhow::Result<TranscriptionResult> {
+        let _ = cancel;
+        self.transcribe(audio, language)
+    }
+
+    /// Like `transcribe_cancelable`, but biases decoding with `initial_prompt`
+    /// (user vocabulary and detected domain keywords) before the model runs,
+    /// rather than relying solely on the LLM correction pass afterward.
+    /// Engines that don't support prompting ignore it. `translate` requests
+    /// Whisper's translate task, decoding the source-language speech
+    /// straight into English text instead of transcribing it verbatim; see
+    /// `AppSettings.translate_to_english`. Engines that can't translate
+    /// ignore it and transcribe normally.
+    fn transcribe_primed(
+        &self,
+        audio: &[f32],
+        language: &str,
+        initial_prompt: Option<&str>,
+        translate: bool,
+        cancel: &Arc<AtomicBool>,
+    ) -> anyhow::Result<TranscriptionResult> {
+        let _ = (initial_prompt, translate);
+        self.transcribe_cancelable(audio, language, cancel)
+    }
+}
+
+/// Which backend actually served the last-loaded model. whisper.cpp only
+/// reports an error here if a compiled-in GPU backend actively fails to
+/// initialize (e.g. no CUDA device found); a CPU-only build silently runs
+/// on CPU regardless of `use_gpu`, so this reflects the request, not a
+/// runtime guarantee, unless GPU support was compiled in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SttBackend {
+    Cpu,
+    Gpu,
+}
+
+/// Advanced decoding knobs sourced from `AppSettings::stt_*` and applied on
+/// every call into a `WhisperEngine`, unless a call supplies its own
+/// override (the pipeline's dynamic context-built `initial_prompt`, or the
+/// pathological-output retry in `transcribe_inner` forcing beam search).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscribeParams {
+    pub sampling_strategy: crate::config::SttSamplingStrategy,
+    pub best_of: i32,
+    pub beam_size: i32,
+    /// 0 lets the engine pick via `WhisperEngine::optimal_threads`.
+    pub n_threads: i32,
+    pub initial_prompt: Option<String>,
+    /// Decode per-token timestamps and derive `Segment::words` from them.
+    /// Off by default since it adds decode overhead most transcriptions
+    /// don't need. See `AppSettings::stt_word_timestamps`.
+    pub word_timestamps: bool,
+}
+
+impl Default for TranscribeParams {
+    fn default() -> Self {
+        Self {
+            sampling_strategy: crate::config::SttSamplingStrategy::Greedy,
+            best_of: 1,
+            beam_size: 5,
+            n_threads: 0,
+            initial_prompt: None,
+            word_timestamps: false,
+        }
+    }
+}
+
+impl TranscribeParams {
+    pub fn from_settings(settings: &crate::config::AppSettings) -> Self {
+        Self {
+            sampling_strategy: settings.stt_sampling_strategy,
+            best_of: settings.stt_best_of,
+            beam_size: settings.stt_beam_size,
+            n_threads: settings.stt_n_threads,
+            initial_prompt: (!settings.stt_initial_prompt.is_empty())
+                .then(|| settings.stt_initial_prompt.clone()),
+            word_timestamps: settings.stt_word_timestamps,
+        }
+    }
+}
+
+pub struct WhisperEngine {
+    ctx: WhisperContext,
+    model_size: ModelSize,
+    active_backend: SttBackend,
+    /// See `AppSettings::hallucination_blocklist`.
+    hallucination_blocklist: Vec<String>,
+    params: TranscribeParams,
+}
+
+impl WhisperEngine {
+    /// Load `model_path`, preferring GPU acceleration when `use_gpu` is set
+    /// and a compiled-in backend (CUDA/Metal/Vulkan) accepts it, falling
+    /// back to CPU otherwise. Segments are filtered against
+    /// `super::hallucination::DEFAULT_BLOCKLIST` and decoded with default
+    /// `TranscribeParams`; use `with_params` to configure either.
+    pub fn new(model_path: &str, use_gpu: bool) -> anyhow::Result<Self> {
+        Self::with_params(
+            model_path,
+            use_gpu,
+            super::hallucination::DEFAULT_BLOCKLIST
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            TranscribeParams::default(),
+        )
+    }
+
+    /// Like `new`, but with an explicit hallucination blocklist (typically
+    /// `AppSettings::hallucination_blocklist`) instead of the built-in
+    /// default.
+    pub fn with_blocklist(
+        model_path: &str,
+        use_gpu: bool,
+        hallucination_blocklist: Vec<String>,
+    ) -> anyhow::Result<Self> {
+        Self::with_params(
+            model_path,
+            use_gpu,
+            hallucination_blocklist,
+            TranscribeParams::default(),
+        )
+    }
+
+    /// Full constructor: `hallucination_blocklist` and `params` are
+    /// typically both derived from the same `AppSettings` (see
+    /// `TranscribeParams::from_settings`).
+    pub fn with_params(
+        model_path: &str,
+        use_gpu: bool,
+        hallucination_blocklist: Vec<String>,
+        params: TranscribeParams,
+    ) -> anyhow::Result<Self> {
+        let (ctx, active_backend) = Self::load_context(model_path, use_gpu)?;
+        let model_size = Self::detect_model_size(model_path);
+
+        Ok(Self {
+            ctx,
+            model_size,
+            active_backend,
+            hallucination_blocklist,
+            params,
+        })
+    }
+
+    fn load_context(
+        model_path: &str,
+        use_gpu: bool,
+    ) -> anyhow::Result<(WhisperContext, SttBackend)> {
+        if use_gpu {
+            let mut params = WhisperContextParameters::default();
+            params.use_gpu = true;
+            match WhisperContext::new_with_params(model_path, params) {
+                Ok(ctx) => return Ok((ctx, SttBackend::Gpu)),
+                Err(e) => {
+                    log::warn!("GPU acceleration unavailable ({}), falling back to CPU", e);
+                }
+            }
+        }
+
+        let mut params = WhisperContextParameters::default();
+        params.use_gpu = false;
+        let ctx = WhisperContext::new_with_params(model_path, params)
+            .map_err(|e| anyhow::anyhow!("Failed to load Whisper model: {}", e))?;
+        Ok((ctx, SttBackend::Cpu))
+    }
+
+    pub fn active_backend(&self) -> SttBackend {
+        self.active_backend
+    }
+
+    /// Runs one throwaway decode over a second of silence right after
+    /// loading, so the memory paging and threadpool spin-up that make the
+    /// very first real transcription noticeably slower happen before the
+    /// user is waiting on it instead of during their first dictation.
+    /// Errors are logged and otherwise ignored — a failed warm-up just means
+    /// the first real transcription pays the cold-start cost it would have
+    /// paid anyway.
+    pub fn warm_up(&self) {
+        let silence = vec![0.0f32; 16_000];
+        if let Err(e) = self.transcribe(&silence, "en") {
+            log::warn!("STT warm-up pass failed: {}", e);
+        }
+    }
+
+    /// Overwrite the configured thread count after the fact, e.g. once
+    /// `stt::calibration::calibrate` has picked one. Applies to every
+    /// transcription from this point on, without reloading the model.
+    pub fn set_n_threads(&mut self, n_threads: i32) {
+        self.params.n_threads = n_threads;
+    }
+
+    fn detect_model_size(model_path: &str) -> ModelSize {
+        if model_path.contains("small") {
+            ModelSize::Small
+        } else if model_path.contains("large") {
+            ModelSize::Large
+        } else {
+            ModelSize::Medium
+        }
+    }
+
+    /// Mean per-token probability for segment `i`, or 0.0 if it has no
+    /// tokens (shouldn't happen for a non-empty segment, but token lookup
+    /// can fail if whisper.cpp trims a segment down after the fact).
+    fn segment_avg_confidence(state: &WhisperState, i: i32) -> f32 {
+        let num_tokens = state.full_n_tokens(i).unwrap_or(0);
+        if num_tokens == 0 {
+            return 0.0;
+        }
+        let sum: f32 = (0..num_tokens)
+            .map(|j| state.full_get_token_p(i, j).unwrap_or(0.0))
+            .sum();
+        sum / num_tokens as f32
+    }
+
+    /// Group segment `i`'s per-token timestamps (only present when
+    /// `params.set_token_timestamps(true)` was set) into word-level spans.
+    /// whisper.cpp's tokens are BPE sub-words; a token whose text starts
+    /// with a space marks the start of a new word, so tokens are merged
+    /// until the next one that does. Special tokens (rendered as bracketed
+    /// text like `[_BEG_]`) carry no real timing information and are
+    /// skipped.
+    fn segment_words(state: &WhisperState, i: i32) -> Vec<WordTiming> {
+        let num_tokens = state.full_n_tokens(i).unwrap_or(0);
+        let mut words = Vec::new();
+        let mut current: Option<WordTiming> = None;
+
+        for j in 0..num_tokens {
+            let Ok(text) = state.full_get_token_text(i, j) else {
+                continue;
+            };
+            if text.starts_with("[_") {
+                continue;
+            }
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let Ok(data) = state.full_get_token_data(i, j) else {
+                continue;
+            };
+
+            if text.starts_with(' ') || current.is_none() {
+                if let Some(word) = current.take() {
+                    words.push(word);
+                }
+                current = Some(WordTiming {
+                    word: trimmed.to_string(),
+                    start_ms: data.t0 as u64 * 10,
+                    end_ms: data.t1 as u64 * 10,
+                });
+            } else if let Some(word) = current.as_mut() {
+                word.word.push_str(trimmed);
+                word.end_ms = data.t1 as u64 * 10;
+            }
+        }
+        if let Some(word) = current.take() {
+            words.push(word);
+        }
+
+        words
+    }
+
+    pub(crate) fn optimal_threads() -> i32 {
+        num_cpus::get_physical().min(8) as i32
+    }
+
+    /// Decode `audio` with a specific thread count, ignoring both the
+    /// configured `stt_n_threads` override and `optimal_threads`'s guess.
+    /// Used only by `stt::calibration` to time each candidate thread count
+    /// against the loaded model; real transcriptions always go through
+    /// `transcribe_inner`/`decode`.
+    pub fn benchmark_decode(&self, audio: &[f32], n_threads: i32) -> anyhow::Result<()> {
+        self.decode(
+            audio,
+            "en",
+            None,
+            false,
+            None,
+            self.resolved_strategy(),
+            Some(n_threads),
+        )
+        .map(|_| ())
+    }
+
+    fn resolved_strategy(&self) -> SamplingStrategy {
+        match self.params.sampling_strategy {
+            crate::config::SttSamplingStrategy::Greedy => SamplingStrategy::Greedy {
+                best_of: self.params.best_of,
+            },
+            crate::config::SttSamplingStrategy::BeamSearch => SamplingStrategy::BeamSearch {
+                beam_size: self.params.beam_size,
+                patience: 1.0,
+            },
+        }
+    }
+}
+
+impl SttEngine for WhisperEngine {
+    /// Transcribe 16kHz mono f32 audio into text in the configured language.
+    /// `language`: ISO-639-1 code ("th", "en", ...) or "auto" for Whisper detection.
+    fn transcribe(&self, audio: &[f32], language: &str) -> anyhow::Result<TranscriptionResult> {
+        self.transcribe_inner(audio, language, None, false, None)
+    }
+
+    fn transcribe_cancelable(
+        &self,
+        audio: &[f32],
+        language: &str,
+        cancel: &Arc<AtomicBool>,
+    ) -> anyhow::Result<TranscriptionResult> {
+        self.transcribe_inner(audio, language, None, false, Some(cancel.clone()))
+    }
+
+    fn transcribe_primed(
+        &self,
+        audio: &[f32],
+        language: &str,
+        initial_prompt: Option<&str>,
+        translate: bool,
+        cancel: &Arc<AtomicBool>,
+    ) -> anyhow::Result<TranscriptionResult> {
+        self.transcribe_inner(
+            audio,
+            language,
+            initial_prompt,
+            translate,
+            Some(cancel.clone()),
+        )
+    }
+}
+
+impl WhisperEngine {
+    fn transcribe_inner(
+        &self,
+        audio: &[f32],
+        language: &str,
+        initial_prompt: Option<&str>,
+        translate: bool,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> anyhow::Result<TranscriptionResult> {
+        let start = std::time::Instant::now();
+        let audio_duration_secs = audio.len() as f32 / 16_000.0;
+
+        let mut result = self.decode(
+            audio,
+            language,
+            initial_prompt,
+            translate,
+            cancel.clone(),
+            self.resolved_strategy(),
+            None,
+        )?;
+
+        // Greedy decoding occasionally gets stuck in a repetition loop or
+        // runs on well past where the audio ended. Beam search costs more
+        // but rarely repeats the same failure, so it's worth one retry
+        // before giving up and returning the bad transcript. Skip the retry
+        // if beam search is already the configured default — it would just
+        // reproduce the same failure.
+        if sanity::is_pathological(&result, audio_duration_secs)
+            && self.params.sampling_strategy != crate::config::SttSamplingStrategy::BeamSearch
+        {
+            log::warn!("Whisper output looked pathological, retrying with beam search");
+            result = self.decode(
+                audio,
+                language,
+                initial_prompt,
+                translate,
+                cancel,
+                SamplingStrategy::BeamSearch {
+                    beam_size: self.params.beam_size,
+                    patience: 1.0,
+                },
+                None,
+            )?;
+        }
+
+        Ok(TranscriptionResult {
+            duration_ms: start.elapsed().as_millis(),
+            ..result
+        })
+    }
+
+    fn decode(
+        &self,
+        audio: &[f32],
+        language: &str,
+        initial_prompt: Option<&str>,
+        translate: bool,
+        cancel: Option<Arc<AtomicBool>>,
+        strategy: SamplingStrategy,
+        n_threads_override: Option<i32>,
+    ) -> anyhow::Result<TranscriptionResult> {
+        let mut params = FullParams::new(strategy);
+
+        let lang_opt = if language == "auto" {
+            None
+        } else {
+            Some(language)
+        };
+        params.set_language(lang_opt);
+        params.set_translate(translate);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        let n_threads = n_threads_override.unwrap_or_else(|| {
+            if self.params.n_threads > 0 {
+                self.params.n_threads
+            } else {
+                Self::optimal_threads()
+            }
+        });
+        params.set_n_threads(n_threads);
+        params.set_single_segment(false);
+        params.set_token_timestamps(self.params.word_timestamps);
+        let prompt = initial_prompt.or(self.params.initial_prompt.as_deref());
+        if let Some(prompt) = prompt {
+            params.set_initial_prompt(prompt);
+        }
+        if let Some(cancel) = cancel {
+            params.set_abort_callback_safe(move || cancel.load(Ordering::Relaxed));
+        }
+
+        let mut state = self
+            .ctx
+            .create_state()
+            .map_err(|e| anyhow::anyhow!("Failed to create state: {}", e))?;
+
+        state
+            .full(params, audio)
+            .map_err(|e| anyhow::anyhow!("Transcription failed: {}", e))?;
+
+        let num_segments = state
+            .full_n_segments()
+            .map_err(|e| anyhow::anyhow!("Failed to get segments: {}", e))?;
+
+        let mut segments = Vec::new();
+
+        for i in 0..num_segments {
+            let segment_text = state
+                .full_get_segment_text(i)
+                .map_err(|e| anyhow::anyhow!("Failed to get segment {}: {}", i, e))?;
+            let t0 = state.full_get_segment_t0(i).unwrap_or(0);
+            let t1 = state.full_get_segment_t1(i).unwrap_or(0);
+            let avg_confidence = Self::segment_avg_confidence(&state, i);
+
+            let is_english = super::codeswitch::is_english_segment(&segment_text);
+            let words = if self.params.word_timestamps {
+                Self::segment_words(&state, i)
+            } else {
+                Vec::new()
+            };
+            segments.push(Segment {
+                text: segment_text,
+                start_ms: t0 as u64 * 10,
+                end_ms: t1 as u64 * 10,
+                avg_confidence,
+                is_english,
+                words,
+                speaker: None,
+            });
+        }
+
+        let (text, segments) =
+            super::hallucination::filter_segments(audio, segments, &self.hallucination_blocklist);
+
+        Ok(TranscriptionResult {
+            text,
+            segments,
+            duration_ms: 0,
+            model_size: self.model_size,
+        })
+    }
+}
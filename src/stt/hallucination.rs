@@ -0,0 +1,69 @@
+//! Filters known Whisper hallucinations — phantom phrases the model emits
+//! over silence or background noise (e.g. "ขอบคุณครับ", "Thanks for
+//! watching") — out of a transcription's segments.
+
+use super::engine::Segment;
+
+/// Sample rate segment timestamps are computed against; matches the rest of
+/// the STT pipeline, which only ever sees 16kHz mono audio.
+const SAMPLE_RATE: usize = 16_000;
+
+/// A segment's audio region with RMS below this is treated as silence even
+/// though Whisper transcribed text for it.
+const SILENCE_RMS_THRESHOLD: f32 = 0.01;
+
+/// Stock phantom phrases whisper.cpp is known to emit over silence or noise,
+/// independent of `AppSettings::stt_language`. Seeded into
+/// `AppSettings::hallucination_blocklist` by default; users can add more.
+pub const DEFAULT_BLOCKLIST: &[&str] = &[
+    "ขอบคุณครับ",
+    "ขอบคุณค่ะ",
+    "ขอบคุณที่รับชม",
+    "subscribe",
+    "thanks for watching",
+    "thank you for watching",
+];
+
+fn segment_rms(audio: &[f32], segment: &Segment) -> f32 {
+    let start = (segment.start_ms as usize * SAMPLE_RATE / 1000).min(audio.len());
+    let end = (segment.end_ms as usize * SAMPLE_RATE / 1000).min(audio.len());
+    if start >= end {
+        return 0.0;
+    }
+    let region = &audio[start..end];
+    (region.iter().map(|s| s * s).sum::<f32>() / region.len() as f32).sqrt()
+}
+
+fn is_blocklisted(text: &str, blocklist: &[String]) -> bool {
+    let normalized = text
+        .trim()
+        .trim_matches(|c: char| c.is_ascii_punctuation())
+        .to_lowercase();
+    blocklist
+        .iter()
+        .any(|phrase| normalized == phrase.trim().to_lowercase())
+}
+
+/// Drops segments whose audio region is near-silent or whose text matches
+/// `blocklist`, then rejoins the surviving segments' text into the
+/// transcript. `audio` must be the same buffer
+/// the segments' timestamps were computed against.
+pub fn filter_segments(
+    audio: &[f32],
+    segments: Vec<Segment>,
+    blocklist: &[String],
+) -> (String, Vec<Segment>) {
+    let kept: Vec<Segment> = segments
+        .into_iter()
+        .filter(|s| {
+            segment_rms(audio, s) >= SILENCE_RMS_THRESHOLD && !is_blocklisted(&s.text, blocklist)
+        })
+        .collect();
+
+    let mut text = String::new();
+    for segment in &kept {
+        text.push_str(&segment.text);
+    }
+
+    (text.trim().to_string(), kept)
+}
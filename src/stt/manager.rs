@@ -0,0 +1,80 @@
+//! Runtime model hot-swap: load and switch the active `WhisperEngine`
+//! without restarting the pipeline or blocking the async runtime.
+
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+
+use super::downloader::ModelDownloader;
+use super::engine::{TranscribeParams, WhisperEngine};
+use super::model::{check_ram_budget, find_model, verify_integrity, ModelInfo};
+
+/// Holds the currently active `WhisperEngine` behind a lock so the pipeline
+/// can swap models at runtime. Transcriptions already in flight hold their
+/// own `Arc<WhisperEngine>` clone taken before the swap, so a `switch` never
+/// interrupts work that's already running — it only affects the next call
+/// to `current()`.
+pub struct ModelManager {
+    active: RwLock<Arc<WhisperEngine>>,
+    /// Carried over to each engine loaded by `switch`, since `WhisperEngine`
+    /// doesn't persist it anywhere else.
+    hallucination_blocklist: Vec<String>,
+}
+
+impl ModelManager {
+    pub fn new(engine: WhisperEngine, hallucination_blocklist: Vec<String>) -> Self {
+        Self {
+            active: RwLock::new(Arc::new(engine)),
+            hallucination_blocklist,
+        }
+    }
+
+    /// The currently active engine. Clone the returned `Arc` before starting
+    /// a transcription so a concurrent `switch` can't change the engine out
+    /// from under it mid-run.
+    pub fn current(&self) -> Arc<WhisperEngine> {
+        self.active.read().clone()
+    }
+
+    /// Load `model_id` from the registry and swap it in. Loads the whole
+    /// model file, so call this from `spawn_blocking`.
+    pub fn switch(
+        &self,
+        model_id: &str,
+        use_gpu: bool,
+        params: TranscribeParams,
+    ) -> anyhow::Result<()> {
+        let model = find_model(model_id)
+            .ok_or_else(|| anyhow::anyhow!("Unknown STT model: {}", model_id))?;
+        check_ram_budget(model)?;
+
+        if model.is_downloaded() {
+            if let Err(e) = verify_integrity(model) {
+                log::warn!("{} — re-downloading", e);
+                std::fs::remove_file(model.local_path()).ok();
+                Self::redownload(model)?;
+            }
+        } else {
+            Self::redownload(model)?;
+        }
+
+        let engine = WhisperEngine::with_params(
+            &model.local_path().to_string_lossy(),
+            use_gpu,
+            self.hallucination_blocklist.clone(),
+            params,
+        )?;
+        *self.active.write() = Arc::new(engine);
+        Ok(())
+    }
+
+    /// Fetch a missing or corrupted model file fresh. `switch` always runs
+    /// inside `spawn_blocking` (see its doc comment), so a Tokio runtime
+    /// context is guaranteed to be available here.
+    fn redownload(model: &ModelInfo) -> anyhow::Result<()> {
+        let downloader = ModelDownloader::default();
+        tokio::runtime::Handle::current()
+            .block_on(downloader.download(model, |_| {}))
+            .map_err(|e| anyhow::anyhow!("Re-download of {} failed: {}", model.display_name, e))
+    }
+}
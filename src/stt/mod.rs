@@ -0,0 +1,30 @@
+//! Speech-to-text: Whisper integration and model management.
+
+pub mod calibration;
+pub mod chunker;
+pub mod codeswitch;
+pub mod diarize;
+pub mod downloader;
+pub mod engine;
+pub mod hallucination;
+pub mod manager;
+pub mod model;
+pub mod remote;
+pub mod sanity;
+pub mod sha256;
+pub mod sysmem;
+pub mod vosk;
+
+pub use chunker::{default_worker_count, transcribe_chunked, transcribe_chunked_parallel};
+pub use downloader::{DownloadError, DownloadProgress, ModelDownloader};
+pub use engine::{
+    Segment, SttBackend, SttEngine, TranscribeParams, TranscriptionResult, WhisperEngine,
+    WordTiming,
+};
+pub use manager::ModelManager;
+pub use model::{
+    check_ram_budget, find_model, list_local_models, models_for_language, verify_integrity,
+    ModelInfo, ModelSize, Quantization, ALL_MODELS,
+};
+pub use remote::RemoteSttEngine;
+pub use vosk::VoskEngine;
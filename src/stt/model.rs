@@ -0,0 +1,297 @@
+//! STT model registry and path management.
+
+use crate::config::AppPaths;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModelSize {
+    Small,
+    Medium,
+    Large,
+}
+
+/// GGML quantization level. `Q5_0`/`Q8_0` trade transcription accuracy for a
+/// smaller file and RAM footprint, letting a `Medium`-size model run on
+/// machines that can't fit the full-precision file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantization {
+    F16,
+    Q5_0,
+    Q8_0,
+}
+
+impl Quantization {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::F16 => "F16",
+            Self::Q5_0 => "Q5_0 (smaller, less RAM)",
+            Self::Q8_0 => "Q8_0 (balanced)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ModelInfo {
+    pub id: &'static str,
+    pub display_name: &'static str,
+    pub size: ModelSize,
+    pub quantization: Quantization,
+    pub file_name: &'static str,
+    pub file_size_mb: u64,
+    pub ram_required_mb: u64,
+    pub source_url: &'static str,
+    /// ISO-639-1 language code this model is optimised for, or "multilingual".
+    pub language: &'static str,
+    /// Expected SHA256 hex digest of the downloaded file, checked by
+    /// `verify_integrity` after download and before load. Empty means the
+    /// checksum for that release hasn't been recorded yet — verification is
+    /// skipped rather than failing every load against an unknown hash.
+    pub sha256: &'static str,
+}
+
+impl ModelInfo {
+    pub fn local_path(&self) -> std::path::PathBuf {
+        AppPaths::models_dir().join(self.file_name)
+    }
+
+    pub fn is_downloaded(&self) -> bool {
+        self.local_path().exists()
+    }
+}
+
+/// Hash `model`'s local file and compare it against `model.sha256`. `Ok(())`
+/// both when the hashes match and when no expected hash is on record;
+/// `Err` means the file is present but corrupt (or the wrong file
+/// entirely), which the caller should treat as if the download never
+/// happened.
+///
+/// TODO: every `ModelInfo` in `THAI_MODELS`/`WHISPER_MODELS` still has an
+/// empty `sha256` — none of the upstream release hashes have been sourced
+/// and recorded yet. Until that's done, this and `downloader::download`
+/// only log a warning rather than silently skipping, so the gap is at
+/// least visible instead of looking like verification ran and passed.
+pub fn verify_integrity(model: &ModelInfo) -> anyhow::Result<()> {
+    if model.sha256.is_empty() {
+        log::warn!(
+            "{} has no recorded sha256 — skipping integrity check",
+            model.display_name
+        );
+        return Ok(());
+    }
+
+    let actual = super::sha256::hash_file(&model.local_path())
+        .map_err(|e| anyhow::anyhow!("Failed to hash {}: {}", model.display_name, e))?;
+    if actual != model.sha256 {
+        anyhow::bail!(
+            "{} is corrupted (sha256 {} does not match expected {})",
+            model.display_name,
+            actual,
+            model.sha256
+        );
+    }
+    Ok(())
+}
+
+/// Thai-optimised models (Thonburian Whisper — fine-tuned on Thai, ICNLSP 2024).
+pub const THAI_MODELS: &[ModelInfo] = &[
+    ModelInfo {
+        id: "thonburian-small",
+        display_name: "Thonburian Whisper Small (Thai)",
+        size: ModelSize::Small,
+        quantization: Quantization::F16,
+        file_name: "ggml-thonburian-small.bin",
+        file_size_mb: 242,
+        ram_required_mb: 900,
+        source_url: "https://huggingface.co/biodatlab/whisper-small-th-combined",
+        language: "th",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "thonburian-medium",
+        display_name: "Thonburian Whisper Medium (Thai) [Recommended]",
+        size: ModelSize::Medium,
+        quantization: Quantization::F16,
+        file_name: "ggml-thonburian-medium.bin",
+        file_size_mb: 769,
+        ram_required_mb: 3000,
+        source_url: "https://huggingface.co/biodatlab/whisper-th-medium-combined",
+        language: "th",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "thonburian-medium-q5",
+        display_name: "Thonburian Whisper Medium Q5_0 (Thai, low RAM)",
+        size: ModelSize::Medium,
+        quantization: Quantization::Q5_0,
+        file_name: "ggml-thonburian-medium-q5_0.bin",
+        file_size_mb: 280,
+        ram_required_mb: 1200,
+        source_url: "https://huggingface.co/biodatlab/whisper-th-medium-combined",
+        language: "th",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "thonburian-medium-q8",
+        display_name: "Thonburian Whisper Medium Q8_0 (Thai, low RAM)",
+        size: ModelSize::Medium,
+        quantization: Quantization::Q8_0,
+        file_name: "ggml-thonburian-medium-q8_0.bin",
+        file_size_mb: 420,
+        ram_required_mb: 1700,
+        source_url: "https://huggingface.co/biodatlab/whisper-th-medium-combined",
+        language: "th",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "thonburian-large",
+        display_name: "Thonburian Whisper Large (Thai)",
+        size: ModelSize::Large,
+        quantization: Quantization::F16,
+        file_name: "ggml-thonburian-large.bin",
+        file_size_mb: 1500,
+        ram_required_mb: 6000,
+        source_url: "https://huggingface.co/biodatlab/whisper-th-large-combined",
+        language: "th",
+        sha256: "", // checksum not yet recorded for this release
+    },
+];
+
+/// Standard Whisper models (99-language multilingual).
+pub const WHISPER_MODELS: &[ModelInfo] = &[
+    ModelInfo {
+        id: "whisper-small",
+        display_name: "Whisper Small (Multilingual, 99 langs)",
+        size: ModelSize::Small,
+        quantization: Quantization::F16,
+        file_name: "ggml-whisper-small.bin",
+        file_size_mb: 244,
+        ram_required_mb: 1000,
+        source_url: "https://huggingface.co/ggerganov/whisper.cpp",
+        language: "multilingual",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "whisper-medium",
+        display_name: "Whisper Medium (Multilingual, 99 langs)",
+        size: ModelSize::Medium,
+        quantization: Quantization::F16,
+        file_name: "ggml-whisper-medium.bin",
+        file_size_mb: 769,
+        ram_required_mb: 3000,
+        source_url: "https://huggingface.co/ggerganov/whisper.cpp",
+        language: "multilingual",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "whisper-medium-q5",
+        display_name: "Whisper Medium Q5_0 (Multilingual, low RAM)",
+        size: ModelSize::Medium,
+        quantization: Quantization::Q5_0,
+        file_name: "ggml-whisper-medium-q5_0.bin",
+        file_size_mb: 280,
+        ram_required_mb: 1200,
+        source_url: "https://huggingface.co/ggerganov/whisper.cpp",
+        language: "multilingual",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "whisper-medium-q8",
+        display_name: "Whisper Medium Q8_0 (Multilingual, low RAM)",
+        size: ModelSize::Medium,
+        quantization: Quantization::Q8_0,
+        file_name: "ggml-whisper-medium-q8_0.bin",
+        file_size_mb: 420,
+        ram_required_mb: 1700,
+        source_url: "https://huggingface.co/ggerganov/whisper.cpp",
+        language: "multilingual",
+        sha256: "", // checksum not yet recorded for this release
+    },
+    ModelInfo {
+        id: "whisper-large-v3",
+        display_name: "Whisper Large-v3 (Multilingual, 99 langs)",
+        size: ModelSize::Large,
+        quantization: Quantization::F16,
+        file_name: "ggml-whisper-large-v3.bin",
+        file_size_mb: 1550,
+        ram_required_mb: 6000,
+        source_url: "https://huggingface.co/ggerganov/whisper.cpp",
+        language: "multilingual",
+        sha256: "", // checksum not yet recorded for this release
+    },
+];
+
+/// Combined registry of all available models.
+pub const ALL_MODELS: &[&[ModelInfo]] = &[THAI_MODELS, WHISPER_MODELS];
+
+/// Recommended models for a given language code.
+/// "th" → Thonburian models; everything else → standard Whisper.
+pub fn models_for_language(language: &str) -> &'static [ModelInfo] {
+    if language == "th" {
+        THAI_MODELS
+    } else {
+        WHISPER_MODELS
+    }
+}
+
+/// Look up a model by its registry id.
+pub fn find_model(id: &str) -> Option<&'static ModelInfo> {
+    ALL_MODELS
+        .iter()
+        .flat_map(|group| group.iter())
+        .find(|m| m.id == id)
+}
+
+/// Refuse to load `model` if less RAM is available than it needs, so the
+/// caller can surface an explanatory error instead of letting the OS
+/// OOM-kill the process or swap-thrash the machine. Suggests the largest
+/// model (preferring the same language) that would actually fit. A `None`
+/// from `sysmem::available_mb` (RAM couldn't be determined) skips the
+/// check entirely rather than blocking the load.
+pub fn check_ram_budget(model: &ModelInfo) -> anyhow::Result<()> {
+    let Some(available) = super::sysmem::available_mb() else {
+        return Ok(());
+    };
+    if available >= model.ram_required_mb {
+        return Ok(());
+    }
+
+    let fits = |m: &&ModelInfo| m.ram_required_mb <= available;
+    let suggestion = models_for_language(model.language)
+        .iter()
+        .filter(fits)
+        .max_by_key(|m| m.ram_required_mb)
+        .or_else(|| {
+            ALL_MODELS
+                .iter()
+                .flat_map(|group| group.iter())
+                .filter(fits)
+                .max_by_key(|m| m.ram_required_mb)
+        });
+
+    Err(match suggestion {
+        Some(m) => anyhow::anyhow!(
+            "{} needs ~{} MB RAM but only {} MB is available; try \"{}\" (~{} MB) instead",
+            model.display_name,
+            model.ram_required_mb,
+            available,
+            m.display_name,
+            m.ram_required_mb
+        ),
+        None => anyhow::anyhow!(
+            "{} needs ~{} MB RAM but only {} MB is available, and no smaller model is registered",
+            model.display_name,
+            model.ram_required_mb,
+            available
+        ),
+    })
+}
+
+/// Every registered model whose file is already downloaded, for the
+/// `--benchmark` mode and model-switcher UI to offer without triggering a
+/// download.
+pub fn list_local_models() -> Vec<&'static ModelInfo> {
+    ALL_MODELS
+        .iter()
+        .flat_map(|group| group.iter())
+        .filter(|m| m.is_downloaded())
+        .collect()
+}
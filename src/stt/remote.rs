@@ -0,0 +1,164 @@
+//! STT backend that offloads transcription to a remote whisper.cpp `server`
+//! build or any OpenAI-compatible `/v1/audio/transcriptions` endpoint,
+//! instead of decoding locally with `WhisperEngine`. Both accept a
+//! multipart file upload and return a JSON transcript, so a single
+//! implementation covers both. Configured via `AppSettings::stt_remote_url`
+//! / `stt_remote_api_key` and dispatched to by
+//! `pipeline::PipelineOrchestrator::active_stt` when
+//! `AppSettings::stt_remote_enabled` is set.
+//!
+//! `reqwest`'s `multipart` feature isn't enabled in this workspace, so the
+//! form-data body is assembled by hand — RFC 2388 multipart encoding is a
+//! small, stable format, not worth a new Cargo feature for.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use super::engine::{Segment, SttEngine, TranscriptionResult};
+use super::model::ModelSize;
+
+const BOUNDARY: &str = "----voice-to-text-boundary";
+
+/// Talks to a remote whisper.cpp `server` or OpenAI-compatible transcription
+/// endpoint over HTTP. Its trait methods are synchronous, like
+/// `WhisperEngine`'s, so the pipeline can keep calling `SttEngine` from
+/// inside `spawn_blocking` regardless of which engine is active; internally
+/// they bridge to async `reqwest` calls via `Handle::current().block_on`,
+/// the same pattern `ModelManager::redownload` uses for its download call.
+pub struct RemoteSttEngine {
+    base_url: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+impl RemoteSttEngine {
+    pub fn new(base_url: String, api_key: Option<String>) -> Self {
+        Self {
+            base_url,
+            api_key,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn multipart_body(
+        audio: &[f32],
+        language: &str,
+        prompt: Option<&str>,
+        translate: bool,
+    ) -> Vec<u8> {
+        let wav = crate::audio::recording_store::encode_wav_16k_mono(audio);
+
+        let mut body = Vec::with_capacity(wav.len() + 512);
+        body.extend_from_slice(format!("--{BOUNDARY}\r\n").as_bytes());
+        body.extend_from_slice(
+            b"Content-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\n",
+        );
+        body.extend_from_slice(b"Content-Type: audio/wav\r\n\r\n");
+        body.extend_from_slice(&wav);
+        body.extend_from_slice(b"\r\n");
+
+        if language != "auto" && !translate {
+            Self::push_field(&mut body, "language", language);
+        }
+        if let Some(prompt) = prompt {
+            Self::push_field(&mut body, "prompt", prompt);
+        }
+
+        body.extend_from_slice(format!("--{BOUNDARY}--\r\n").as_bytes());
+        body
+    }
+
+    fn push_field(body: &mut Vec<u8>, name: &str, value: &str) {
+        body.extend_from_slice(format!("--{BOUNDARY}\r\n").as_bytes());
+        body.extend_from_slice(
+            format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n").as_bytes(),
+        );
+        body.extend_from_slice(value.as_bytes());
+        body.extend_from_slice(b"\r\n");
+    }
+
+    async fn transcribe_remote(
+        &self,
+        audio: &[f32],
+        language: &str,
+        prompt: Option<&str>,
+        translate: bool,
+    ) -> anyhow::Result<TranscriptionResult> {
+        let start = std::time::Instant::now();
+        let endpoint = if translate {
+            "v1/audio/translations"
+        } else {
+            "v1/audio/transcriptions"
+        };
+        let url = format!("{}/{}", self.base_url.trim_end_matches('/'), endpoint);
+        let body = Self::multipart_body(audio, language, prompt, translate);
+
+        let mut req = self
+            .client
+            .post(&url)
+            .header(
+                "Content-Type",
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            )
+            .body(body);
+        if let Some(key) = &self.api_key {
+            req = req.bearer_auth(key);
+        }
+
+        let response: serde_json::Value = req
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| anyhow::anyhow!("Remote STT server returned an error: {}", e))?
+            .json()
+            .await?;
+        let text = response["text"].as_str().unwrap_or_default().to_string();
+
+        Ok(TranscriptionResult {
+            segments: vec![Segment {
+                text: text.clone(),
+                start_ms: 0,
+                end_ms: (audio.len() as u64 * 1000) / 16_000,
+                avg_confidence: 1.0,
+                is_english: false,
+                words: Vec::new(),
+                speaker: None,
+            }],
+            text,
+            duration_ms: start.elapsed().as_millis(),
+            // The remote server's actual model size isn't reported by
+            // either protocol; `Medium` is the closest stand-in for
+            // downstream code that only uses this to size UI hints.
+            model_size: ModelSize::Medium,
+        })
+    }
+}
+
+impl SttEngine for RemoteSttEngine {
+    fn transcribe(&self, audio: &[f32], language: &str) -> anyhow::Result<TranscriptionResult> {
+        tokio::runtime::Handle::current()
+            .block_on(self.transcribe_remote(audio, language, None, false))
+    }
+
+    /// The remote protocols have no way to abort mid-request, but they do
+    /// support a `prompt` field and a
+    /// separate translate endpoint, so those are honored; `cancel` is
+    /// ignored, matching the trait default's documented fallback for
+    /// engines that can't abort in-flight work.
+    fn transcribe_primed(
+        &self,
+        audio: &[f32],
+        language: &str,
+        initial_prompt: Option<&str>,
+        translate: bool,
+        cancel: &Arc<AtomicBool>,
+    ) -> anyhow::Result<TranscriptionResult> {
+        let _ = cancel;
+        tokio::runtime::Handle::current().block_on(self.transcribe_remote(
+            audio,
+            language,
+            initial_prompt,
+            translate,
+        ))
+    }
+}
@@ -0,0 +1,44 @@
+//! Detects pathological Whisper output — stuck-token repetition loops or
+//! text far too long for the audio it came from — so the caller can retry
+//! with different decoding parameters instead of surfacing garbage.
+
+use super::engine::TranscriptionResult;
+
+/// A word repeated this many times in a row is treated as a decoder loop
+/// rather than legitimate repeated speech ("no no no no...").
+const MAX_CONSECUTIVE_REPEATS: usize = 8;
+
+/// Output longer than this many characters per second of audio is treated
+/// as runaway generation — normal speech, even fast Thai, stays well under
+/// half this.
+const MAX_CHARS_PER_SEC: f32 = 25.0;
+
+/// True if `result.text` looks like a decoder failure rather than a genuine
+/// transcription of `audio_duration_secs` of audio.
+pub fn is_pathological(result: &TranscriptionResult, audio_duration_secs: f32) -> bool {
+    has_stuck_repetition(&result.text) || is_absurdly_long(&result.text, audio_duration_secs)
+}
+
+fn has_stuck_repetition(text: &str) -> bool {
+    let mut run = 1;
+    let mut prev: Option<&str> = None;
+    for word in text.split_whitespace() {
+        if prev == Some(word) {
+            run += 1;
+            if run >= MAX_CONSECUTIVE_REPEATS {
+                return true;
+            }
+        } else {
+            run = 1;
+        }
+        prev = Some(word);
+    }
+    false
+}
+
+fn is_absurdly_long(text: &str, audio_duration_secs: f32) -> bool {
+    if audio_duration_secs <= 0.0 {
+        return false;
+    }
+    text.chars().count() as f32 / audio_duration_secs > MAX_CHARS_PER_SEC
+}
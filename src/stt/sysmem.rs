@@ -0,0 +1,114 @@
+//! Available system RAM, in megabytes, used by `manager::ModelManager` to
+//! refuse loading a model too big for the machine instead of letting the OS
+//! OOM-kill the process or swap-thrash it into unusability. Hand-rolled per
+//! platform (same rationale as `power`) rather than adding a
+//! `sysinfo`-style crate for a single number.
+
+/// Returns `None` if available RAM can't be determined, in which case the
+/// caller should skip the check rather than block loading.
+pub fn available_mb() -> Option<u64> {
+    imp::available_mb()
+}
+
+#[cfg(target_os = "linux")]
+mod imp {
+    pub fn available_mb() -> Option<u64> {
+        let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+        for line in meminfo.lines() {
+            if let Some(rest) = line.strip_prefix("MemAvailable:") {
+                let kb: u64 = rest.trim().trim_end_matches(" kB").trim().parse().ok()?;
+                return Some(kb / 1024);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod imp {
+    use std::process::Command;
+
+    /// `vm_stat`'s first line is `Mach Virtual Memory Statistics: (page
+    /// size of 4096 bytes)`; every following line is `Label: N.` in pages.
+    /// Available RAM is approximated as free + inactive + speculative pages,
+    /// matching Activity Monitor's "Memory Used" complement.
+    pub fn available_mb() -> Option<u64> {
+        let output = Command::new("vm_stat").output().ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut lines = stdout.lines();
+
+        let header = lines.next()?;
+        let page_size: u64 = header
+            .split("page size of ")
+            .nth(1)?
+            .split(' ')
+            .next()?
+            .parse()
+            .ok()?;
+
+        let mut free_pages = 0u64;
+        for line in lines {
+            let label = line.split(':').next().unwrap_or("");
+            if matches!(label, "Pages free" | "Pages inactive" | "Pages speculative") {
+                let count: u64 = line
+                    .split(':')
+                    .nth(1)?
+                    .trim()
+                    .trim_end_matches('.')
+                    .parse()
+                    .ok()?;
+                free_pages += count;
+            }
+        }
+
+        Some(free_pages * page_size / 1024 / 1024)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod imp {
+    #[repr(C)]
+    struct MemoryStatusEx {
+        length: u32,
+        memory_load: u32,
+        total_phys: u64,
+        avail_phys: u64,
+        total_page_file: u64,
+        avail_page_file: u64,
+        total_virtual: u64,
+        avail_virtual: u64,
+        avail_extended_virtual: u64,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GlobalMemoryStatusEx(status: *mut MemoryStatusEx) -> i32;
+    }
+
+    pub fn available_mb() -> Option<u64> {
+        let mut status = MemoryStatusEx {
+            length: std::mem::size_of::<MemoryStatusEx>() as u32,
+            memory_load: 0,
+            total_phys: 0,
+            avail_phys: 0,
+            total_page_file: 0,
+            avail_page_file: 0,
+            total_virtual: 0,
+            avail_virtual: 0,
+            avail_extended_virtual: 0,
+        };
+
+        let ok = unsafe { GlobalMemoryStatusEx(&mut status) };
+        if ok == 0 {
+            return None;
+        }
+        Some(status.avail_phys / 1024 / 1024)
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+mod imp {
+    pub fn available_mb() -> Option<u64> {
+        None
+    }
+}
@@ -0,0 +1,75 @@
+//! STT backend for a self-hosted Vosk (or protocol-compatible) recognizer
+//! server, as an alternative to `WhisperEngine`/`RemoteSttEngine` for
+//! low-end machines or setups that already run a Vosk model on a
+//! GPU-equipped box. See `AppSettings::stt_vosk_enabled`/`stt_vosk_url` and
+//! `pipeline::PipelineOrchestrator::active_stt`.
+//!
+//! Vosk's own server speaks a streaming WebSocket protocol, which would
+//! need a new dependency (`tokio-tungstenite`) to speak from this codebase.
+//! Since most self-hosted Vosk deployments front that server with a thin
+//! HTTP wrapper for exactly this kind of one-shot use, `VoskEngine` instead
+//! POSTs a WAV file to a single HTTP endpoint and reads back a JSON
+//! `{"text": "..."}` response — the same shape `RemoteSttEngine` already
+//! expects from a whisper.cpp/OpenAI-compatible server, so both engines
+//! stay easy to reason about side by side.
+
+use super::engine::{Segment, SttEngine, TranscriptionResult};
+use super::model::ModelSize;
+
+pub struct VoskEngine {
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl VoskEngine {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            base_url,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn transcribe_remote(&self, audio: &[f32]) -> anyhow::Result<TranscriptionResult> {
+        let start = std::time::Instant::now();
+        let wav = crate::audio::recording_store::encode_wav_16k_mono(audio);
+
+        let response: serde_json::Value = self
+            .client
+            .post(self.base_url.trim_end_matches('/'))
+            .header("Content-Type", "audio/wav")
+            .body(wav)
+            .send()
+            .await?
+            .error_for_status()
+            .map_err(|e| anyhow::anyhow!("Vosk server returned an error: {}", e))?
+            .json()
+            .await?;
+        let text = response["text"].as_str().unwrap_or_default().to_string();
+
+        Ok(TranscriptionResult {
+            segments: vec![Segment {
+                text: text.clone(),
+                start_ms: 0,
+                end_ms: (audio.len() as u64 * 1000) / 16_000,
+                avg_confidence: 1.0,
+                is_english: false,
+                words: Vec::new(),
+                speaker: None,
+            }],
+            text,
+            duration_ms: start.elapsed().as_millis(),
+            // Vosk models aren't sized the same way as Whisper's; `Small`
+            // is the closest stand-in for downstream UI hints.
+            model_size: ModelSize::Small,
+        })
+    }
+}
+
+impl SttEngine for VoskEngine {
+    /// Vosk doesn't take a language hint per request (it's baked into the
+    /// server's loaded model), so `language` is unused here.
+    fn transcribe(&self, audio: &[f32], language: &str) -> anyhow::Result<TranscriptionResult> {
+        let _ = language;
+        tokio::runtime::Handle::current().block_on(self.transcribe_remote(audio))
+    }
+}
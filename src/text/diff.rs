@@ -0,0 +1,85 @@
+//! Word-level diff between the raw transcript and the LLM-corrected text,
+//! for the Result panel's diff toggle. Texts here are at most a few
+//! sentences, so a plain O(n*m) LCS table is fine — no need for anything
+//! Myers-style.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffOp {
+    Equal(String),
+    Insert(String),
+    Delete(String),
+}
+
+/// Computes a word-level diff turning `original` into `revised`, returning
+/// the ops in order. Consecutive same-kind words are merged into one op.
+pub fn diff_words(original: &str, revised: &str) -> Vec<DiffOp> {
+    let a: Vec<&str> = original.split_whitespace().collect();
+    let b: Vec<&str> = revised.split_whitespace().collect();
+
+    let mut lcs = vec![vec![0u32; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            push_word(&mut ops, DiffKind::Equal, a[i]);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            push_word(&mut ops, DiffKind::Delete, a[i]);
+            i += 1;
+        } else {
+            push_word(&mut ops, DiffKind::Insert, b[j]);
+            j += 1;
+        }
+    }
+    while i < a.len() {
+        push_word(&mut ops, DiffKind::Delete, a[i]);
+        i += 1;
+    }
+    while j < b.len() {
+        push_word(&mut ops, DiffKind::Insert, b[j]);
+        j += 1;
+    }
+
+    ops
+}
+
+#[derive(PartialEq)]
+enum DiffKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+fn push_word(ops: &mut Vec<DiffOp>, kind: DiffKind, word: &str) {
+    let matches_last = matches!(
+        (ops.last(), &kind),
+        (Some(DiffOp::Equal(_)), DiffKind::Equal)
+            | (Some(DiffOp::Insert(_)), DiffKind::Insert)
+            | (Some(DiffOp::Delete(_)), DiffKind::Delete)
+    );
+    if matches_last {
+        let text = match ops.last_mut().unwrap() {
+            DiffOp::Equal(t) | DiffOp::Insert(t) | DiffOp::Delete(t) => t,
+        };
+        text.push(' ');
+        text.push_str(word);
+        return;
+    }
+    let text = word.to_string();
+    ops.push(match kind {
+        DiffKind::Equal => DiffOp::Equal(text),
+        DiffKind::Insert => DiffOp::Insert(text),
+        DiffKind::Delete => DiffOp::Delete(text),
+    });
+}
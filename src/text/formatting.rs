@@ -0,0 +1,142 @@
+//! Per-app output formatting: reshapes the final transcript to suit
+//! whichever application currently has focus (e.g. a terminal doesn't want
+//! embedded newlines) right before `inject` sends it over. Rules are
+//! matched the same way `AppProfile` picks a context profile — case-
+//! insensitively against the focused window's title or process name.
+
+use serde::Deserialize;
+
+use crate::config::AppPaths;
+use crate::inject::FocusedWindow;
+
+/// What to do to the transcript before it's injected into a matched app.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormatAction {
+    /// Join all lines into one, e.g. for shells where a newline submits
+    /// the command early.
+    StripNewlines,
+    /// Put each sentence on its own line, e.g. for chat apps where several
+    /// short messages read more naturally than one block.
+    SplitSentences,
+    /// Prefix every line with a comment marker, e.g. for pasting notes
+    /// into an IDE without breaking the surrounding code.
+    CommentPrefix(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FormatRule {
+    match_pattern: String,
+    action: FormatAction,
+}
+
+/// Deserialization shape for `formatting.toml`: a list of `[[rule]]` tables.
+#[derive(Deserialize)]
+struct RulesFile {
+    rule: Vec<FormatRule>,
+}
+
+/// Built-in rules, used when no `formatting.toml` exists yet.
+fn default_rules() -> Vec<FormatRule> {
+    [
+        ("terminal", FormatAction::StripNewlines),
+        ("konsole", FormatAction::StripNewlines),
+        ("alacritty", FormatAction::StripNewlines),
+        ("iterm", FormatAction::StripNewlines),
+        ("slack", FormatAction::SplitSentences),
+        ("discord", FormatAction::SplitSentences),
+        ("telegram", FormatAction::SplitSentences),
+        ("code", FormatAction::CommentPrefix("// ".to_string())),
+    ]
+    .into_iter()
+    .map(|(match_pattern, action)| FormatRule {
+        match_pattern: match_pattern.to_string(),
+        action,
+    })
+    .collect()
+}
+
+/// Reshapes a finished transcript based on which application has focus.
+pub struct FormattingEngine {
+    rules: Vec<FormatRule>,
+}
+
+impl Default for FormattingEngine {
+    fn default() -> Self {
+        Self::load_or_default()
+    }
+}
+
+impl FormattingEngine {
+    /// Load `formatting.toml`, falling back to the built-in rule set if it
+    /// doesn't exist or fails to parse.
+    pub fn load_or_default() -> Self {
+        let path = AppPaths::formatting_rules_path();
+        let rules = if path.exists() {
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| toml::from_str::<RulesFile>(&content).ok())
+                .map(|file| file.rule)
+                .unwrap_or_else(default_rules)
+        } else {
+            default_rules()
+        };
+        Self { rules }
+    }
+
+    /// Action for the first rule whose `match_pattern` is contained in the
+    /// focused window's title or process name, or `None` if nothing
+    /// matches (or focus couldn't be determined).
+    fn active_action(&self, focus: Option<&FocusedWindow>) -> Option<&FormatAction> {
+        let focus = focus?;
+        let title = focus.title.to_lowercase();
+        let process = focus.process_name.to_lowercase();
+        self.rules
+            .iter()
+            .find(|r| {
+                let pattern = r.match_pattern.to_lowercase();
+                title.contains(&pattern) || process.contains(&pattern)
+            })
+            .map(|r| &r.action)
+    }
+
+    /// Reshapes `text` for whichever application `focus` identifies. Text
+    /// passes through unchanged if no rule matches.
+    pub fn apply(&self, text: &str, focus: Option<&FocusedWindow>) -> String {
+        match self.active_action(focus) {
+            Some(FormatAction::StripNewlines) => text.lines().collect::<Vec<_>>().join(" "),
+            Some(FormatAction::SplitSentences) => split_sentences(text),
+            Some(FormatAction::CommentPrefix(prefix)) => text
+                .lines()
+                .map(|line| format!("{}{}", prefix, line))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None => text.to_string(),
+        }
+    }
+}
+
+/// Breaks `text` after each sentence-final punctuation mark and puts what
+/// follows on its own line. `inject` has no notion of sending several
+/// separate messages, so this approximates "one message per sentence" as
+/// newline-separated lines the user can send one at a time rather than
+/// actually dispatching them individually.
+fn split_sentences(text: &str) -> String {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        if matches!(c, '.' | '!' | '?') {
+            let end = i + c.len_utf8();
+            let sentence = text[start..end].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence.to_string());
+            }
+            start = end;
+        }
+    }
+    let remainder = text[start..].trim();
+    if !remainder.is_empty() {
+        sentences.push(remainder.to_string());
+    }
+    sentences.join("\n")
+}
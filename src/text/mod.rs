@@ -0,0 +1,25 @@
+//! Post-correction text cleanup: Thai number formatting and
+//! punctuation/spacing normalization. Runs identically in every operating
+//! mode, right before `commands::CommandProcessor` sees the text, so
+//! command phrases are matched against the normalized words.
+
+pub mod diff;
+pub mod formatting;
+pub mod numbers;
+pub mod profanity;
+pub mod snippets;
+pub mod spacing;
+
+pub use profanity::ProfanityFilter;
+
+use crate::config::AppSettings;
+
+/// Applies every enabled cleanup pass to `text`.
+pub fn normalize(text: &str, settings: &AppSettings) -> String {
+    let text = numbers::normalize_numbers(text, settings.number_format);
+    if settings.normalize_punctuation_spacing {
+        spacing::normalize_spacing(&text)
+    } else {
+        text
+    }
+}
@@ -0,0 +1,217 @@
+//! Thai number normalization: spelled-out number words to/from digits, and
+//! digit-script conversion (Arabic `0-9` vs Thai `๐-๙`).
+//!
+//! Only 0-99 is handled as a single spelled-out compound word (the
+//! `เอ็ด`/`ยี่`/`สิบ` irregulars that make Thai counting tricky) — good
+//! enough for dictated dates, prices, and phone-adjacent numbers, the
+//! common case, without a full numeral-parsing grammar. Numbers spelled out
+//! past 99 ("หนึ่งพันสองร้อย") are left untouched.
+
+use crate::config::NumberFormat;
+
+const THAI_DIGITS: [char; 10] = ['๐', '๑', '๒', '๓', '๔', '๕', '๖', '๗', '๘', '๙'];
+const DIGIT_WORDS: [&str; 10] = [
+    "ศูนย์",
+    "หนึ่ง",
+    "สอง",
+    "สาม",
+    "สี่",
+    "ห้า",
+    "หก",
+    "เจ็ด",
+    "แปด",
+    "เก้า",
+];
+
+/// The standard spelled-out form of `n` (`0..=99`), e.g. `11` -> `"สิบเอ็ด"`,
+/// `20` -> `"ยี่สิบ"`, `23` -> `"ยี่สิบสาม"`.
+fn spelled_form(n: u32) -> String {
+    if n < 10 {
+        return DIGIT_WORDS[n as usize].to_string();
+    }
+
+    let tens = n / 10;
+    let unit = n % 10;
+    let mut word = match tens {
+        1 => "สิบ".to_string(),
+        2 => "ยี่สิบ".to_string(),
+        t => format!("{}สิบ", DIGIT_WORDS[t as usize]),
+    };
+    match unit {
+        0 => {}
+        1 => word.push_str("เอ็ด"),
+        u => word.push_str(DIGIT_WORDS[u as usize]),
+    }
+    word
+}
+
+/// `(spelled word, value)` for `0..=99`, longest word first so replacing
+/// e.g. `"สิบเอ็ด"` (11) happens before `"สิบ"` (10) would otherwise match
+/// as a substring of it.
+fn word_table() -> Vec<(String, u32)> {
+    let mut table: Vec<(String, u32)> = (0..=99).map(|n| (spelled_form(n), n)).collect();
+    table.sort_by(|a, b| b.0.chars().count().cmp(&a.0.chars().count()));
+    table
+}
+
+/// True for characters in the Thai Unicode block (`U+0E00`-`U+0E7F`), used
+/// as a crude word-boundary check: a spelled-out number word is only
+/// replaced where it isn't glued to more Thai script on either side, since
+/// Thai has no spaces between words and e.g. "สาม" (3) is a substring of
+/// unrelated words like "ข้าวต้มสามกษัตริย์" (a dish name). This can miss a
+/// real number that's written flush against neighboring Thai text with no
+/// separator, but that's a safer failure mode for a dictation app than
+/// silently corrupting an unrelated word.
+fn is_thai_char(c: char) -> bool {
+    ('\u{0E00}'..='\u{0E7F}').contains(&c)
+}
+
+fn words_to_digits(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let table = word_table();
+    let mut out = String::with_capacity(text.len());
+    let mut i = 0;
+
+    'outer: while i < chars.len() {
+        let prev_is_thai = i > 0 && is_thai_char(chars[i - 1]);
+        if !prev_is_thai {
+            for (word, value) in &table {
+                let word_len = word.chars().count();
+                if i + word_len > chars.len() {
+                    continue;
+                }
+                if chars[i..i + word_len].iter().collect::<String>() != *word {
+                    continue;
+                }
+                let next_is_thai = i + word_len < chars.len() && is_thai_char(chars[i + word_len]);
+                if next_is_thai {
+                    continue;
+                }
+                out.push_str(&value.to_string());
+                i += word_len;
+                continue 'outer;
+            }
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+
+    out
+}
+
+fn digit_value(c: char) -> Option<u32> {
+    arabic_digit_value(c).or_else(|| thai_digit_value(c))
+}
+
+/// Replaces every maximal run of 1-2 ASCII or Thai digits with its
+/// spelled-out Thai form. Longer runs (100+) are left untouched — see the
+/// module doc comment.
+fn digits_to_words(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let run_start = i;
+        while i < chars.len() && digit_value(chars[i]).is_some() {
+            i += 1;
+        }
+        let run_len = i - run_start;
+
+        if run_len == 1 || run_len == 2 {
+            let n = chars[run_start..i]
+                .iter()
+                .fold(0u32, |acc, &c| acc * 10 + digit_value(c).unwrap());
+            out.push_str(&spelled_form(n));
+        } else if run_len > 2 {
+            out.extend(&chars[run_start..i]);
+        } else {
+            out.push(chars[run_start]);
+            i += 1;
+        }
+    }
+
+    out
+}
+
+fn arabic_digit_value(c: char) -> Option<u32> {
+    c.to_digit(10)
+}
+
+fn thai_digit_value(c: char) -> Option<u32> {
+    THAI_DIGITS.iter().position(|&d| d == c).map(|i| i as u32)
+}
+
+fn convert_digit_script(text: &str, to_thai: bool) -> String {
+    text.chars()
+        .map(|c| {
+            if to_thai {
+                arabic_digit_value(c)
+                    .map(|d| THAI_DIGITS[d as usize])
+                    .unwrap_or(c)
+            } else {
+                thai_digit_value(c)
+                    .map(|d| std::char::from_digit(d, 10).unwrap())
+                    .unwrap_or(c)
+            }
+        })
+        .collect()
+}
+
+/// Normalizes numbers in `text` per `format`: converts spelled-out words to
+/// digits (or digits to spelled-out words for `NumberFormat::SpelledOut`),
+/// then settles on the requested digit script.
+pub fn normalize_numbers(text: &str, format: NumberFormat) -> String {
+    match format {
+        NumberFormat::Arabic => convert_digit_script(&words_to_digits(text), false),
+        NumberFormat::Thai => convert_digit_script(&words_to_digits(text), true),
+        NumberFormat::SpelledOut => digits_to_words(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leaves_number_word_glued_inside_an_unrelated_thai_word_untouched() {
+        // "สาม" (3) is a substring of "ข้าวต้มสามกษัตริย์", a dish name —
+        // it's flush against Thai script on both sides, so it must not
+        // convert.
+        let out = normalize_numbers("ข้าวต้มสามกษัตริย์", NumberFormat::Arabic);
+        assert_eq!(out, "ข้าวต้มสามกษัตริย์");
+    }
+
+    #[test]
+    fn converts_a_number_word_set_off_by_spaces() {
+        // Neighboring whitespace (not Thai script) on both sides means the
+        // boundary heuristic allows the match.
+        let out = normalize_numbers("มี สาม คน", NumberFormat::Arabic);
+        assert_eq!(out, "มี 3 คน");
+    }
+
+    #[test]
+    fn converts_the_longest_matching_compound_before_a_shorter_prefix() {
+        // "สิบเอ็ด" (11) must win over "สิบ" (10) matching as a prefix.
+        let out = normalize_numbers("สิบเอ็ด", NumberFormat::Arabic);
+        assert_eq!(out, "11");
+    }
+
+    #[test]
+    fn digits_to_words_round_trips_a_two_digit_number() {
+        let out = normalize_numbers("23", NumberFormat::SpelledOut);
+        assert_eq!(out, "ยี่สิบสาม");
+    }
+
+    #[test]
+    fn digits_to_words_leaves_runs_longer_than_two_digits_untouched() {
+        let out = normalize_numbers("2026", NumberFormat::SpelledOut);
+        assert_eq!(out, "2026");
+    }
+
+    #[test]
+    fn converts_digit_script_to_thai() {
+        let out = normalize_numbers("มี3คน", NumberFormat::Thai);
+        assert_eq!(out, "มี๓คน");
+    }
+}
@@ -0,0 +1,89 @@
+//! Masks profane or sensitive words before injection, using a user-editable
+//! wordlist at `AppPaths::blocklist_path()` (one word per line, `#` for
+//! comments). Absent by default — masking is opt-in; a user who wants it
+//! creates the file themselves. Matching is whole-word and
+//! case-insensitive, bounded by non-alphanumeric characters on both sides,
+//! so it reliably catches English words and Thai words that stand at a
+//! space/punctuation boundary, but — like the rest of Whisper's Thai
+//! output — won't split a blocked word out of a longer unspaced Thai
+//! compound.
+
+use crate::config::AppPaths;
+
+/// Character each letter of a masked word is replaced with, so its length
+/// stays visible without revealing what it said.
+const MASK_CHAR: char = '*';
+
+pub struct ProfanityFilter {
+    words: Vec<String>,
+}
+
+impl Default for ProfanityFilter {
+    fn default() -> Self {
+        Self::load()
+    }
+}
+
+impl ProfanityFilter {
+    /// Load `AppPaths::blocklist_path()`, or an empty (no-op) filter if it
+    /// doesn't exist.
+    pub fn load() -> Self {
+        let words = std::fs::read_to_string(AppPaths::blocklist_path())
+            .map(|content| {
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { words }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.words.is_empty()
+    }
+
+    /// Replaces every whole-word, case-insensitive match of a blocked word
+    /// with `MASK_CHAR` repeated to its length. A no-op when no wordlist
+    /// was found.
+    pub fn mask(&self, text: &str) -> String {
+        let mut masked = text.to_string();
+        for word in &self.words {
+            masked = mask_word(&masked, word);
+        }
+        masked
+    }
+}
+
+fn is_boundary(c: Option<char>) -> bool {
+    c.map_or(true, |c| !c.is_alphanumeric())
+}
+
+fn mask_word(text: &str, word: &str) -> String {
+    if word.is_empty() {
+        return text.to_string();
+    }
+
+    let lower_text = text.to_lowercase();
+    let lower_word = word.to_lowercase();
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    let mut search_start = 0;
+
+    while let Some(offset) = lower_text[search_start..].find(&lower_word) {
+        let start = search_start + offset;
+        let end = start + lower_word.len();
+        search_start = end;
+
+        if is_boundary(text[..start].chars().next_back()) && is_boundary(text[end..].chars().next())
+        {
+            result.push_str(&text[last..start]);
+            result.push_str(&MASK_CHAR.to_string().repeat(word.chars().count()));
+            last = end;
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
@@ -0,0 +1,67 @@
+//! Spoken snippet/macro expansion: lets the user define short trigger
+//! phrases ("ลายเซ็น") that expand into a longer block of boilerplate text
+//! (an email signature, an address) after correction, the same way
+//! `commands::CommandProcessor` expands spoken phrases into edits.
+
+use std::collections::HashMap;
+
+use crate::config::AppPaths;
+
+/// Loads the trigger → expansion map and rewrites transcripts before
+/// injection. Empty (a no-op) until the user creates `snippets.toml`.
+pub struct SnippetExpander {
+    snippets: HashMap<String, String>,
+}
+
+impl Default for SnippetExpander {
+    fn default() -> Self {
+        Self::load_or_default()
+    }
+}
+
+impl SnippetExpander {
+    /// Load `snippets.toml`, falling back to an empty map if it doesn't
+    /// exist or fails to parse.
+    pub fn load_or_default() -> Self {
+        let path = AppPaths::snippets_path();
+        let snippets = if path.exists() {
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| toml::from_str::<HashMap<String, String>>(&content).ok())
+                .unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        Self { snippets }
+    }
+
+    /// Rewrite `text`, replacing every recognized trigger with its
+    /// expansion. Unrecognized text passes through unchanged.
+    pub fn apply(&self, text: &str) -> String {
+        let mut output = String::with_capacity(text.len());
+        let mut remaining = text;
+
+        while let Some((start, end, expansion)) = self.find_next_snippet(remaining) {
+            output.push_str(&remaining[..start]);
+            output.push_str(expansion);
+            remaining = &remaining[end..];
+        }
+        output.push_str(remaining);
+        output
+    }
+
+    /// Find the earliest-occurring configured trigger in `text`, returning
+    /// its byte range and expansion. Case-insensitive so "ลายเซ็น" matches
+    /// regardless of the surrounding words' capitalization.
+    fn find_next_snippet(&self, text: &str) -> Option<(usize, usize, &str)> {
+        let lower = text.to_lowercase();
+        self.snippets
+            .iter()
+            .filter_map(|(trigger, expansion)| {
+                lower
+                    .find(&trigger.to_lowercase())
+                    .map(|start| (start, start + trigger.len(), expansion.as_str()))
+            })
+            .min_by_key(|(start, _, _)| *start)
+    }
+}
@@ -0,0 +1,63 @@
+//! Whitespace and punctuation cleanup: collapses runs of whitespace, drops
+//! the space before sentence punctuation, and inserts a space at Thai/Latin
+//! script boundaries (Whisper often runs "Wordคำ" together with no
+//! separator).
+
+const PUNCTUATION: [char; 6] = ['.', ',', '!', '?', ':', ';'];
+
+fn is_thai(c: char) -> bool {
+    ('\u{0E01}'..='\u{0E5B}').contains(&c)
+}
+
+fn is_latin_alnum(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+}
+
+/// Normalizes `text`'s whitespace and script-boundary spacing.
+pub fn normalize_spacing(text: &str) -> String {
+    let collapsed = collapse_whitespace(text);
+    insert_script_boundaries(&collapsed)
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut pending_space = false;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            pending_space = true;
+            continue;
+        }
+        if PUNCTUATION.contains(&c) {
+            pending_space = false;
+            out.push(c);
+            continue;
+        }
+        if pending_space && !out.is_empty() {
+            out.push(' ');
+        }
+        pending_space = false;
+        out.push(c);
+    }
+
+    out
+}
+
+fn insert_script_boundaries(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 8);
+    let mut prev: Option<char> = None;
+
+    for c in text.chars() {
+        if let Some(p) = prev {
+            let crosses_boundary =
+                (is_thai(p) && is_latin_alnum(c)) || (is_latin_alnum(p) && is_thai(c));
+            if crosses_boundary {
+                out.push(' ');
+            }
+        }
+        out.push(c);
+        prev = Some(c);
+    }
+
+    out
+}
@@ -0,0 +1,219 @@
+//! Opt-in check against the GitHub releases API for a newer build than the
+//! one currently running, surfaced in the settings panel and title bar
+//! instead of the widget silently going stale. Off by default —
+//! `AppSettings::check_for_updates` — since it's the one outbound network
+//! call an otherwise offline-first tool makes.
+
+use serde::Deserialize;
+
+/// GitHub API endpoint for the latest published (non-prerelease,
+/// non-draft) release of this project.
+const RELEASES_URL: &str = "https://api.github.com/repos/mark10112/voice-to-text/releases/latest";
+
+/// A newer release than the one currently running.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdateInfo {
+    /// Tag name with any leading `v` stripped, e.g. `"0.2.0"`.
+    pub version: String,
+    /// Release notes body, shown verbatim in the notification.
+    pub changelog: String,
+    /// Direct download URL for the asset matching this platform, if the
+    /// release publishes one. `None` sends the user to the releases page
+    /// instead of downloading in-app.
+    pub download_url: Option<String>,
+    /// Expected SHA-256 hex digest for `download_url`'s asset, from
+    /// GitHub's own `digest` field on the release asset. `download_update`
+    /// refuses to run an in-app download that doesn't carry one rather than
+    /// installing an unverified binary — see its doc comment.
+    pub download_sha256: Option<String>,
+}
+
+/// Where the background check (or download) currently stands, read by the
+/// settings panel and title-bar badge each frame.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpdateStatus {
+    Idle,
+    Checking,
+    UpToDate,
+    Available(UpdateInfo),
+    Downloading,
+    /// Saved to this path; the user still has to close the widget and run
+    /// it themselves — this crate doesn't self-replace a running binary.
+    Downloaded(std::path::PathBuf),
+    Error(String),
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+    /// `"sha256:<hex>"` (or another algorithm's prefix) — GitHub computes
+    /// this itself on upload, so it's a digest we didn't have to trust the
+    /// release pipeline to also get right. Absent on assets uploaded before
+    /// GitHub added the field.
+    #[serde(default)]
+    digest: Option<String>,
+}
+
+/// Parses a version string like `"v1.2.3"` or `"1.2.3"` into comparable
+/// numeric parts, treating missing/non-numeric parts as `0` so a malformed
+/// tag never panics — worst case it just compares equal to nothing.
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse::<u32>().unwrap_or(0))
+        .collect()
+}
+
+/// True if `remote` is a strictly newer version than `current`.
+fn is_newer(remote: &str, current: &str) -> bool {
+    parse_version(remote) > parse_version(current)
+}
+
+/// Extracts the hex digest from a GitHub asset's `digest` field, which is
+/// `"<algorithm>:<hex>"` — `None` for any algorithm other than `sha256`
+/// (GitHub also supports `sha512`) since that's what `stt::sha256` can
+/// check against.
+fn parse_sha256_digest(digest: &str) -> Option<String> {
+    digest.strip_prefix("sha256:").map(|hex| hex.to_lowercase())
+}
+
+/// The platform-specific substring expected in a release asset's file name,
+/// e.g. `voice-to-text-linux-x86_64.tar.gz`.
+fn platform_tag() -> &'static str {
+    match std::env::consts::OS {
+        "windows" => "windows",
+        "macos" => "macos",
+        _ => "linux",
+    }
+}
+
+/// Queries `RELEASES_URL` and reports whether it's newer than
+/// `current_version` (typically `env!("CARGO_PKG_VERSION")`).
+pub async fn check_for_update(current_version: &str) -> Result<Option<UpdateInfo>, String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(RELEASES_URL)
+        // GitHub's API rejects requests with no User-Agent header.
+        .header("User-Agent", "voice-to-text-updater")
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("GitHub API returned {}", response.status()));
+    }
+
+    let release: GithubRelease = response.json().await.map_err(|e| e.to_string())?;
+    let version = release.tag_name.trim_start_matches('v').to_string();
+
+    if !is_newer(&version, current_version) {
+        return Ok(None);
+    }
+
+    let asset = release
+        .assets
+        .into_iter()
+        .find(|a| a.name.contains(platform_tag()));
+    let download_sha256 = asset
+        .as_ref()
+        .and_then(|a| a.digest.as_deref())
+        .and_then(parse_sha256_digest);
+    let download_url = asset.map(|a| a.browser_download_url);
+
+    Ok(Some(UpdateInfo {
+        version,
+        changelog: release.body,
+        download_url,
+        download_sha256,
+    }))
+}
+
+/// Downloads `info.download_url` into memory, verifies it against
+/// `info.download_sha256`, and only on a match writes it to `dest`,
+/// overwriting anything already there — this ends with the user running the file, so an
+/// unverified binary is treated the same as a corrupt model download
+/// (`stt::model::verify_integrity`): refused rather than silently trusted.
+/// Releases GitHub hasn't computed a digest for (uploaded before GitHub
+/// added the field) can't be verified in-app at all; those fail closed
+/// here rather than downloading unverified, same as `control::socket`
+/// failing closed when it has nowhere safe to put its socket. Callers are
+/// responsible for making the verified result executable and prompting
+/// the user to restart into it.
+pub async fn download_update(info: &UpdateInfo, dest: &std::path::Path) -> Result<(), String> {
+    let url = info
+        .download_url
+        .as_ref()
+        .ok_or_else(|| "This release has no asset for this platform".to_string())?;
+    let expected_sha256 = info.download_sha256.as_ref().ok_or_else(|| {
+        "This release's asset has no published checksum to verify against — download it \
+         manually from the releases page instead"
+            .to_string()
+    })?;
+
+    let response = reqwest::Client::new()
+        .get(url)
+        .header("User-Agent", "voice-to-text-updater")
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("Download returned {}", response.status()));
+    }
+
+    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    let actual_sha256 = crate::stt::sha256::hash_bytes(&bytes);
+    if &actual_sha256 != expected_sha256 {
+        return Err(format!(
+            "Downloaded file's sha256 {} does not match expected {} — refusing to save it",
+            actual_sha256, expected_sha256
+        ));
+    }
+
+    tokio::fs::write(dest, &bytes)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_newer_compares_numeric_parts_ignoring_a_leading_v() {
+        assert!(is_newer("v0.3.0", "0.2.9"));
+        assert!(is_newer("1.0.0", "0.9.9"));
+        assert!(!is_newer("0.2.0", "0.2.0"));
+        assert!(!is_newer("0.1.9", "0.2.0"));
+    }
+
+    #[test]
+    fn is_newer_treats_a_malformed_tag_as_all_zeros_rather_than_panicking() {
+        assert!(!is_newer("not-a-version", "0.0.1"));
+        assert!(is_newer("0.0.1", "not-a-version"));
+    }
+
+    #[test]
+    fn parse_sha256_digest_extracts_and_lowercases_the_hex() {
+        assert_eq!(
+            parse_sha256_digest("sha256:ABCDEF0123"),
+            Some("abcdef0123".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_sha256_digest_rejects_other_algorithms() {
+        assert_eq!(parse_sha256_digest("sha512:abcdef"), None);
+    }
+}